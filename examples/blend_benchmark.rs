@@ -0,0 +1,51 @@
+//! Measures the hot loops of the canvas: translucent and opaque fills and converting the buffer for presenting.
+//!
+//! Run with `cargo run --release --example blend_benchmark`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use drawing_stuff::canvas::Canvas;
+use drawing_stuff::color::{RGB, RGBA};
+
+const WIDTH: usize = 1920;
+const HEIGHT: usize = 1080;
+const ITERATIONS: u32 = 100;
+
+fn measure<F: FnMut()>(name: &str, mut f: F) {
+    f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_pixel =
+        start.elapsed().as_nanos() as f64 / (ITERATIONS as usize * WIDTH * HEIGHT) as f64;
+    println!("{name:<28} {per_pixel:>6.3} ns/pixel");
+}
+
+fn main() {
+    let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    let translucent = RGBA::new(200, 100, 50, 128);
+    let opaque = RGBA::new(200, 100, 50, 255);
+
+    measure("fill_rect translucent", || {
+        canvas.fill_rect(0, 0, WIDTH, HEIGHT, black_box(translucent));
+    });
+    measure("fill_rect opaque", || {
+        canvas.fill_rect(0, 0, WIDTH, HEIGHT, black_box(opaque));
+    });
+
+    // the per-pixel floating point interpolation blending used to be based on
+    let mut pixels = vec![RGB { r: 0, g: 0, b: 0 }; WIDTH * HEIGHT];
+    measure("f64 lerp (reference)", || {
+        let (color, alpha) = black_box(translucent).to_rgb();
+        for pixel in pixels.iter_mut() {
+            *pixel = pixel.lerp(&color, alpha as f64 / 255.0);
+        }
+    });
+
+    let mut buffer = vec![0u32; WIDTH * HEIGHT];
+    measure("buffer_u32_into", || {
+        canvas.buffer_u32_into(black_box(&mut buffer));
+    });
+}
@@ -0,0 +1,303 @@
+use crate::canvas::{Canvas, Rect};
+use crate::color::{Channel, RGB};
+
+impl Canvas {
+    /// Balances the colors of the canvas so that a region showing something neutral (e.g. a gray card) becomes gray.
+    ///
+    /// Returns `None` if the region is not inside the canvas or one of its average channels is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 140, g: 120, b: 100 });
+    ///
+    /// canvas.white_balance_from_region(Rect::new(0, 0, 50, 50));
+    /// assert_eq!(Some(&RGB { r: 120, g: 120, b: 120 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn white_balance_from_region(&mut self, rect: Rect) -> Option<()> {
+        let (x, y, w, h) = rect.clip(self.width(), self.height())?;
+
+        let mut sum = [0u64; 3];
+        for row in self.buffer().chunks(self.width()).skip(y).take(h) {
+            for pixel in &row[x..x + w] {
+                sum[0] += pixel.r as u64;
+                sum[1] += pixel.g as u64;
+                sum[2] += pixel.b as u64;
+            }
+        }
+
+        if sum.contains(&0) {
+            return None;
+        }
+
+        let gray = (sum[0] + sum[1] + sum[2]) as f64 / 3.0;
+        let table = |sum: u64| {
+            let gain = gray / sum as f64;
+            let mut table = [0u8; 256];
+            for (i, v) in table.iter_mut().enumerate() {
+                *v = (i as f64 * gain).round().min(255.0) as u8;
+            }
+            table
+        };
+
+        self.apply_lut(&table(sum[0]), &table(sum[1]), &table(sum[2]));
+        Some(())
+    }
+
+    /// Stretches the colors of the canvas so that `black` becomes black and `white` becomes white.
+    ///
+    /// Values outside of the range are clipped. Does nothing if `black` is not smaller than `white`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 20, g: 120, b: 220 });
+    ///
+    /// canvas.levels(20, 220);
+    /// assert_eq!(Some(&RGB { r: 0, g: 128, b: 255 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn levels(&mut self, black: u8, white: u8) {
+        if black >= white {
+            return;
+        }
+
+        let table = levels_table(black, white);
+        self.apply_lut(&table, &table, &table);
+    }
+
+    /// Stretches each channel of the canvas to the full range by detecting its black and white point.
+    ///
+    /// `clip` is the fraction of pixels (between `0.0` and `0.5`) allowed to be clipped at each end,
+    /// which prevents single outliers from dominating the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.auto_levels(0.01);
+    /// ```
+    pub fn auto_levels(&mut self, clip: f32) {
+        let mut histograms = [[0usize; 256]; 3];
+        for pixel in self.buffer().iter() {
+            histograms[0][pixel.r as usize] += 1;
+            histograms[1][pixel.g as usize] += 1;
+            histograms[2][pixel.b as usize] += 1;
+        }
+
+        let clipped = (self.buffer().len() as f32 * clip.clamp(0.0, 0.5)) as usize;
+        let tables = histograms.map(|histogram| {
+            let mut count = 0;
+            let black = histogram
+                .iter()
+                .position(|&n| {
+                    count += n;
+                    count > clipped
+                })
+                .unwrap_or(0);
+
+            let mut count = 0;
+            let white = 255
+                - histogram
+                    .iter()
+                    .rev()
+                    .position(|&n| {
+                        count += n;
+                        count > clipped
+                    })
+                    .unwrap_or(0);
+
+            match black < white {
+                true => levels_table(black as u8, white as u8),
+                false => std::array::from_fn(|i| i as u8),
+            }
+        });
+
+        self.apply_lut(&tables[0], &tables[1], &tables[2]);
+    }
+
+    /// Returns a grayscale copy of the canvas.
+    ///
+    /// Every pixel is replaced by its luma, weighting the channels by how bright they appear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 0, g: 255, b: 0 });
+    ///
+    /// let gray = canvas.to_grayscale();
+    /// assert_eq!(Some(&RGB { r: 182, g: 182, b: 182 }), gray.get_at(200, 100));
+    /// ```
+    pub fn to_grayscale(&self) -> Canvas {
+        let mut canvas = self.clone();
+        canvas.desaturate(1.0);
+        canvas
+    }
+
+    /// Moves the colors of the canvas towards their luma by `amount` between `0.0` (unchanged) and `1.0` (grayscale).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 255, g: 0, b: 0 });
+    ///
+    /// canvas.desaturate(0.5);
+    /// assert_eq!(Some(&RGB { r: 155, g: 27, b: 27 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn desaturate(&mut self, amount: f32) {
+        let amount = amount.clamp(0.0, 1.0);
+        if amount == 0.0 {
+            return;
+        }
+
+        for pixel in self.buffer_mut().iter_mut() {
+            let luma = 0.2126 * pixel.r as f32 + 0.7152 * pixel.g as f32 + 0.0722 * pixel.b as f32;
+            let mix = |v: u8| (v as f32 + (luma - v as f32) * amount).round() as u8;
+            pixel.r = mix(pixel.r);
+            pixel.g = mix(pixel.g);
+            pixel.b = mix(pixel.b);
+        }
+    }
+
+    /// Inverts the colors inside of a region of the canvas, e.g. to highlight a selection.
+    ///
+    /// Parts of the region laying outside of the canvas are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 20, g: 120, b: 220 });
+    ///
+    /// canvas.invert(Rect::new(100, 50, 300, 200));
+    /// assert_eq!(Some(&RGB { r: 235, g: 135, b: 35 }), canvas.get_at(200, 100));
+    /// assert_eq!(Some(&RGB { r: 20, g: 120, b: 220 }), canvas.get_at(500, 100));
+    /// ```
+    pub fn invert(&mut self, rect: Rect) {
+        self.map_region(rect, |pixel| {
+            pixel.r = 255 - pixel.r;
+            pixel.g = 255 - pixel.g;
+            pixel.b = 255 - pixel.b;
+        });
+    }
+
+    /// Moves the colors inside of a region of the canvas towards a color by `strength` between `0.0` and `1.0`.
+    ///
+    /// Parts of the region laying outside of the canvas are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.tint(Rect::new(100, 50, 300, 200), RGB { r: 0, g: 120, b: 255 }, 0.25);
+    /// assert_eq!(Some(&RGB { r: 0, g: 30, b: 64 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn tint(&mut self, rect: Rect, color: RGB, strength: f32) {
+        let strength = strength.clamp(0.0, 1.0);
+        self.map_region(rect, |pixel| {
+            let mix = |v: u8, c: u8| (v as f32 + (c as f32 - v as f32) * strength).round() as u8;
+            pixel.r = mix(pixel.r, color.r);
+            pixel.g = mix(pixel.g, color.g);
+            pixel.b = mix(pixel.b, color.b);
+        });
+    }
+
+    /// Multiplies a single channel inside of a region of the canvas by a factor, clipping the result.
+    ///
+    /// Parts of the region laying outside of the canvas are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::{Channel, RGB};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 100, g: 100, b: 100 });
+    ///
+    /// canvas.multiply_channel(Rect::new(100, 50, 300, 200), Channel::Red, 1.5);
+    /// assert_eq!(Some(&RGB { r: 150, g: 100, b: 100 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn multiply_channel(&mut self, rect: Rect, channel: Channel, factor: f32) {
+        let factor = factor.max(0.0);
+        self.map_region(rect, |pixel| {
+            let value = match channel {
+                Channel::Red => &mut pixel.r,
+                Channel::Green => &mut pixel.g,
+                Channel::Blue => &mut pixel.b,
+            };
+            *value = (*value as f32 * factor).round().min(255.0) as u8;
+        });
+    }
+
+    /// Calls a function for every pixel inside of a region of the canvas.
+    fn map_region<F>(&mut self, rect: Rect, f: F)
+    where
+        F: Fn(&mut RGB) + Send + Sync,
+    {
+        let Some((x, y, w, h)) = rect.clip(self.width(), self.height()) else {
+            return;
+        };
+
+        self.for_each_row_mut(y..y + h, |_, row| row[x..x + w].iter_mut().for_each(&f));
+    }
+}
+
+/// Computes a lookup table mapping `black..=white` onto the full range.
+fn levels_table(black: u8, white: u8) -> [u8; 256] {
+    let range = (white - black) as f32;
+    std::array::from_fn(|i| {
+        ((i as f32 - black as f32) / range * 255.0)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    })
+}
@@ -0,0 +1,578 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::canvas::Canvas;
+use crate::color::{RGB, RGBA};
+use crate::error::CanvasError;
+use crate::gif;
+use crate::png;
+
+/// A single captured frame of an animation.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub canvas: Canvas,
+
+    /// Point in time the frame was captured at in seconds.
+    pub timestamp: f64,
+}
+
+/// How frames get combined when resampling a sequence to a constant frame rate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resample {
+    /// Uses the latest frame captured at or before each output time, dropping or duplicating frames as needed.
+    Nearest,
+    /// Linearly blends the two frames surrounding each output time.
+    Blend,
+}
+
+/// Resamples a sequence of timestamped frames to a constant frame rate.
+///
+/// The frames have to be sorted by their timestamps.
+/// The output starts at the timestamp of the first frame and ends at the timestamp of the last one.
+/// Blending frames of different sizes falls back to [`Resample::Nearest`].
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::animation::{resample, Frame, Resample};
+/// use drawing_stuff::canvas::Canvas;
+///
+/// let frames = vec![
+///     Frame { canvas: Canvas::new(64, 64), timestamp: 0.0 },
+///     Frame { canvas: Canvas::new(64, 64), timestamp: 0.07 },
+///     Frame { canvas: Canvas::new(64, 64), timestamp: 0.25 },
+/// ];
+///
+/// let resampled = resample(&frames, 20.0, Resample::Blend);
+/// assert_eq!(6, resampled.len());
+/// ```
+pub fn resample(frames: &[Frame], fps: f64, mode: Resample) -> Vec<Canvas> {
+    let (Some(first), Some(last)) = (frames.first(), frames.last()) else {
+        return Vec::new();
+    };
+    if fps <= 0.0 {
+        return Vec::new();
+    }
+
+    let duration = last.timestamp - first.timestamp;
+    let count = (duration * fps + 1e-9).floor() as usize + 1;
+
+    let mut output = Vec::with_capacity(count);
+    let mut index = 0;
+    for i in 0..count {
+        let time = first.timestamp + i as f64 / fps;
+
+        while index + 1 < frames.len() && frames[index + 1].timestamp <= time {
+            index += 1;
+        }
+
+        let current = &frames[index];
+        let next = frames.get(index + 1);
+
+        let frame = match (mode, next) {
+            (Resample::Blend, Some(next))
+                if next.canvas.width() == current.canvas.width()
+                    && next.canvas.height() == current.canvas.height()
+                    && next.timestamp > current.timestamp =>
+            {
+                let t = (time - current.timestamp) / (next.timestamp - current.timestamp);
+                blend_frames(&current.canvas, &next.canvas, t)
+            }
+            _ => current.canvas.clone(),
+        };
+
+        output.push(frame);
+    }
+
+    output
+}
+
+/// Blends two equally sized canvases.
+fn blend_frames(a: &Canvas, b: &Canvas, t: f64) -> Canvas {
+    let mut canvas = a.clone();
+    for (pixel, other) in canvas.buffer_mut().iter_mut().zip(b.buffer()) {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        *pixel = RGB {
+            r: mix(pixel.r, other.r),
+            g: mix(pixel.g, other.g),
+            b: mix(pixel.b, other.b),
+        };
+    }
+    canvas
+}
+
+/// Accumulates successive frames into a weighted average, e.g. for motion blur
+/// or progressive refinement of stochastic renders.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::animation::Accumulator;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::RGB;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut accumulator = Accumulator::new(WIDTH, HEIGHT);
+///
+/// let mut frame = Canvas::new(WIDTH, HEIGHT);
+/// accumulator.accumulate(&frame, 1.0);
+/// frame.fill(RGB { r: 200, g: 200, b: 200 });
+/// accumulator.accumulate(&frame, 1.0);
+///
+/// let blurred = accumulator.resolve();
+/// assert_eq!(Some(&RGB { r: 100, g: 100, b: 100 }), blurred.get_at(200, 100));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Accumulator {
+    width: usize,
+    height: usize,
+
+    sum: Vec<[f32; 3]>,
+    weight: f32,
+}
+
+impl Accumulator {
+    /// Creates a new empty accumulator for frames of the specified size.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            sum: vec![[0.0; 3]; width * height],
+            weight: 0.0,
+        }
+    }
+
+    /// Returns the width of the accumulated frames.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the accumulated frames.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the total weight accumulated so far.
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    /// Adds a frame with the specified weight.
+    ///
+    /// Returns `None` if the size of the frame differs from the accumulator.
+    pub fn accumulate(&mut self, frame: &Canvas, weight: f32) -> Option<()> {
+        if frame.width() != self.width || frame.height() != self.height {
+            return None;
+        }
+
+        for (sum, pixel) in self.sum.iter_mut().zip(frame.buffer()) {
+            sum[0] += pixel.r as f32 * weight;
+            sum[1] += pixel.g as f32 * weight;
+            sum[2] += pixel.b as f32 * weight;
+        }
+        self.weight += weight;
+        Some(())
+    }
+
+    /// Scales down everything accumulated so far by a factor between 0 and 1.
+    ///
+    /// Calling this before every [`Accumulator::accumulate`] turns the average into an exponential decay
+    /// where older frames fade out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::animation::Accumulator;
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// let mut trail = Accumulator::new(64, 64);
+    ///
+    /// // every frame
+    /// let frame = Canvas::new(64, 64);
+    /// trail.decay(0.8);
+    /// trail.accumulate(&frame, 1.0);
+    /// let output = trail.resolve();
+    /// ```
+    pub fn decay(&mut self, factor: f32) {
+        let factor = factor.clamp(0.0, 1.0);
+        for sum in self.sum.iter_mut() {
+            sum[0] *= factor;
+            sum[1] *= factor;
+            sum[2] *= factor;
+        }
+        self.weight *= factor;
+    }
+
+    /// Discards everything accumulated so far.
+    pub fn clear(&mut self) {
+        self.sum.fill([0.0; 3]);
+        self.weight = 0.0;
+    }
+
+    /// Returns the weighted average of all accumulated frames.
+    ///
+    /// The result is black if nothing has been accumulated yet.
+    pub fn resolve(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        if self.weight <= 0.0 {
+            return canvas;
+        }
+
+        let to_u8 = |v: f32| (v / self.weight).round().clamp(0.0, 255.0) as u8;
+        for (pixel, sum) in canvas.buffer_mut().iter_mut().zip(&self.sum) {
+            *pixel = RGB {
+                r: to_u8(sum[0]),
+                g: to_u8(sum[1]),
+                b: to_u8(sum[2]),
+            };
+        }
+        canvas
+    }
+}
+
+/// Onion-skinning settings, showing faded copies of previous frames under the current one
+/// while authoring frame-by-frame animations.
+///
+/// As canvases have no transparency, pixels of the current frame matching the background color count as empty
+/// and let the faded previous frames show through.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::animation::OnionSkin;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{BLACK, WHITE};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut frames = Vec::new();
+/// for i in 0..3 {
+///     let mut frame = Canvas::new(WIDTH, HEIGHT);
+///     frame.draw_circle_solid(200 + i * 100, 100, 30, WHITE);
+///     frames.push(frame);
+/// }
+///
+/// let onion = OnionSkin { layers: 2, opacity: 0.5, background: BLACK.to_rgb().0 };
+/// let (current, previous) = frames.split_last().unwrap();
+/// let preview = onion.apply(previous, current);
+///
+/// assert_eq!(255, preview.get_at(400, 100).unwrap().r); // current frame
+/// assert_eq!(128, preview.get_at(300, 100).unwrap().r); // previous frame
+/// assert_eq!(64, preview.get_at(200, 100).unwrap().r); // frame before
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnionSkin {
+    /// Number of previous frames shown.
+    pub layers: usize,
+    /// Opacity of the closest previous frame, every older frame is faded by this factor again.
+    pub opacity: f32,
+
+    /// Color of the empty parts of the frames.
+    pub background: RGB,
+}
+
+impl OnionSkin {
+    /// Composites the last [`OnionSkin::layers`] of the previous frames, given from oldest to newest, under the current frame.
+    ///
+    /// Previous frames with a size different from the current frame are skipped.
+    pub fn apply(&self, previous: &[Canvas], current: &Canvas) -> Canvas {
+        let mut output = current.clone();
+        let start = previous.len().saturating_sub(self.layers);
+
+        // newer frames are drawn last to end up on top of older ones
+        for (i, frame) in previous[start..].iter().enumerate() {
+            if frame.width() != current.width() || frame.height() != current.height() {
+                continue;
+            }
+
+            let age = (previous.len() - start - i) as i32;
+            let alpha = (self.opacity.clamp(0.0, 1.0).powi(age) * 255.0).round() as u8;
+            let pixels = output.buffer_mut().iter_mut().zip(current.buffer());
+            for ((pixel, &empty), &ghost) in pixels.zip(frame.buffer()) {
+                if empty == self.background && ghost != self.background {
+                    *pixel = pixel.add_rgba(RGBA::new(ghost.r, ghost.g, ghost.b, alpha));
+                }
+            }
+        }
+        output
+    }
+}
+
+/// How a [`Recorder`] assigns timestamps to captured frames.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Timing {
+    /// Frames are spaced evenly at the specified frame rate, no matter how long drawing them took.
+    FixedFps(f64),
+    /// Frames are stamped with the wall-clock time passed since the first capture.
+    RealTime,
+}
+
+/// Records frames of an animation for previewing and exporting them.
+///
+/// Capturing a canvas is cheap, as the pixels are only copied once the canvas is drawn onto again.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::animation::{OnionSkin, Recorder, Timing};
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{BLACK, WHITE};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut recorder = Recorder::new(Timing::FixedFps(30.0));
+/// let onion = OnionSkin { layers: 2, opacity: 0.5, background: BLACK.to_rgb().0 };
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+/// for i in 0..3 {
+///     canvas.fill(BLACK.to_rgb().0);
+///     canvas.draw_circle_solid(200 + i * 100, 100, 30, WHITE);
+///
+///     // preview the frame over the ones before it
+///     let preview = recorder.onion_skin(&canvas, &onion);
+///     recorder.capture(&canvas);
+/// }
+///
+/// assert_eq!(3, recorder.len());
+/// assert_eq!(2.0 / 30.0, recorder.frames()[2].timestamp);
+///
+/// let apng = recorder.encode_apng(0).unwrap();
+/// let gif = recorder.encode_gif(&[BLACK.to_rgb().0, WHITE.to_rgb().0], 0).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    timing: Timing,
+    frames: Vec<Frame>,
+
+    /// Time of the first capture for [`Timing::RealTime`].
+    start: Option<Instant>,
+
+    /// Only every n-th frame passed to [`Recorder::record`] gets captured.
+    every_nth: usize,
+    /// Maximum rate in frames per second at which [`Recorder::record`] captures.
+    max_fps: Option<f64>,
+    paused: bool,
+    /// Number of frames passed to [`Recorder::record`] so far.
+    offered: usize,
+    last_record: Option<Instant>,
+}
+
+impl Recorder {
+    /// Creates a new recorder without any frames.
+    pub fn new(timing: Timing) -> Self {
+        Self {
+            timing,
+            frames: Vec::new(),
+            start: None,
+            every_nth: 1,
+            max_fps: None,
+            paused: false,
+            offered: 0,
+            last_record: None,
+        }
+    }
+
+    /// Makes [`Recorder::record`] capture only every n-th frame and skip the ones in between.
+    pub fn every_nth(mut self, n: usize) -> Self {
+        self.every_nth = n.max(1);
+        self
+    }
+
+    /// Makes [`Recorder::record`] skip frames arriving faster than the specified frame rate in wall-clock time.
+    pub fn max_fps(mut self, fps: f64) -> Self {
+        self.max_fps = Some(fps);
+        self
+    }
+
+    /// Stops [`Recorder::record`] from capturing until [`Recorder::resume`] is called.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Lets [`Recorder::record`] capture again after [`Recorder::pause`].
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns `true` if the recorder is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Offers a frame of an interactive session, e.g. every presented frame of a window loop.
+    ///
+    /// The frame is captured like with [`Recorder::capture`] unless the recorder is paused
+    /// or the frame gets skipped by [`Recorder::every_nth`] or [`Recorder::max_fps`].
+    /// Returns `true` if the frame was captured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::animation::{Recorder, Timing};
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let mut recorder = Recorder::new(Timing::FixedFps(20.0)).every_nth(3);
+    ///
+    /// for _ in 0..9 {
+    ///     recorder.record(&canvas);
+    /// }
+    /// assert_eq!(3, recorder.len());
+    ///
+    /// recorder.pause();
+    /// assert!(!recorder.record(&canvas));
+    /// ```
+    pub fn record(&mut self, canvas: &Canvas) -> bool {
+        if self.paused {
+            return false;
+        }
+
+        let offered = self.offered;
+        self.offered += 1;
+        if !offered.is_multiple_of(self.every_nth) {
+            return false;
+        }
+
+        if let Some(fps) = self.max_fps {
+            let now = Instant::now();
+            if let Some(last) = self.last_record {
+                if now.duration_since(last).as_secs_f64() < 1.0 / fps {
+                    return false;
+                }
+            }
+            self.last_record = Some(now);
+        }
+
+        self.capture(canvas);
+        true
+    }
+
+    /// Captures the current state of a canvas as the next frame, timed according to the recorder's [`Timing`].
+    pub fn capture(&mut self, canvas: &Canvas) {
+        let timestamp = match self.timing {
+            Timing::FixedFps(fps) => self.frames.len() as f64 / fps,
+            Timing::RealTime => self
+                .start
+                .get_or_insert_with(Instant::now)
+                .elapsed()
+                .as_secs_f64(),
+        };
+        self.capture_at(canvas, timestamp);
+    }
+
+    /// Captures the current state of a canvas as the next frame with an explicit timestamp in seconds.
+    pub fn capture_at(&mut self, canvas: &Canvas, timestamp: f64) {
+        self.frames.push(Frame {
+            canvas: canvas.clone(),
+            timestamp,
+        });
+    }
+
+    /// Returns all captured frames from oldest to newest.
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Returns the captured frames, consuming the recorder.
+    pub fn into_frames(self) -> Vec<Frame> {
+        self.frames
+    }
+
+    /// Returns the number of captured frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns `true` if no frames were captured yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Removes all captured frames and restarts the clock of [`Timing::RealTime`].
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.start = None;
+        self.offered = 0;
+        self.last_record = None;
+    }
+
+    /// Returns the frame captured `back` frames ago, `0` being the latest one.
+    pub fn previous(&self, back: usize) -> Option<&Canvas> {
+        let index = self.frames.len().checked_sub(back + 1)?;
+        Some(&self.frames[index].canvas)
+    }
+
+    /// Composites the latest captured frames under a canvas, see [`OnionSkin::apply`].
+    pub fn onion_skin(&self, current: &Canvas, skin: &OnionSkin) -> Canvas {
+        let start = self.frames.len().saturating_sub(skin.layers);
+        let previous: Vec<Canvas> = self.frames[start..]
+            .iter()
+            .map(|f| f.canvas.clone())
+            .collect();
+        skin.apply(&previous, current)
+    }
+
+    /// Resamples the captured frames to a constant frame rate, see [`resample`].
+    pub fn resample(&self, fps: f64, mode: Resample) -> Vec<Canvas> {
+        resample(&self.frames, fps, mode)
+    }
+
+    /// Encodes the captured frames as an animated PNG, see [`png::encode_apng`].
+    pub fn encode_apng(&self, loops: u32) -> Result<Vec<u8>, CanvasError> {
+        png::encode_apng(&self.frames, loops)
+    }
+
+    /// Writes the captured frames to an animated PNG file, see [`png::save_apng`].
+    pub fn save_apng<P: AsRef<Path>>(&self, path: P, loops: u32) -> io::Result<()> {
+        png::save_apng(path, &self.frames, loops)
+    }
+
+    /// Encodes the captured frames as an animated GIF dithered to a palette, see [`gif::encode_gif`].
+    pub fn encode_gif(&self, palette: &[RGB], loops: u32) -> Result<Vec<u8>, CanvasError> {
+        gif::encode_gif(&self.frames, palette, loops)
+    }
+
+    /// Writes the captured frames to an animated GIF file dithered to a palette, see [`gif::save_gif`].
+    pub fn save_gif<P: AsRef<Path>>(&self, path: P, palette: &[RGB], loops: u32) -> io::Result<()> {
+        gif::save_gif(path, &self.frames, palette, loops)
+    }
+
+    /// Writes every captured frame to a numbered binary PPM file in a directory,
+    /// named `frame_00000.ppm`, `frame_00001.ppm` and so on.
+    ///
+    /// The directory is created if it does not exist yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use drawing_stuff::animation::{Recorder, Timing};
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut recorder = Recorder::new(Timing::FixedFps(60.0));
+    /// recorder.capture(&Canvas::new(WIDTH, HEIGHT));
+    ///
+    /// recorder.save_ppm_sequence("frames").unwrap();
+    /// ```
+    pub fn save_ppm_sequence<P: AsRef<Path>>(&self, directory: P) -> io::Result<()> {
+        let directory = directory.as_ref();
+        fs::create_dir_all(directory)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            frame
+                .canvas
+                .save_ppm(directory.join(format!("frame_{i:05}.ppm")), false)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+//! Packing of many small canvases into a single sprite sheet.
+
+use crate::canvas::{Canvas, Rect};
+
+/// Handle to a canvas added to an [`AtlasBuilder`], indexing its rectangle in the built atlas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AtlasHandle(pub usize);
+
+/// Builder packing canvases into a single atlas canvas.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::atlas::AtlasBuilder;
+/// use drawing_stuff::canvas::Canvas;
+///
+/// let mut builder = AtlasBuilder::new().padding(1);
+///
+/// let player = builder.add_named("player", Canvas::new(32, 48));
+/// let coin = builder.add_named("coin", Canvas::new(16, 16));
+///
+/// let (atlas, rects) = builder.build();
+/// let player_rect = rects[player.0];
+///
+/// let json = builder.to_json(&rects);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AtlasBuilder {
+    entries: Vec<(Option<String>, Canvas)>,
+    padding: usize,
+}
+
+impl AtlasBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of empty pixels kept between packed canvases.
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Adds a canvas to the atlas.
+    pub fn add(&mut self, canvas: Canvas) -> AtlasHandle {
+        self.entries.push((None, canvas));
+        AtlasHandle(self.entries.len() - 1)
+    }
+
+    /// Adds a canvas with a name used in the JSON metadata to the atlas.
+    pub fn add_named(&mut self, name: &str, canvas: Canvas) -> AtlasHandle {
+        self.entries.push((Some(name.to_string()), canvas));
+        AtlasHandle(self.entries.len() - 1)
+    }
+
+    /// Packs all added canvases into a single canvas.
+    ///
+    /// Returns the atlas and the rectangle of every added canvas inside of it, indexed by the handles.
+    /// The atlas is roughly square with a power of two width.
+    pub fn build(&self) -> (Canvas, Vec<Rect>) {
+        let padding = self.padding;
+        let area = self
+            .entries
+            .iter()
+            .map(|(_, c)| (c.width() + padding) * (c.height() + padding))
+            .sum::<usize>();
+        let widest = self
+            .entries
+            .iter()
+            .map(|(_, c)| c.width() + padding)
+            .max()
+            .unwrap_or(0);
+
+        let atlas_width = ((area as f64).sqrt().ceil() as usize)
+            .max(widest)
+            .next_power_of_two();
+
+        // shelf packing of the canvases sorted from tallest to shortest
+        let mut order = (0..self.entries.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| {
+            let canvas = &self.entries[i].1;
+            (
+                std::cmp::Reverse(canvas.height()),
+                std::cmp::Reverse(canvas.width()),
+            )
+        });
+
+        let mut rects = vec![Rect::new(0, 0, 0, 0); self.entries.len()];
+        let (mut x, mut y, mut shelf_height) = (0, 0, 0);
+        for i in order {
+            let canvas = &self.entries[i].1;
+            if x + canvas.width() > atlas_width {
+                x = 0;
+                y += shelf_height + padding;
+                shelf_height = 0;
+            }
+
+            rects[i] = Rect::new(x as isize, y as isize, canvas.width(), canvas.height());
+            x += canvas.width() + padding;
+            shelf_height = shelf_height.max(canvas.height());
+        }
+
+        let atlas_height = match self.entries.is_empty() {
+            true => 0,
+            false => y + shelf_height,
+        };
+        let mut atlas = Canvas::new(atlas_width, atlas_height);
+        for ((_, canvas), rect) in self.entries.iter().zip(&rects) {
+            atlas.blit(canvas, rect.x, rect.y);
+        }
+
+        (atlas, rects)
+    }
+
+    /// Serializes the rectangles of a built atlas into JSON metadata.
+    ///
+    /// The result is an object mapping the name of every canvas (or its index if it has none)
+    /// to an object with the fields `x`, `y`, `w` and `h`.
+    pub fn to_json(&self, rects: &[Rect]) -> String {
+        let frames = self
+            .entries
+            .iter()
+            .zip(rects)
+            .enumerate()
+            .map(|(i, ((name, _), rect))| {
+                let name = match name {
+                    Some(name) => escape_json(name),
+                    None => i.to_string(),
+                };
+                format!(
+                    "  \"{}\": {{ \"x\": {}, \"y\": {}, \"w\": {}, \"h\": {} }}",
+                    name, rect.x, rect.y, rect.width, rect.height
+                )
+            })
+            .collect::<Vec<_>>();
+
+        format!("{{\n{}\n}}\n", frames.join(",\n"))
+    }
+}
+
+/// Escapes a string for use inside of a JSON string literal.
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,258 @@
+//! One-call generators for pleasant non-flat backgrounds.
+//!
+//! All generators are seeded, so the same seed always produces the same background.
+
+use crate::canvas::Canvas;
+use crate::color::{Interpolation, RGB};
+use crate::noise::value_noise;
+use crate::random::Rng;
+
+/// Fills the canvas with a soft vertical gradient with film-like grain on top.
+///
+/// `grain` is the strength of the grain between `0.0` and `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::backgrounds;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::RGB;
+///
+/// let mut canvas = Canvas::new(1080, 720);
+///
+/// let top = RGB { r: 30, g: 40, b: 90 };
+/// let bottom = RGB { r: 200, g: 90, b: 120 };
+/// backgrounds::soft_gradient(&mut canvas, top, bottom, 0.05, 42);
+/// ```
+pub fn soft_gradient(canvas: &mut Canvas, top: RGB, bottom: RGB, grain: f32, seed: u64) {
+    soft_gradient_in(canvas, top, bottom, grain, seed, Interpolation::SRGB);
+}
+
+/// Fills the canvas with a soft vertical gradient interpolated in the specified way with film-like grain on top.
+///
+/// See [`soft_gradient`] for more details.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::backgrounds;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{Interpolation, RGB};
+///
+/// let mut canvas = Canvas::new(1080, 720);
+///
+/// let top = RGB { r: 0, g: 90, b: 255 };
+/// let bottom = RGB { r: 255, g: 200, b: 0 };
+/// backgrounds::soft_gradient_in(&mut canvas, top, bottom, 0.0, 42, Interpolation::OKLab);
+/// ```
+pub fn soft_gradient_in(
+    canvas: &mut Canvas,
+    top: RGB,
+    bottom: RGB,
+    grain: f32,
+    seed: u64,
+    interpolation: Interpolation,
+) {
+    let width = canvas.width();
+    let height = canvas.height();
+    let mut rng = Rng::new(seed);
+
+    for (y, row) in canvas.buffer_mut().chunks_mut(width.max(1)).enumerate() {
+        let t = match height {
+            0 | 1 => 0.0,
+            _ => y as f32 / (height - 1) as f32,
+        };
+        // smoothstep keeps the ends of the gradient calm
+        let t = t * t * (3.0 - 2.0 * t);
+
+        let base = match interpolation {
+            Interpolation::SRGB => {
+                let mix = |a: u8, b: u8| a as f32 + (b as f32 - a as f32) * t;
+                [
+                    mix(top.r, bottom.r),
+                    mix(top.g, bottom.g),
+                    mix(top.b, bottom.b),
+                ]
+            }
+            Interpolation::OKLab => {
+                let RGB { r, g, b } = top.lerp_oklab(&bottom, t as f64);
+                [r as f32, g as f32, b as f32]
+            }
+        };
+
+        for pixel in row {
+            let noise = (rng.next_f32() - 0.5) * 2.0 * grain * 255.0;
+            let [r, g, b] = base.map(|v| (v + noise).round().clamp(0.0, 255.0) as u8);
+            *pixel = RGB { r, g, b };
+        }
+    }
+}
+
+/// Fills the canvas with a background color covered by soft, out of focus blobs (bokeh).
+///
+/// The colors of the blobs are randomly picked from `colors`.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::backgrounds;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::RGB;
+///
+/// let mut canvas = Canvas::new(1080, 720);
+///
+/// let background = RGB { r: 20, g: 20, b: 35 };
+/// let colors = [RGB { r: 255, g: 180, b: 80 }, RGB { r: 90, g: 160, b: 255 }];
+/// backgrounds::bokeh(&mut canvas, background, &colors, 25, 42);
+/// ```
+pub fn bokeh(canvas: &mut Canvas, background: RGB, colors: &[RGB], count: usize, seed: u64) {
+    canvas.fill(background);
+    if colors.is_empty() {
+        return;
+    }
+
+    let width = canvas.width();
+    let height = canvas.height();
+    let size = width.min(height) as f32;
+    let mut rng = Rng::new(seed);
+
+    for _ in 0..count {
+        let cx = rng.range_f32(0.0, width as f32);
+        let cy = rng.range_f32(0.0, height as f32);
+        let radius = rng.range_f32(0.05, 0.25) * size;
+        let strength = rng.range_f32(0.15, 0.4);
+        let color = colors[rng.below(colors.len())];
+
+        let min_x = (cx - radius).floor().max(0.0) as usize;
+        let max_x = ((cx + radius).ceil().max(0.0) as usize).min(width);
+        let min_y = (cy - radius).floor().max(0.0) as usize;
+        let max_y = ((cy + radius).ceil().max(0.0) as usize).min(height);
+
+        let buffer = canvas.buffer_mut();
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let dx = x as f32 + 0.5 - cx;
+                let dy = y as f32 + 0.5 - cy;
+                let d = (dx * dx + dy * dy).sqrt() / radius;
+                if d >= 1.0 {
+                    continue;
+                }
+
+                let falloff = 1.0 - d * d;
+                let pixel = &mut buffer[y * width + x];
+                *pixel = blend(*pixel, color, strength * falloff * falloff);
+            }
+        }
+    }
+}
+
+/// Fills the canvas with a background color covered by stars of varying brightness.
+///
+/// `density` is the fraction of pixels (e.g. `0.002`) becoming a star.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::backgrounds;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::RGB;
+///
+/// let mut canvas = Canvas::new(1080, 720);
+///
+/// let background = RGB { r: 5, g: 5, b: 20 };
+/// backgrounds::starfield(&mut canvas, background, 0.002, 42);
+/// ```
+pub fn starfield(canvas: &mut Canvas, background: RGB, density: f32, seed: u64) {
+    canvas.fill(background);
+
+    let width = canvas.width();
+    let height = canvas.height();
+    let count = (width as f32 * height as f32 * density.max(0.0)) as usize;
+    let mut rng = Rng::new(seed);
+
+    for _ in 0..count {
+        let x = rng.below(width) as isize;
+        let y = rng.below(height) as isize;
+        let brightness = rng.next_f32().powi(3);
+
+        // slightly warm or cold tint
+        let tint = rng.range_f32(-0.15, 0.15);
+        let star = RGB {
+            r: (255.0 * (1.0 + tint).min(1.0)) as u8,
+            g: (255.0 * (1.0 - tint.abs() * 0.3)) as u8,
+            b: (255.0 * (1.0 - tint).min(1.0)) as u8,
+        };
+
+        let mut plot = |x: isize, y: isize, alpha: f32| {
+            if !canvas.pixel_inside(x, y) {
+                return;
+            }
+            let pixel = &mut canvas.buffer_mut()[y as usize * width + x as usize];
+            *pixel = blend(*pixel, star, alpha);
+        };
+
+        plot(x, y, 0.3 + 0.7 * brightness);
+        if brightness > 0.5 {
+            let glow = (brightness - 0.5) * 0.8;
+            plot(x - 1, y, glow);
+            plot(x + 1, y, glow);
+            plot(x, y - 1, glow);
+            plot(x, y + 1, glow);
+        }
+    }
+}
+
+/// Fills the canvas with a paper-like texture of the specified base color.
+///
+/// `roughness` controls the strength of the texture between `0.0` and `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::backgrounds;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::RGB;
+///
+/// let mut canvas = Canvas::new(1080, 720);
+///
+/// let base = RGB { r: 240, g: 232, b: 215 };
+/// backgrounds::paper(&mut canvas, base, 0.3, 42);
+/// ```
+pub fn paper(canvas: &mut Canvas, base: RGB, roughness: f32, seed: u64) {
+    let width = canvas.width();
+    let mut rng = Rng::new(seed);
+
+    for (y, row) in canvas.buffer_mut().chunks_mut(width.max(1)).enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            let (fx, fy) = (x as f32, y as f32);
+
+            // blotches of a few octaves of value noise plus fine fibers
+            let blotches = 0.5 * value_noise(fx / 64.0, fy / 64.0, seed)
+                + 0.3 * value_noise(fx / 16.0, fy / 16.0, seed.wrapping_add(1))
+                + 0.2 * value_noise(fx / 4.0, fy / 4.0, seed.wrapping_add(2));
+            let fibers = value_noise(fx / 24.0, fy / 1.5, seed.wrapping_add(3));
+            let grain = rng.next_f32();
+
+            let shade = (blotches - 0.5) * 0.6 + (fibers - 0.5) * 0.25 + (grain - 0.5) * 0.15;
+            let factor = 1.0 + shade * roughness;
+
+            let scale = |c: u8| (c as f32 * factor).round().clamp(0.0, 255.0) as u8;
+            *pixel = RGB {
+                r: scale(base.r),
+                g: scale(base.g),
+                b: scale(base.b),
+            };
+        }
+    }
+}
+
+/// Blends a color onto another one with the specified opacity.
+fn blend(base: RGB, color: RGB, alpha: f32) -> RGB {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * alpha).round() as u8;
+    RGB {
+        r: mix(base.r, color.r),
+        g: mix(base.g, color.g),
+        b: mix(base.b, color.b),
+    }
+}
@@ -0,0 +1,126 @@
+use crate::canvas::Canvas;
+use crate::color::RGB;
+use crate::error::CanvasError;
+
+/// Per channel operation used when combining two colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Adds both colors, saturating at white.
+    Add,
+    /// Subtracts the other color, saturating at black.
+    Subtract,
+    /// Absolute difference of both colors, e.g. for background subtraction.
+    Difference,
+    /// Multiplies both colors treating channels as values between 0 and 1.
+    Multiply,
+    /// Inverse of multiplying the inverted colors, always brightens.
+    Screen,
+    /// Keeps the darker channel.
+    Darken,
+    /// Keeps the brighter channel, e.g. for motion-trails.
+    Lighten,
+    /// Average of both colors.
+    Average,
+}
+
+impl BlendMode {
+    /// Combines two colors.
+    pub fn apply(self, base: RGB, other: RGB) -> RGB {
+        let f: fn(u8, u8) -> u8 = match self {
+            BlendMode::Add => |a, b| a.saturating_add(b),
+            BlendMode::Subtract => |a, b| a.saturating_sub(b),
+            BlendMode::Difference => |a, b| a.abs_diff(b),
+            BlendMode::Multiply => |a, b| ((a as u32 * b as u32 + 127) / 255) as u8,
+            BlendMode::Screen => {
+                |a, b| 255 - (((255 - a) as u32 * (255 - b) as u32 + 127) / 255) as u8
+            }
+            BlendMode::Darken => |a, b| a.min(b),
+            BlendMode::Lighten => |a, b| a.max(b),
+            BlendMode::Average => |a, b| (a as u32 + b as u32).div_ceil(2) as u8,
+        };
+
+        RGB {
+            r: f(base.r, other.r),
+            g: f(base.g, other.g),
+            b: f(base.b, other.b),
+        }
+    }
+}
+
+impl Canvas {
+    /// Combines the canvas pixel by pixel with another canvas of the same size.
+    ///
+    /// Returns `None` if the sizes of the canvases differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::blend::BlendMode;
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut frame = Canvas::new(WIDTH, HEIGHT);
+    /// frame.fill(RGB { r: 200, g: 100, b: 50 });
+    /// let mut background = Canvas::new(WIDTH, HEIGHT);
+    /// background.fill(RGB { r: 150, g: 150, b: 50 });
+    ///
+    /// let success = frame.blend_with(&background, BlendMode::Difference);
+    ///
+    /// assert_eq!(true, success.is_some());
+    /// assert_eq!(Some(&RGB { r: 50, g: 50, b: 0 }), frame.get_at(200, 100));
+    /// ```
+    pub fn blend_with(&mut self, other: &Canvas, mode: BlendMode) -> Option<()> {
+        self.try_blend_with(other, mode).ok()
+    }
+
+    /// Combines every pixel of the canvas with the pixel at the same position of another canvas.
+    ///
+    /// Returns [`CanvasError::SizeMismatch`] if the canvases are not the same size.
+    pub fn try_blend_with(&mut self, other: &Canvas, mode: BlendMode) -> Result<(), CanvasError> {
+        if self.width() != other.width() || self.height() != other.height() {
+            return Err(CanvasError::SizeMismatch {
+                expected: (self.width(), self.height()),
+                found: (other.width(), other.height()),
+            });
+        }
+
+        let width = self.width();
+        self.for_each_row_mut(0..self.height(), |y, row| {
+            let other = &other.buffer()[y * width..(y + 1) * width];
+            for (pixel, other) in row.iter_mut().zip(other) {
+                *pixel = mode.apply(*pixel, *other);
+            }
+        });
+        Ok(())
+    }
+
+    /// Combines every pixel of the canvas with a single color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::blend::BlendMode;
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 255, g: 255, b: 255 });
+    ///
+    /// // fade the previous frame for a motion-trail
+    /// canvas.blend_scalar(RGB { r: 128, g: 128, b: 128 }, BlendMode::Multiply);
+    /// assert_eq!(Some(&RGB { r: 128, g: 128, b: 128 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn blend_scalar(&mut self, color: RGB, mode: BlendMode) {
+        self.for_each_row_mut(0..self.height(), |_, row| {
+            for pixel in row {
+                *pixel = mode.apply(*pixel, color);
+            }
+        });
+    }
+}
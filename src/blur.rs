@@ -0,0 +1,239 @@
+use crate::canvas::Canvas;
+use crate::color::RGB;
+
+impl Canvas {
+    /// Blurs the canvas with a gaussian kernel of the specified standard deviation in pixels.
+    ///
+    /// The blur is approximated by three box blur passes, each split into a horizontal and a vertical pass,
+    /// so the cost per pixel does not depend on `sigma`. Pixels outside of the canvas repeat the closest edge pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill_rect(540, 0, 540, HEIGHT, WHITE);
+    ///
+    /// canvas.gaussian_blur(4.0);
+    ///
+    /// let edge = canvas.get_at(540, 100).unwrap().r;
+    /// assert!(edge > 100 && edge < 155);
+    /// assert_eq!(0, canvas.get_at(500, 100).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(580, 100).unwrap().r);
+    ///
+    /// // invalid deviations leave the canvas untouched
+    /// canvas.gaussian_blur(f32::INFINITY);
+    /// assert_eq!(255, canvas.get_at(580, 100).unwrap().r);
+    /// ```
+    pub fn gaussian_blur(&mut self, sigma: f32) {
+        if !sigma.is_finite() || sigma <= 0.0 {
+            return;
+        }
+
+        // wider kernels don't change the result noticeably anymore
+        let sigma = sigma.min(self.width().max(self.height()) as f32);
+        let (width, height) = (self.width(), self.height());
+        for radius in gaussian_box_radii(sigma, 3) {
+            box_blur_buffer(self.buffer_mut(), width, height, radius);
+        }
+    }
+
+    /// Blurs the canvas by replacing every pixel with the average of the square of `2 * radius + 1` pixels around it.
+    ///
+    /// Running sums per row and column keep the cost per pixel independent of the radius, which makes it
+    /// a cheap alternative to [`Canvas::gaussian_blur`] for real-time use. Pixels outside of the canvas repeat the closest edge pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill_rect(540, 0, 540, HEIGHT, WHITE);
+    ///
+    /// canvas.box_blur(2);
+    ///
+    /// assert_eq!(0, canvas.get_at(537, 100).unwrap().r);
+    /// assert_eq!(102, canvas.get_at(539, 100).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(542, 100).unwrap().r);
+    ///
+    /// // radii larger than the canvas are clamped to its size
+    /// canvas.box_blur(usize::MAX);
+    /// ```
+    pub fn box_blur(&mut self, radius: usize) {
+        let (width, height) = (self.width(), self.height());
+        box_blur_buffer(self.buffer_mut(), width, height, radius);
+    }
+}
+
+/// Values which can be averaged by the box blur, having up to three channels.
+pub(crate) trait BlurPixel: Copy + Send + Sync {
+    fn add(self, sum: &mut [u32; 3]);
+    fn subtract(self, sum: &mut [u32; 3]);
+    fn average(sum: [u32; 3], size: u32) -> Self;
+}
+
+impl BlurPixel for RGB {
+    fn add(self, sum: &mut [u32; 3]) {
+        sum[0] += self.r as u32;
+        sum[1] += self.g as u32;
+        sum[2] += self.b as u32;
+    }
+
+    fn subtract(self, sum: &mut [u32; 3]) {
+        sum[0] -= self.r as u32;
+        sum[1] -= self.g as u32;
+        sum[2] -= self.b as u32;
+    }
+
+    fn average(sum: [u32; 3], size: u32) -> Self {
+        let [r, g, b] = sum.map(|v| ((v + size / 2) / size) as u8);
+        RGB { r, g, b }
+    }
+}
+
+impl BlurPixel for u8 {
+    fn add(self, sum: &mut [u32; 3]) {
+        sum[0] += self as u32;
+    }
+
+    fn subtract(self, sum: &mut [u32; 3]) {
+        sum[0] -= self as u32;
+    }
+
+    fn average(sum: [u32; 3], size: u32) -> Self {
+        ((sum[0] + size / 2) / size) as u8
+    }
+}
+
+/// Approximates a gaussian blur of a buffer by three box blur passes with the same radius.
+pub(crate) fn blur_buffer<T>(buffer: &mut [T], width: usize, height: usize, radius: usize)
+where
+    T: BlurPixel,
+{
+    for _ in 0..3 {
+        box_blur_buffer(buffer, width, height, radius);
+    }
+}
+
+/// Blurs every value of a buffer with the average of the square of values around it in one horizontal and one vertical pass.
+///
+/// Values outside of the buffer repeat the closest edge value.
+pub(crate) fn box_blur_buffer<T>(buffer: &mut [T], width: usize, height: usize, radius: usize)
+where
+    T: BlurPixel,
+{
+    if radius == 0 || width == 0 || height == 0 {
+        return;
+    }
+    // beyond the size of the buffer the window only repeats edge values
+    let radius = radius.min(width.max(height));
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        buffer.par_chunks_mut(width).for_each(|row| {
+            let source = row.to_vec();
+            box_blur_line(&source, row, radius);
+        });
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    for row in buffer.chunks_mut(width) {
+        let source = row.to_vec();
+        box_blur_line(&source, row, radius);
+    }
+
+    box_blur_columns(buffer, width, height, radius);
+}
+
+/// Radii of `passes` box blurs which together approximate a gaussian blur with the standard deviation `sigma`.
+fn gaussian_box_radii(sigma: f32, passes: usize) -> Vec<usize> {
+    let n = passes as f32;
+    let ideal = (12.0 * sigma * sigma / n + 1.0).sqrt();
+    let mut lower = ideal.floor() as usize;
+    if lower.is_multiple_of(2) {
+        lower = lower.saturating_sub(1);
+    }
+    let lower = lower.max(1);
+    let upper = lower + 2;
+
+    let l = lower as f32;
+    let lower_count = ((12.0 * sigma * sigma - n * l * l - 4.0 * n * l - 3.0 * n)
+        / (-4.0 * l - 4.0))
+        .round()
+        .max(0.0) as usize;
+
+    (0..passes)
+        .map(|i| match i < lower_count {
+            true => (lower - 1) / 2,
+            false => (upper - 1) / 2,
+        })
+        .collect()
+}
+
+/// Box blurs a line of pixels into `target` using a running sum, repeating the pixels at both ends.
+fn box_blur_line<T>(source: &[T], target: &mut [T], radius: usize)
+where
+    T: BlurPixel,
+{
+    let len = source.len();
+    let at = |i: isize| source[i.clamp(0, len as isize - 1) as usize];
+
+    let mut sum = [0u32; 3];
+    for i in -(radius as isize)..=radius as isize {
+        at(i).add(&mut sum);
+    }
+
+    let size = 2 * radius as u32 + 1;
+    for (i, pixel) in target.iter_mut().enumerate() {
+        *pixel = T::average(sum, size);
+
+        let i = i as isize;
+        at(i + radius as isize + 1).add(&mut sum);
+        at(i - radius as isize).subtract(&mut sum);
+    }
+}
+
+/// Box blurs all columns of a buffer at once, sliding a row of column sums down to stay cache friendly.
+fn box_blur_columns<T>(buffer: &mut [T], width: usize, height: usize, radius: usize)
+where
+    T: BlurPixel,
+{
+    let source = buffer.to_vec();
+    let row = |y: isize| {
+        let y = y.clamp(0, height as isize - 1) as usize;
+        &source[y * width..(y + 1) * width]
+    };
+
+    let mut sums = vec![[0u32; 3]; width];
+    for y in -(radius as isize)..=radius as isize {
+        for (sum, &pixel) in sums.iter_mut().zip(row(y)) {
+            pixel.add(sum);
+        }
+    }
+
+    let size = 2 * radius as u32 + 1;
+    for (y, target) in buffer.chunks_mut(width).enumerate() {
+        for (pixel, &sum) in target.iter_mut().zip(&sums) {
+            *pixel = T::average(sum, size);
+        }
+
+        let y = y as isize;
+        let entering = row(y + radius as isize + 1);
+        let leaving = row(y - radius as isize);
+        for ((sum, &new), &old) in sums.iter_mut().zip(entering).zip(leaving) {
+            new.add(sum);
+            old.subtract(sum);
+        }
+    }
+}
@@ -1,4 +1,10 @@
-use crate::color::{RGB, RGBA};
+use std::ops::{Index, IndexMut, Range};
+use std::sync::Arc;
+
+use crate::color::{fill_span, pack_span, Blender, ByteFormat, ColorSpace, PixelOrder, RGB, RGBA};
+use crate::error::CanvasError;
+use crate::mask::{rasterize_layer, Mask};
+use crate::target::{PixelTarget, Stroke};
 
 /// Trait for drawing anything arbitrary onto a [`Canvas`].
 ///
@@ -28,15 +34,347 @@ use crate::color::{RGB, RGBA};
 pub trait Draw {
     /// Draws onto a [`Canvas`].
     fn draw(&self, canvas: &mut Canvas);
+
+    /// Adds the area covered by the drawable to a [`Mask`]. Translucent colors result in partial coverage.
+    ///
+    /// By default the drawable is drawn onto two temporary canvases the size of the mask and the coverage is derived from their difference.
+    /// Drawables made of basic shapes should draw into the mask directly instead, as it implements [`PixelTarget`].
+    fn draw_mask(&self, mask: &mut Mask) {
+        let (_, coverage) = rasterize_layer(mask.width(), mask.height(), self);
+        mask.cover(&coverage);
+    }
+}
+
+/// How positions outside of a source [`Canvas`] are resolved when reading from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressMode {
+    /// Positions outside of the source are skipped.
+    None,
+    /// The source is repeated endlessly.
+    Repeat,
+    /// Positions are clamped to the closest edge of the source.
+    Clamp,
+    /// The source is repeated endlessly, mirroring every other repetition.
+    Mirror,
+}
+
+impl AddressMode {
+    /// Resolves a coordinate along an axis of the specified size.
+    ///
+    /// Returns `None` if the coordinate is outside and the mode is `AddressMode::None`.
+    pub(crate) fn resolve(&self, v: isize, size: usize) -> Option<usize> {
+        let size = size as isize;
+        if size == 0 {
+            return None;
+        }
+
+        match self {
+            AddressMode::None => match v >= 0 && v < size {
+                true => Some(v as usize),
+                false => None,
+            },
+            AddressMode::Repeat => Some(v.rem_euclid(size) as usize),
+            AddressMode::Clamp => Some(v.clamp(0, size - 1) as usize),
+            AddressMode::Mirror => {
+                let v = v.rem_euclid(2 * size);
+                match v < size {
+                    true => Some(v as usize),
+                    false => Some((2 * size - 1 - v) as usize),
+                }
+            }
+        }
+    }
+}
+
+/// Filter used when sampling a [`Canvas`] at a different resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Filter {
+    /// Picks the closest pixel. Keeps hard edges, e.g. for pixel-art.
+    Nearest,
+    /// Linearly interpolates between the four closest pixels.
+    Bilinear,
+}
+
+/// An axis aligned rectangle given by its top-left corner and its size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: isize,
+    pub y: isize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: isize, y: isize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns the part of the rectangle laying inside of an area of the specified size
+    /// as `(x, y, width, height)` or `None` if there is no overlap.
+    pub(crate) fn clip(&self, width: usize, height: usize) -> Option<(usize, usize, usize, usize)> {
+        let start_x = self.x.max(0);
+        let start_y = self.y.max(0);
+        let end_x = (self.x + self.width as isize).min(width as isize);
+        let end_y = (self.y + self.height as isize).min(height as isize);
+
+        if start_x >= end_x || start_y >= end_y {
+            return None;
+        }
+
+        Some((
+            start_x as usize,
+            start_y as usize,
+            (end_x - start_x) as usize,
+            (end_y - start_y) as usize,
+        ))
+    }
 }
 
 #[derive(Debug, Clone)]
 /// A [`Canvas`] is just a glorified pixel buffer with some usefull functionality.
+///
+/// The pixel buffer is shared copy-on-write, so cloning a canvas is cheap
+/// and the actual copy only happens once one of the clones gets modified.
 pub struct Canvas {
     width: usize,
     height: usize,
+    color_space: ColorSpace,
+
+    buffer: Arc<Vec<RGB>>,
+}
+
+/// Accesses the pixel at the position `(x, y)`.
+///
+/// # Panics
+///
+/// Panics if the position is not inside the canvas. Use [`Canvas::get_at`] for a checked variant.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::RGB;
+///
+/// let mut canvas = Canvas::new(1080, 720);
+///
+/// canvas[(200, 100)] = RGB { r: 255, g: 255, b: 255 };
+/// assert_eq!(255, canvas[(200, 100)].g);
+/// ```
+impl Index<(usize, usize)> for Canvas {
+    type Output = RGB;
+
+    fn index(&self, (x, y): (usize, usize)) -> &RGB {
+        match self.index_of(x, y) {
+            Some(index) => &self.buffer[index],
+            None => panic!(
+                "pixel ({x}, {y}) out of bounds for canvas of size {}x{}",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+/// Mutably accesses the pixel at the position `(x, y)`.
+///
+/// # Panics
+///
+/// Panics if the position is not inside the canvas. Use [`Canvas::get_mut`] for a checked variant.
+impl IndexMut<(usize, usize)> for Canvas {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut RGB {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(x, y) {
+            Some(pixel) => pixel,
+            None => panic!("pixel ({x}, {y}) out of bounds for canvas of size {width}x{height}"),
+        }
+    }
+}
+
+/// A mutable view into a rectangular region of a [`Canvas`].
+///
+/// The view borrows the pixels of the region directly and implements [`PixelTarget`] with coordinates relative to the region,
+/// so components can draw into their own region without copies or manual offsetting. Drawing outside of the region is clipped.
+#[derive(Debug)]
+pub struct CanvasView<'a> {
+    /// Pixels from the top-left to the bottom-right pixel of the region, including the parts of the rows in between.
+    pixels: &'a mut [RGB],
+    stride: usize,
+    color_space: ColorSpace,
+
+    width: usize,
+    height: usize,
+}
+
+impl CanvasView<'_> {
+    /// Returns the color of the pixel at the specified position relative to the view.
+    ///
+    /// Returns `None` if position is not inside the view.
+    pub fn get_at(&self, x: isize, y: isize) -> Option<&RGB> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.pixels.get(y as usize * self.stride + x as usize)
+    }
+
+    /// Sets the color of the pixel at the specified position relative to the view.
+    ///
+    /// Returns `None` if position is not inside the view.
+    pub fn set_at(&mut self, x: isize, y: isize, color: RGB) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.pixels[y as usize * self.stride + x as usize] = color;
+        Some(())
+    }
+
+    /// Fills the whole view with a given color.
+    pub fn fill(&mut self, color: RGB) {
+        for row in self.rows_mut() {
+            fill_span(row, color);
+        }
+    }
+
+    /// Returns an iterator over the rows of the view from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[RGB]> {
+        let (stride, width) = (self.stride, self.width);
+        (0..self.height).map(move |y| &self.pixels[y * stride..y * stride + width])
+    }
+
+    /// Returns an iterator over the mutable rows of the view from top to bottom.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [RGB]> {
+        let width = self.width;
+        self.pixels
+            .chunks_mut(self.stride.max(1))
+            .take(self.height)
+            .map(move |row| &mut row[..width])
+    }
+
+    /// Draws anything arbitrary implementing the [`Draw`] trait into the view.
+    ///
+    /// As [`Draw`] works on a [`Canvas`], the region is copied into a temporary canvas and copied back afterwards.
+    /// Prefer the methods of [`PixelTarget`] when drawing many small things.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::WHITE;
+    /// use drawing_stuff::drawables::Circle;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let mut sidebar = canvas.sub_canvas_mut(Rect::new(880, 0, 200, HEIGHT));
+    /// sidebar.draw(&Circle { center: (100, 100), radius: 50, solid: true, color: WHITE });
+    ///
+    /// assert_eq!(255, canvas.get_at(980, 100).unwrap().r);
+    /// ```
+    pub fn draw<T>(&mut self, drawable: &T)
+    where
+        T: Draw,
+    {
+        let mut canvas = Canvas::new(self.width, self.height);
+        canvas.color_space = self.color_space;
+        for (dst, src) in canvas.rows_mut().zip(self.rows()) {
+            dst.copy_from_slice(src);
+        }
+
+        drawable.draw(&mut canvas);
+
+        for (dst, src) in self.rows_mut().zip(canvas.rows()) {
+            dst.copy_from_slice(src);
+        }
+    }
+}
+
+impl PixelTarget for CanvasView<'_> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn blend_span(&mut self, y: usize, x1: usize, x2: usize, color: RGBA) {
+        let start = y * self.stride;
+        blend_row(&mut self.pixels[start + x1..start + x2], color);
+    }
+}
+
+/// A read-only snapshot of a rectangular region of a [`Canvas`].
+///
+/// The view shares the pixel buffer with the canvas it was created from.
+/// It can be sent to other threads and keeps showing the pixels at the time of its creation,
+/// while the canvas copies its buffer the next time it gets modified.
+#[derive(Debug, Clone)]
+pub struct ReadView {
+    buffer: Arc<Vec<RGB>>,
+    stride: usize,
+    color_space: ColorSpace,
+
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl ReadView {
+    /// Returns the width of the view.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the view.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the color of the pixel at the specified position relative to the view.
+    ///
+    /// Returns `None` if position is not inside the view.
+    pub fn get(&self, x: usize, y: usize) -> Option<&RGB> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.buffer.get((self.y + y) * self.stride + self.x + x)
+    }
+
+    /// Returns an iterator over the rows of the view.
+    pub fn rows(&self) -> impl Iterator<Item = &[RGB]> {
+        (self.y..self.y + self.height).map(move |y| {
+            let start = y * self.stride + self.x;
+            &self.buffer[start..start + self.width]
+        })
+    }
+
+    /// Copies the view into a new canvas.
+    pub fn to_canvas(&self) -> Canvas {
+        if self.x == 0
+            && self.width == self.stride
+            && self.height * self.stride == self.buffer.len()
+        {
+            return Canvas {
+                width: self.width,
+                height: self.height,
+                color_space: self.color_space,
+                buffer: Arc::clone(&self.buffer),
+            };
+        }
 
-    buffer: Vec<RGB>,
+        Canvas {
+            width: self.width,
+            height: self.height,
+            color_space: self.color_space,
+            buffer: Arc::new(self.rows().flatten().copied().collect()),
+        }
+    }
 }
 
 impl Canvas {
@@ -56,8 +394,29 @@ impl Canvas {
         Canvas {
             width,
             height,
-            buffer: vec![RGB { r: 0, g: 0, b: 0 }; width * height],
+            color_space: ColorSpace::SRGB,
+            buffer: Arc::new(vec![RGB { r: 0, g: 0, b: 0 }; width * height]),
+        }
+    }
+
+    /// Creates a new black canvas.
+    ///
+    /// Returns [`CanvasError::ZeroSize`] if the width or height is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::error::CanvasError;
+    ///
+    /// assert!(Canvas::try_new(1080, 720).is_ok());
+    /// assert_eq!(Some(CanvasError::ZeroSize), Canvas::try_new(0, 720).err());
+    /// ```
+    pub fn try_new(width: usize, height: usize) -> Result<Self, CanvasError> {
+        if width == 0 || height == 0 {
+            return Err(CanvasError::ZeroSize);
         }
+        Ok(Self::new(width, height))
     }
 }
 
@@ -98,6 +457,21 @@ impl Canvas {
         self.height
     }
 
+    /// Returns the color space the pixels of the canvas are encoded in.
+    ///
+    /// New canvases are in [`ColorSpace::SRGB`].
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    /// Tags the canvas with a color space without touching its pixels,
+    /// e.g. after loading pixels which are known to be in a different color space.
+    ///
+    /// Use [`Canvas::convert_color_space`] to convert the pixels instead.
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
+
     /// Returns a reference to the pixel buffer of the canvas.
     ///
     /// # Examples
@@ -135,10 +509,10 @@ impl Canvas {
     /// }
     /// ```
     pub fn buffer_mut(&mut self) -> &mut Vec<RGB> {
-        &mut self.buffer
+        Arc::make_mut(&mut self.buffer)
     }
 
-    /// Returns the pixel buffer as a 32-bit buffer in the format `0RGB`.
+    /// Returns an iterator over the rows of the canvas from top to bottom.
     ///
     /// # Examples
     ///
@@ -148,39 +522,42 @@ impl Canvas {
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
-    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let buffer = canvas.buffer_u32();
+    /// for row in canvas.rows() {
+    ///     assert_eq!(WIDTH, row.len());
+    /// }
     /// ```
-    pub fn buffer_u32(&self) -> Vec<u32> {
-        self.buffer
-            .iter()
-            .map(|c| (c.r as u32) << 16 | (c.g as u32) << 8 | (c.b as u32))
-            .collect::<Vec<u32>>()
+    pub fn rows(&self) -> std::slice::Chunks<'_, RGB> {
+        self.buffer.chunks(self.width.max(1))
     }
 
-    /// Checks if the pixel specified lays inside of the canvas.
+    /// Returns an iterator over the mutable rows of the canvas from top to bottom.
+    ///
+    /// The rows don't overlap, so they can be processed independently, e.g. on separate threads.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let in_bound = canvas.pixel_inside(200, 100);
-    /// assert_eq!(true, in_bound);
+    /// // scanline effect
+    /// for row in canvas.rows_mut().step_by(2) {
+    ///     row.fill(RGB { r: 20, g: 20, b: 20 });
+    /// }
     /// ```
-    pub fn pixel_inside(&self, x: isize, y: isize) -> bool {
-        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    pub fn rows_mut(&mut self) -> std::slice::ChunksMut<'_, RGB> {
+        let width = self.width.max(1);
+        Arc::make_mut(&mut self.buffer).chunks_mut(width)
     }
 
-    /// Returns the color of the pixel at the specified position.
-    ///
-    /// Returns `None` if position is not inside the canvas.
+    /// Returns the pixel buffer as a 32-bit buffer in the format `0RGB`.
     ///
     /// # Examples
     ///
@@ -192,332 +569,1083 @@ impl Canvas {
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let pixel = canvas.get(200, 100);
-    ///
-    /// assert_eq!(true, pixel.is_some());
+    /// let buffer = canvas.buffer_u32();
     /// ```
-    pub fn get(&self, x: usize, y: usize) -> Option<&RGB> {
-        self.buffer.get(y * self.width + x)
+    pub fn buffer_u32(&self) -> Vec<u32> {
+        self.buffer_u32_ordered(PixelOrder::XRGB)
     }
 
-    /// Sets the color of the pixel at the specified position.
+    /// Writes the pixel buffer as 32-bit values in the format `0RGB` into a provided buffer without allocating.
     ///
-    /// Returns `None` if position is not inside the canvas.
+    /// Returns `None` if the provided buffer is smaller than the pixel buffer.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGB;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let mut window_buffer = vec![0u32; WIDTH * HEIGHT];
     ///
-    /// let color = RGB { r: 255, g: 255, b: 255 };
-    /// let success = canvas.set(200, 100, color);
+    /// // every frame
+    /// let success = canvas.buffer_u32_into(&mut window_buffer);
     ///
     /// assert_eq!(true, success.is_some());
     /// ```
-    pub fn set(&mut self, x: usize, y: usize, color: RGB) -> Option<()> {
-        *self.buffer.get_mut(y * self.width + x)? = color;
-        Some(())
+    pub fn buffer_u32_into(&self, target: &mut [u32]) -> Option<()> {
+        self.try_buffer_u32_into(target).ok()
     }
 
-    /// Fills the whole canvas with a given color.
+    /// Writes the pixel buffer as 32-bit values in the format `0RGB` into a provided buffer.
+    ///
+    /// Returns [`CanvasError::BufferTooSmall`] if the provided buffer is smaller than the pixel buffer.
+    pub fn try_buffer_u32_into(&self, target: &mut [u32]) -> Result<(), CanvasError> {
+        let found = target.len();
+        let target = target
+            .get_mut(..self.buffer.len())
+            .ok_or(CanvasError::BufferTooSmall {
+                required: self.buffer.len(),
+                found,
+            })?;
+        pack_span(PixelOrder::XRGB, &self.buffer, target);
+        Ok(())
+    }
+
+    /// Returns an iterator over the pixel buffer as 32-bit values in the format `0RGB`.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGB;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGB { r: 255, g: 255, b: 255 };
-    /// canvas.fill(color);
+    /// let first = canvas.buffer_u32_iter().next();
+    /// assert_eq!(Some(0), first);
     /// ```
-    pub fn fill(&mut self, color: RGB) {
-        self.buffer = vec![color; self.width * self.height];
+    pub fn buffer_u32_iter(&self) -> impl ExactSizeIterator<Item = u32> + '_ {
+        self.buffer.iter().map(|c| c.to_u32(PixelOrder::XRGB))
     }
-}
 
-impl Canvas {
-    /// Draws anything arbitrary implementing the `Draw` trait onto the canvas.
+    /// Returns the pixel buffer as a 32-bit buffer in the specified byte order.
+    ///
+    /// The alpha channel of orders containing one is always opaque.
     ///
     /// # Examples
     ///
     /// ```
-    /// use drawing_stuff::canvas::{Canvas, Draw};
-    /// use drawing_stuff::color::RGBA;
-    ///
-    /// pub struct Circle {
-    ///     pub center: (isize, isize),
-    ///     pub radius: u32,
-    ///     pub solid: bool,
-    ///
-    ///     pub color: RGBA,
-    /// }
-    ///
-    /// impl Draw for Circle {
-    ///     fn draw(&self, canvas: &mut Canvas) {
-    ///        match self.solid {
-    ///           true => canvas.draw_circle_solid(self.center.0, self.center.1, self.radius, self.color),
-    ///           false => canvas.draw_circle(self.center.0, self.center.1, self.radius, self.color),
-    ///       }
-    ///     }
-    /// }
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::PixelOrder;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// let circle = Circle {
-    ///     center: (200, 100),
-    ///     radius: 50,
-    ///     solid: true,
-    ///     color,
-    /// };
-    ///
-    /// canvas.draw(&circle);
-    /// // or
-    /// circle.draw(&mut canvas);
+    /// let buffer = canvas.buffer_u32_ordered(PixelOrder::RGBA);
+    /// assert_eq!(0x000000ff, buffer[0]);
     /// ```
-    pub fn draw<T>(&mut self, drawable: &T)
-    where
-        T: Draw,
-    {
-        drawable.draw(self);
+    pub fn buffer_u32_ordered(&self, order: PixelOrder) -> Vec<u32> {
+        let mut buffer = vec![0; self.buffer.len()];
+        pack_span(order, &self.buffer, &mut buffer);
+        buffer
     }
 
-    /// Draws a single pixel onto the canvas.
+    /// Writes the pixel buffer as 32-bit values in the specified byte order into a provided buffer without allocating.
     ///
-    /// Returns `None` if position is not inside the canvas.
+    /// The alpha channel of orders containing one is always opaque.
+    /// Returns `None` if the provided buffer is smaller than the pixel buffer.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::color::PixelOrder;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let mut texture = vec![0u32; WIDTH * HEIGHT];
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// let success = canvas.draw_pixel(200, 100, color);
+    /// let success = canvas.buffer_u32_into_ordered(&mut texture, PixelOrder::BGRA);
     ///
     /// assert_eq!(true, success.is_some());
     /// ```
-    pub fn draw_pixel(&mut self, x: isize, y: isize, color: RGBA) -> Option<()> {
-        if !self.pixel_inside(x, y) {
-            return None;
-        };
-
-        let old_color = self.get(x as usize, y as usize)?;
-        let new_color = old_color.add_rgba(color);
-        self.set(x as usize, y as usize, new_color)
+    pub fn buffer_u32_into_ordered(&self, target: &mut [u32], order: PixelOrder) -> Option<()> {
+        let target = target.get_mut(..self.buffer.len())?;
+        pack_span(order, &self.buffer, target);
+        Some(())
     }
 
-    /// Draws a line onto the canvas.
+    /// Returns the pixel buffer as tightly packed bytes in the format `RGB8`.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// canvas.draw_line(200, 100, 500, 700, color);
+    /// let bytes = canvas.as_bytes();
+    /// assert_eq!(WIDTH * HEIGHT * 3, bytes.len());
     /// ```
-    pub fn draw_line(&mut self, x1: isize, y1: isize, x2: isize, y2: isize, color: RGBA) {
-        let (x1, y1, x2, y2) = self.clamp_line_coords(x1, y1, x2, y2);
-
-        if x1 == x2 {
-            let (start_y, end_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
-            for i in 0..(end_y - start_y) {
-                self.draw_pixel(x1, start_y + i, color);
-            }
-            return;
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: `RGB` is `repr(C)` and consists of three `u8`, so it has no padding
+        // and a buffer of `RGB` is a valid buffer of three times as many `u8`.
+        unsafe {
+            std::slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.buffer.len() * 3)
         }
+    }
 
-        let dx = (x2 - x1).abs();
-        let dy = (y2 - y1).abs();
+    /// Returns the pixel buffer as bytes in the specified format with every row padded to a multiple of `row_align` bytes.
+    ///
+    /// This matches the layout expected by e.g. GPU texture uploads requiring 4-byte-aligned rows.
+    /// A `row_align` of `0` or `1` adds no padding. Padding bytes are zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::ByteFormat;
+    ///
+    /// let canvas = Canvas::new(5, 2);
+    ///
+    /// // 5 * 3 = 15 bytes per row padded to 16
+    /// let bytes = canvas.to_bytes_with_stride(ByteFormat::RGB8, 4);
+    /// assert_eq!(32, bytes.len());
+    /// ```
+    pub fn to_bytes_with_stride(&self, format: ByteFormat, row_align: usize) -> Vec<u8> {
+        let row_len = self.width * format.bytes_per_pixel();
+        let stride = match row_align {
+            0 | 1 => row_len,
+            align => row_len.div_ceil(align) * align,
+        };
+
+        let mut bytes = vec![0u8; stride * self.height];
+        if self.width == 0 {
+            return bytes;
+        }
+
+        for (row, dst) in self.buffer.chunks(self.width).zip(bytes.chunks_mut(stride)) {
+            match format {
+                ByteFormat::RGB8 => {
+                    for (c, d) in row.iter().zip(dst.chunks_mut(3)) {
+                        d.copy_from_slice(&[c.r, c.g, c.b]);
+                    }
+                }
+                ByteFormat::RGBA8 => {
+                    for (c, d) in row.iter().zip(dst.chunks_mut(4)) {
+                        d.copy_from_slice(&[c.r, c.g, c.b, 255]);
+                    }
+                }
+            }
+        }
+
+        bytes
+    }
+
+    /// Returns a read-only snapshot of a region of the canvas without copying the pixel buffer.
+    ///
+    /// The region is clipped to the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let view = canvas.read_view(Rect::new(0, 0, WIDTH, HEIGHT));
+    /// let encoder = std::thread::spawn(move || view.to_canvas());
+    ///
+    /// // continue drawing the next frame
+    /// canvas.draw_pixel(200, 100, WHITE);
+    ///
+    /// let frame = encoder.join().unwrap();
+    /// assert_ne!(frame.get_at(200, 100), canvas.get_at(200, 100));
+    /// ```
+    pub fn read_view(&self, rect: Rect) -> ReadView {
+        let (x, y, width, height) = rect.clip(self.width, self.height).unwrap_or((0, 0, 0, 0));
+
+        ReadView {
+            buffer: Arc::clone(&self.buffer),
+            stride: self.width,
+            color_space: self.color_space,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns a mutable view into a region of the canvas.
+    ///
+    /// The view acts like a canvas of the size of the region, so components can draw into their own region
+    /// without offsetting their coordinates. Drawing outside of the region is clipped.
+    /// The region itself is clipped to the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::WHITE;
+    /// use drawing_stuff::target::PixelTarget;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let mut sidebar = canvas.sub_canvas_mut(Rect::new(680, 0, 200, HEIGHT));
+    /// assert_eq!(200, sidebar.width());
+    ///
+    /// sidebar.draw_pixel(10, 10, WHITE);
+    /// // clipped at the edge of the view
+    /// sidebar.draw_hline(190, 300, 20, WHITE);
+    ///
+    /// assert_eq!(255, canvas.get_at(690, 10).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(879, 20).unwrap().r);
+    /// assert_eq!(0, canvas.get_at(880, 20).unwrap().r);
+    /// ```
+    pub fn sub_canvas_mut(&mut self, rect: Rect) -> CanvasView<'_> {
+        let (x, y, width, height) = rect.clip(self.width, self.height).unwrap_or((0, 0, 0, 0));
+        let (stride, color_space) = (self.width, self.color_space);
+        let pixels = match width > 0 && height > 0 {
+            true => {
+                let start = y * stride + x;
+                let end = (y + height - 1) * stride + x + width;
+                &mut self.buffer_mut()[start..end]
+            }
+            false => &mut [],
+        };
+
+        CanvasView {
+            pixels,
+            stride,
+            color_space,
+            width,
+            height,
+        }
+    }
+
+    /// Checks if the pixel specified lays inside of the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let in_bound = canvas.pixel_inside(200, 100);
+    /// assert_eq!(true, in_bound);
+    /// ```
+    pub fn pixel_inside(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let pixel = canvas.get_at(200, 100);
+    ///
+    /// assert_eq!(true, pixel.is_some());
+    /// assert_eq!(None, canvas.get_at(-1, 100));
+    /// ```
+    pub fn get_at(&self, x: isize, y: isize) -> Option<&RGB> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.buffer.get(y as usize * self.width + x as usize)
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `get_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn get(&self, x: usize, y: usize) -> Option<&RGB> {
+        let index = self.index_of(x, y)?;
+        self.buffer.get(index)
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns [`CanvasError::OutOfBounds`] if position is not inside the canvas.
+    pub fn try_get(&self, x: isize, y: isize) -> Result<&RGB, CanvasError> {
+        let error = self.out_of_bounds(x, y);
+        self.get_at(x, y).ok_or(error)
+    }
+
+    /// Returns a mutable reference to the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// if let Some(pixel) = canvas.get_mut(200, 100) {
+    ///     pixel.r = 255;
+    /// }
+    ///
+    /// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+    /// assert_eq!(None, canvas.get_mut(WIDTH, 0));
+    /// ```
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut RGB> {
+        let index = self.index_of(x, y)?;
+        Arc::make_mut(&mut self.buffer).get_mut(index)
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGB { r: 255, g: 255, b: 255 };
+    /// let success = canvas.set_at(200, 100, color);
+    ///
+    /// assert_eq!(true, success.is_some());
+    /// assert_eq!(None, canvas.set_at(-1, 100, color));
+    /// ```
+    pub fn set_at(&mut self, x: isize, y: isize, color: RGB) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+
+        // SAFETY: the position has been checked to be inside of the canvas.
+        unsafe { self.set_unchecked(x as usize, y as usize, color) };
+        Some(())
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `set_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn set(&mut self, x: usize, y: usize, color: RGB) -> Option<()> {
+        *self.get_mut(x, y)? = color;
+        Some(())
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns [`CanvasError::OutOfBounds`] if position is not inside the canvas.
+    pub fn try_set(&mut self, x: isize, y: isize, color: RGB) -> Result<(), CanvasError> {
+        let error = self.out_of_bounds(x, y);
+        self.set_at(x, y, color).ok_or(error)
+    }
+
+    /// Sets the color of the pixel at the specified position without checking the position.
+    ///
+    /// # Safety
+    ///
+    /// `x` has to be smaller than the width and `y` smaller than the height of the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// // SAFETY: 200 < WIDTH and 100 < HEIGHT
+    /// unsafe { canvas.set_unchecked(200, 100, RGB { r: 255, g: 255, b: 255 }) };
+    /// ```
+    pub unsafe fn set_unchecked(&mut self, x: usize, y: usize, color: RGB) {
+        *Arc::make_mut(&mut self.buffer).get_unchecked_mut(y * self.width + x) = color;
+    }
+
+    /// Fills the whole canvas with a given color.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGB { r: 255, g: 255, b: 255 };
+    /// canvas.fill(color);
+    /// ```
+    pub fn fill(&mut self, color: RGB) {
+        match Arc::get_mut(&mut self.buffer) {
+            Some(_) => self.for_each_row_mut(0..self.height, |_, row| fill_span(row, color)),
+            None => self.buffer = Arc::new(vec![color; self.width * self.height]),
+        }
+    }
+}
+
+impl Canvas {
+    /// Draws anything arbitrary implementing the `Draw` trait onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Draw};
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// pub struct Circle {
+    ///     pub center: (isize, isize),
+    ///     pub radius: u32,
+    ///     pub solid: bool,
+    ///
+    ///     pub color: RGBA,
+    /// }
+    ///
+    /// impl Draw for Circle {
+    ///     fn draw(&self, canvas: &mut Canvas) {
+    ///        match self.solid {
+    ///           true => canvas.draw_circle_solid(self.center.0, self.center.1, self.radius, self.color),
+    ///           false => canvas.draw_circle(self.center.0, self.center.1, self.radius, self.color),
+    ///       }
+    ///     }
+    /// }
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// let circle = Circle {
+    ///     center: (200, 100),
+    ///     radius: 50,
+    ///     solid: true,
+    ///     color,
+    /// };
+    ///
+    /// canvas.draw(&circle);
+    /// // or
+    /// circle.draw(&mut canvas);
+    /// ```
+    pub fn draw<T>(&mut self, drawable: &T)
+    where
+        T: Draw,
+    {
+        drawable.draw(self);
+    }
+
+    /// Draws a single pixel onto the canvas.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// let success = canvas.draw_pixel(200, 100, color);
+    ///
+    /// assert_eq!(true, success.is_some());
+    /// ```
+    pub fn draw_pixel(&mut self, x: isize, y: isize, color: RGBA) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        };
+
+        // SAFETY: the position has been checked to be inside of the canvas.
+        unsafe { self.draw_pixel_unchecked(x as usize, y as usize, color) };
+        Some(())
+    }
+
+    /// Draws a single pixel onto the canvas.
+    ///
+    /// Returns [`CanvasError::OutOfBounds`] if position is not inside the canvas.
+    pub fn try_draw_pixel(&mut self, x: isize, y: isize, color: RGBA) -> Result<(), CanvasError> {
+        let error = self.out_of_bounds(x, y);
+        self.draw_pixel(x, y, color).ok_or(error)
+    }
+
+    /// Draws a single pixel onto the canvas without checking the position.
+    ///
+    /// # Safety
+    ///
+    /// `x` has to be smaller than the width and `y` smaller than the height of the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// for x in 0..WIDTH {
+    ///     // SAFETY: x < WIDTH and 100 < HEIGHT
+    ///     unsafe { canvas.draw_pixel_unchecked(x, 100, WHITE) };
+    /// }
+    /// ```
+    pub unsafe fn draw_pixel_unchecked(&mut self, x: usize, y: usize, color: RGBA) {
+        let pixel = Arc::make_mut(&mut self.buffer).get_unchecked_mut(y * self.width + x);
+        *pixel = pixel.add_rgba(color);
+    }
+
+    /// Draws a line onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// canvas.draw_line(200, 100, 500, 700, color);
+    /// ```
+    pub fn draw_line(&mut self, x1: isize, y1: isize, x2: isize, y2: isize, color: RGBA) {
+        PixelTarget::draw_line(self, x1, y1, x2, y2, color);
+    }
+
+    /// Draws a horizontal line from `x1` to `x2` (both inclusive) onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.draw_hline(200, 500, 100, WHITE);
+    /// assert_eq!(255, canvas.get_at(500, 100).unwrap().r);
+    /// ```
+    pub fn draw_hline(&mut self, x1: isize, x2: isize, y: isize, color: RGBA) {
+        PixelTarget::draw_hline(self, x1, x2, y, color);
+    }
+
+    /// Draws a vertical line from `y1` to `y2` (both inclusive) onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.draw_vline(200, 100, 700, WHITE);
+    /// assert_eq!(255, canvas.get_at(200, 700).unwrap().r);
+    /// ```
+    pub fn draw_vline(&mut self, x: isize, y1: isize, y2: isize, color: RGBA) {
+        PixelTarget::draw_vline(self, x, y1, y2, color);
+    }
+
+    /// Draws a line with specified width onto the canvas.
+    /// Drawing the line as a filled polygon.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// canvas.draw_polyline(200, 100, 500, 700, 5, color);
+    /// ```
+    pub fn draw_polyline(
+        &mut self,
+        x1: isize,
+        y1: isize,
+        x2: isize,
+        y2: isize,
+        width: u32,
+        color: RGBA,
+    ) {
+        PixelTarget::draw_polyline(self, x1, y1, x2, y2, width, color);
+    }
 
-        let abs_m = dy as f32 / dx as f32;
-        match abs_m <= 1.0 {
-            true => {
-                let (start_x, start_y, end_x, end_y) = if x1 < x2 {
-                    (x1, y1, x2, y2)
-                } else {
-                    (x2, y2, x1, y1)
-                };
+    /// Draws a line with specified width and capped ends onto the canvas.
+    /// Drawing the line as a filled polygon with circles on both ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// canvas.draw_polyline_capped(200, 100, 500, 700, 5, color);
+    /// ```
+    pub fn draw_polyline_capped(
+        &mut self,
+        x1: isize,
+        y1: isize,
+        x2: isize,
+        y2: isize,
+        width: u32,
+        color: RGBA,
+    ) {
+        PixelTarget::draw_polyline_capped(self, x1, y1, x2, y2, width, color);
+    }
 
-                let step = if start_y < end_y { 1 } else { -1 };
-
-                let a = 2 * dy;
-                let b = a - 2 * dx;
-                let mut p = a - dx;
-                self.draw_pixel(start_x, start_y, color);
-
-                let mut offset = 0isize;
-                for i in 1..=(end_x - start_x) {
-                    match p < 0 {
-                        true => {
-                            p += a;
-                        }
-                        false => {
-                            offset += step;
-                            p += b;
-                        }
-                    }
+    /// Draws an anti-aliased stroke with the specified width through a list of points onto the canvas.
+    ///
+    /// The stroke has round caps and joins. Its coverage is computed in a single pass over the union of all segments,
+    /// so translucent strokes are blended once per pixel even where caps, joins and segments overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 128 };
+    /// canvas.draw_stroke_aa(&[(200, 100), (500, 100), (500, 400)], 9, color);
+    ///
+    /// // the join is not blended twice
+    /// assert_eq!(canvas.get_at(300, 100), canvas.get_at(500, 100));
+    /// ```
+    pub fn draw_stroke_aa(&mut self, points: &[(isize, isize)], width: u32, color: RGBA) {
+        PixelTarget::draw_stroke_aa(self, points, width, color);
+    }
 
-                    self.draw_pixel(start_x + i, start_y + offset, color);
-                }
-            }
-            false => {
-                let (start_x, start_y, end_x, end_y) = if y1 < y2 {
-                    (x1, y1, x2, y2)
-                } else {
-                    (x2, y2, x1, y1)
-                };
+    /// Draws a circle onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// canvas.draw_circle(200, 100, 15, color);
+    /// ```
+    pub fn draw_circle(&mut self, x: isize, y: isize, r: u32, color: RGBA) {
+        PixelTarget::draw_circle(self, x, y, r, color);
+    }
+
+    /// Draws a solid circle onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// canvas.draw_circle_solid(200, 100, 15, color);
+    /// ```
+    pub fn draw_circle_solid(&mut self, x: isize, y: isize, r: u32, color: RGBA) {
+        PixelTarget::draw_circle_solid(self, x, y, r, color);
+    }
+
+    /// Draws a solid axis aligned rectangle onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// canvas.fill_rect(200, 100, 300, 50, color);
+    /// ```
+    pub fn fill_rect(&mut self, x: isize, y: isize, width: usize, height: usize, color: RGBA) {
+        PixelTarget::fill_rect(self, x, y, width, height, color);
+    }
+
+    /// Draws a polygon onto the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// let vertices = vec![(200, 100), (500, 700), (300, 800)];
+    /// canvas.draw_polygon(&vertices, color);
+    /// ```
+    #[allow(clippy::ptr_arg)]
+    pub fn draw_polygon(&mut self, vertices: &Vec<(isize, isize)>, color: RGBA) {
+        PixelTarget::draw_polygon(self, vertices, color);
+    }
+
+    /// Draws a solid polygon onto the canvas.
+    ///
+    /// The vertices of the polygon have to be given in the specified order (clockwise / anti-clockwise).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
+    /// let clockwise = true;
+    /// let vertices = vec![(200, 100), (500, 700), (300, 800)]; // clockwise
+    /// canvas.draw_polygon_solid(&vertices, clockwise, color);
+    /// ```
+    #[allow(clippy::ptr_arg)]
+    pub fn draw_polygon_solid(
+        &mut self,
+        vertices: &Vec<(isize, isize)>,
+        clockwise: bool,
+        color: RGBA,
+    ) {
+        PixelTarget::draw_polygon_solid(self, vertices, clockwise, color);
+    }
+
+    /// Draws a solid polygon onto the canvas.
+    ///
+    /// Returns [`CanvasError::InvalidGeometry`] if the polygon has less than three vertices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let result = canvas.try_draw_polygon_solid(&[(200, 100), (500, 700)], true, WHITE);
+    ///
+    /// assert!(result.is_err());
+    /// ```
+    pub fn try_draw_polygon_solid(
+        &mut self,
+        vertices: &[(isize, isize)],
+        clockwise: bool,
+        color: RGBA,
+    ) -> Result<(), CanvasError> {
+        if vertices.len() < 3 {
+            return Err(CanvasError::InvalidGeometry(
+                "a polygon needs at least three vertices",
+            ));
+        }
 
-                let step = if start_x < end_x { 1 } else { -1 };
+        PixelTarget::draw_polygon_solid(self, vertices, clockwise, color);
+        Ok(())
+    }
+}
 
-                let a = 2 * dx;
-                let b = a - 2 * dy;
-                let mut p = a - dy;
+impl Canvas {
+    /// Copies another canvas onto the canvas at the specified position.
+    ///
+    /// Parts of the other canvas laying outside of the canvas are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let sprite = Canvas::new(32, 32);
+    ///
+    /// canvas.blit(&sprite, 200, 100);
+    /// ```
+    pub fn blit(&mut self, other: &Canvas, x: isize, y: isize) {
+        let Some((src_x, src_y, dst_x, dst_y, w, h)) =
+            self.clip_blit(other.width, other.height, x, y)
+        else {
+            return;
+        };
 
-                self.draw_pixel(start_x, start_y, color);
+        self.for_each_row_mut(dst_y..dst_y + h, |row, dst| {
+            let src_start = (src_y + row - dst_y) * other.width + src_x;
+            dst[dst_x..dst_x + w].copy_from_slice(&other.buffer[src_start..src_start + w]);
+        });
+    }
 
-                let mut offset = 0isize;
-                for i in 1..=(end_y - start_y) {
-                    match p < 0 {
-                        true => {
-                            p += a;
-                        }
-                        false => {
-                            offset += step;
-                            p += b;
-                        }
-                    }
+    /// Copies another canvas mirrored horizontally and / or vertically onto the canvas at the specified position.
+    ///
+    /// Parts of the other canvas laying outside of the canvas are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let sprite = Canvas::new(32, 32);
+    ///
+    /// // sprite facing the other direction
+    /// canvas.blit_flipped(&sprite, 200, 100, true, false);
+    /// ```
+    pub fn blit_flipped(
+        &mut self,
+        other: &Canvas,
+        x: isize,
+        y: isize,
+        horizontal: bool,
+        vertical: bool,
+    ) {
+        let Some((src_x, src_y, dst_x, dst_y, w, h)) =
+            self.clip_blit(other.width, other.height, x, y)
+        else {
+            return;
+        };
 
-                    self.draw_pixel(start_x + offset, start_y + i, color);
+        let buffer = Arc::make_mut(&mut self.buffer);
+        for row in 0..h {
+            let sy = match vertical {
+                true => other.height - 1 - (src_y + row),
+                false => src_y + row,
+            };
+            let dst_start = (dst_y + row) * self.width + dst_x;
+            let dst = &mut buffer[dst_start..dst_start + w];
+
+            match horizontal {
+                true => {
+                    // the visible columns src_x..src_x + w read from the mirrored side of the source
+                    let src_end = sy * other.width + other.width - src_x;
+                    let src = &other.buffer[src_end - w..src_end];
+                    for (d, s) in dst.iter_mut().zip(src.iter().rev()) {
+                        *d = *s;
+                    }
+                }
+                false => {
+                    let src_start = sy * other.width + src_x;
+                    dst.copy_from_slice(&other.buffer[src_start..src_start + w]);
                 }
             }
         }
     }
 
-    /// Draws a line with specified width onto the canvas.
-    /// Drawing the line as a filled polygon.
+    /// Copies a region of another canvas onto the canvas at the specified position.
+    ///
+    /// This allows using a single canvas as a sprite sheet.
+    /// The region is clipped to the other canvas and parts laying outside of the canvas are clipped as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let atlas = Canvas::new(128, 128);
+    ///
+    /// canvas.blit_region(&atlas, Rect::new(32, 0, 32, 32), 200, 100);
+    /// ```
+    pub fn blit_region(&mut self, other: &Canvas, region: Rect, x: isize, y: isize) {
+        let Some((region_x, region_y, region_w, region_h)) = region.clip(other.width, other.height)
+        else {
+            return;
+        };
+
+        let x = x + (region_x as isize - region.x);
+        let y = y + (region_y as isize - region.y);
+        let Some((src_x, src_y, dst_x, dst_y, w, h)) = self.clip_blit(region_w, region_h, x, y)
+        else {
+            return;
+        };
+
+        self.for_each_row_mut(dst_y..dst_y + h, |row, dst| {
+            let src_start = (region_y + src_y + row - dst_y) * other.width + region_x + src_x;
+            dst[dst_x..dst_x + w].copy_from_slice(&other.buffer[src_start..src_start + w]);
+        });
+    }
+
+    /// Copies a region of another canvas onto the canvas at the specified position
+    /// resolving parts of the region outside of the other canvas with an [`AddressMode`].
+    ///
+    /// Parts laying outside of the canvas are clipped.
     ///
     /// # Examples
     ///
     /// ```
-    /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::canvas::{AddressMode, Canvas, Rect};
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let tile = Canvas::new(32, 32);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// canvas.draw_polyline(200, 100, 500, 700, 5, color);
+    /// // a 3x2 repetition of the tile
+    /// canvas.blit_region_with(&tile, Rect::new(0, 0, 96, 64), 200, 100, AddressMode::Repeat);
     /// ```
-    pub fn draw_polyline(
+    pub fn blit_region_with(
         &mut self,
-        x1: isize,
-        y1: isize,
-        x2: isize,
-        y2: isize,
-        width: u32,
-        color: RGBA,
+        other: &Canvas,
+        region: Rect,
+        x: isize,
+        y: isize,
+        mode: AddressMode,
     ) {
-        if width == 0 {
-            return;
-        }
-
-        if width == 1 {
-            self.draw_line(x1, y1, x2, y2, color);
+        let Some((src_x, src_y, dst_x, dst_y, w, h)) =
+            self.clip_blit(region.width, region.height, x, y)
+        else {
             return;
-        }
-
-        let (x1, y1, x2, y2) = self.clamp_line_coords(x1, y1, x2, y2);
-
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-
-        let d_len = ((dx * dx + dy * dy) as f32).sqrt();
-        let dx_n = dx as f32 / d_len;
-        let dy_n = dy as f32 / d_len;
+        };
 
-        let v1 = (
-            x1 - (dy_n * width as f32 / 2.0).round() as isize,
-            y1 + (dx_n * width as f32 / 2.0).round() as isize,
-        );
-        let v2 = (
-            x1 + (dy_n * width as f32 / 2.0).round() as isize,
-            y1 - (dx_n * width as f32 / 2.0).round() as isize,
-        );
-        let v3 = (
-            x2 + (dy_n * width as f32 / 2.0).round() as isize,
-            y2 - (dx_n * width as f32 / 2.0).round() as isize,
-        );
-        let v4 = (
-            x2 - (dy_n * width as f32 / 2.0).round() as isize,
-            y2 + (dx_n * width as f32 / 2.0).round() as isize,
-        );
+        self.for_each_row_mut(dst_y..dst_y + h, |row, dst| {
+            let row = row - dst_y;
+            let Some(sy) = mode.resolve(region.y + (src_y + row) as isize, other.height) else {
+                return;
+            };
 
-        let vertices = vec![v1, v2, v3, v4];
+            for col in 0..w {
+                let Some(sx) = mode.resolve(region.x + (src_x + col) as isize, other.width) else {
+                    continue;
+                };
 
-        self.draw_polygon_solid(&vertices, true, color);
+                dst[dst_x + col] = other.buffer[sy * other.width + sx];
+            }
+        });
     }
 
-    /// Draws a line with specified width and capped ends onto the canvas.
-    /// Drawing the line as a filled polygon with circles on both ends.
+    /// Fills the whole canvas with another canvas used as a texture, e.g. for seamless tiled backgrounds.
+    ///
+    /// The offset specifies the position in the texture shown at the top-left corner of the canvas.
     ///
     /// # Examples
     ///
     /// ```
-    /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::canvas::{AddressMode, Canvas};
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let tile = Canvas::new(32, 32);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// canvas.draw_polyline_capped(200, 100, 500, 700, 5, color);
+    /// canvas.fill_texture(&tile, 0, 0, AddressMode::Mirror);
     /// ```
-    pub fn draw_polyline_capped(
+    pub fn fill_texture(
         &mut self,
-        x1: isize,
-        y1: isize,
-        x2: isize,
-        y2: isize,
-        width: u32,
-        color: RGBA,
+        texture: &Canvas,
+        offset_x: isize,
+        offset_y: isize,
+        mode: AddressMode,
     ) {
-        self.draw_polyline(x1, y1, x2, y2, width, color);
-        self.draw_circle_solid(x1, y1, width / 2, color);
-        self.draw_circle_solid(x2, y2, width / 2, color);
+        let region = Rect::new(offset_x, offset_y, self.width, self.height);
+        self.blit_region_with(texture, region, 0, 0, mode);
     }
 
-    /// Draws a circle onto the canvas.
+    /// Composites a buffer of RGBA values onto the canvas at the specified position.
+    ///
+    /// The buffer is interpreted as an image of the specified width stored row by row.
+    /// Parts of the image laying outside of the canvas are clipped.
     ///
     /// # Examples
     ///
@@ -530,422 +1658,458 @@ impl Canvas {
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// canvas.draw_circle(200, 100, 15, color);
+    /// let color = RGBA { r: 255, g: 255, b: 255, a: 128 };
+    /// let sprite = vec![color; 32 * 32];
+    /// canvas.blit_rgba(&sprite, 32, 200, 100);
     /// ```
-    pub fn draw_circle(&mut self, x: isize, y: isize, r: u32, color: RGBA) {
-        if r == 0 {
+    pub fn blit_rgba(&mut self, other: &[RGBA], width: usize, x: isize, y: isize) {
+        if width == 0 {
             return;
         }
 
-        let mut e = -(r as isize);
-        let mut x_offset = r as isize;
-        let mut y_offset = 0isize;
+        let height = other.len() / width;
+        let Some((src_x, src_y, dst_x, dst_y, w, h)) = self.clip_blit(width, height, x, y) else {
+            return;
+        };
 
-        while y_offset <= x_offset {
-            self.draw_pixel(x + x_offset, y + y_offset, color);
-            self.draw_pixel(x + x_offset, y - y_offset, color);
-            self.draw_pixel(x - x_offset, y + y_offset, color);
-            self.draw_pixel(x - x_offset, y - y_offset, color);
+        self.for_each_row_mut(dst_y..dst_y + h, |row, dst| {
+            let src_start = (src_y + row - dst_y) * width + src_x;
+            let src = &other[src_start..src_start + w];
+            for (d, s) in dst[dst_x..dst_x + w].iter_mut().zip(src) {
+                *d = d.add_rgba(*s);
+            }
+        });
+    }
+}
 
-            self.draw_pixel(x + y_offset, y + x_offset, color);
-            self.draw_pixel(x + y_offset, y - x_offset, color);
-            self.draw_pixel(x - y_offset, y - x_offset, color);
-            self.draw_pixel(x - y_offset, y + x_offset, color);
+impl Canvas {
+    /// Returns a copy of a rectangular region of the canvas.
+    ///
+    /// The region is clamped to the borders of the canvas, so the result may be smaller than requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let cropped = canvas.crop(1000, 600, 200, 200);
+    /// assert_eq!((80, 120), (cropped.width(), cropped.height()));
+    /// ```
+    pub fn crop(&self, x: isize, y: isize, width: usize, height: usize) -> Canvas {
+        self.read_view(Rect::new(x, y, width, height)).to_canvas()
+    }
 
-            e += 2 * y_offset + 1;
-            y_offset += 1;
-            if e >= 0 {
-                e -= 2 * x_offset - 1;
-                x_offset -= 1;
-            }
+    /// Mirrors the canvas in place along its vertical axis, swapping left and right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.flip_horizontal();
+    /// ```
+    pub fn flip_horizontal(&mut self) {
+        let width = self.width.max(1);
+        for row in Arc::make_mut(&mut self.buffer).chunks_mut(width) {
+            row.reverse();
         }
     }
 
-    /// Draws a solid circle onto the canvas.
+    /// Mirrors the canvas in place along its horizontal axis, swapping top and bottom.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// canvas.draw_circle_solid(200, 100, 15, color);
+    /// canvas.flip_vertical();
     /// ```
-    pub fn draw_circle_solid(&mut self, x: isize, y: isize, r: u32, color: RGBA) {
-        if r == 0 {
-            return;
+    pub fn flip_vertical(&mut self) {
+        let width = self.width;
+        let height = self.height;
+        let buffer = Arc::make_mut(&mut self.buffer);
+
+        for y in 0..height / 2 {
+            let (top, bottom) = buffer.split_at_mut((height - 1 - y) * width);
+            top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
         }
+    }
 
-        let mut e = -(r as isize);
-        let mut x_offset = r as isize;
-        let mut y_offset = 0isize;
-
-        let dy = 2 * r;
-
-        let mut left_buff = vec![0isize; dy as usize + 1];
-        let mut right_buff = vec![0isize; dy as usize + 1];
-
-        while y_offset <= x_offset {
-            right_buff[(y + y_offset - (y - r as isize)) as usize] = x + x_offset;
-            right_buff[(y - y_offset - (y - r as isize)) as usize] = x + x_offset;
-            left_buff[(y + y_offset - (y - r as isize)) as usize] = x - x_offset;
-            left_buff[(y - y_offset - (y - r as isize)) as usize] = x - x_offset;
-
-            right_buff[(y + x_offset - (y - r as isize)) as usize] = x + y_offset;
-            right_buff[(y - x_offset - (y - r as isize)) as usize] = x + y_offset;
-            left_buff[(y + x_offset - (y - r as isize)) as usize] = x - y_offset;
-            left_buff[(y - x_offset - (y - r as isize)) as usize] = x - y_offset;
-
-            e += 2 * y_offset + 1;
-            y_offset += 1;
-            if e >= 0 {
-                e -= 2 * x_offset - 1;
-                x_offset -= 1;
+    /// Rotates the canvas by 90 degrees clockwise, swapping its width and height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.rotate_90_cw();
+    /// assert_eq!((HEIGHT, WIDTH), (canvas.width(), canvas.height()));
+    /// ```
+    pub fn rotate_90_cw(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let mut rotated = Vec::with_capacity(self.buffer.len());
+        for x in 0..width {
+            for y in (0..height).rev() {
+                rotated.push(self.buffer[y * width + x]);
             }
         }
 
-        for i in 0..dy {
-            let y = i as isize + (y - r as isize);
-            let x1 = left_buff[i as usize];
-            let x2 = right_buff[i as usize];
+        self.width = height;
+        self.height = width;
+        self.buffer = Arc::new(rotated);
+    }
 
-            for x in x1..x2 {
-                self.draw_pixel(x, y, color);
+    /// Rotates the canvas by 90 degrees counter-clockwise, swapping its width and height.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.rotate_90_ccw();
+    /// assert_eq!((HEIGHT, WIDTH), (canvas.width(), canvas.height()));
+    /// ```
+    pub fn rotate_90_ccw(&mut self) {
+        let (width, height) = (self.width, self.height);
+        let mut rotated = Vec::with_capacity(self.buffer.len());
+        for x in (0..width).rev() {
+            for y in 0..height {
+                rotated.push(self.buffer[y * width + x]);
             }
         }
+
+        self.width = height;
+        self.height = width;
+        self.buffer = Arc::new(rotated);
     }
 
-    /// Draws a polygon onto the canvas.
+    /// Rotates the canvas in place by 180 degrees.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// let vertices = vec![(200, 100), (500, 700), (300, 800)];
-    /// canvas.draw_polygon(&vertices, color);
+    /// canvas.rotate_180();
     /// ```
-    pub fn draw_polygon(&mut self, vertices: &Vec<(isize, isize)>, color: RGBA) {
-        if vertices.is_empty() {
-            return;
+    pub fn rotate_180(&mut self) {
+        Arc::make_mut(&mut self.buffer).reverse();
+    }
+
+    /// Returns a copy of the canvas with its content rescaled to the specified size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Filter};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let thumbnail = canvas.resized(108, 72, Filter::Bilinear);
+    /// assert_eq!(108, thumbnail.width());
+    /// ```
+    pub fn resized(&self, width: usize, height: usize, filter: Filter) -> Canvas {
+        let mut canvas = Canvas::new(width, height);
+        canvas.color_space = self.color_space;
+        if self.width == 0 || self.height == 0 {
+            return canvas;
         }
 
-        for i in 1..vertices.len() {
-            let (x1, y1) = vertices[i];
-            let (x2, y2) = vertices[i - 1];
-            self.draw_line(x1, y1, x2, y2, color);
+        let scale_x = self.width as f32 / width as f32;
+        let scale_y = self.height as f32 / height as f32;
+
+        let buffer = Arc::make_mut(&mut canvas.buffer);
+        for y in 0..height {
+            let sy = (y as f32 + 0.5) * scale_y;
+            for x in 0..width {
+                let sx = (x as f32 + 0.5) * scale_x;
+                buffer[y * width + x] = self.sample(sx, sy, filter);
+            }
         }
 
-        let (x1, y1) = vertices[0];
-        let (x2, y2) = vertices[vertices.len() - 1];
-        self.draw_line(x1, y1, x2, y2, color);
+        canvas
     }
 
-    /// Draws a solid polygon onto the canvas.
+    /// Draws onto the canvas at a multiple of its resolution and downsamples the result, smoothing all edges.
     ///
-    /// The vertices of the polygon have to be given in the specified order (clockwise / anti-clockwise).
+    /// The closure receives a temporary canvas `factor` times as wide and high as this one, so coordinates and sizes
+    /// used while drawing have to be scaled by `factor` as well. Every pixel becomes the average of the `factor * factor`
+    /// pixels it was split into. A factor of `0` or `1` draws onto the canvas directly.
     ///
     /// # Examples
     ///
     /// ```
     /// use drawing_stuff::canvas::Canvas;
-    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::color::WHITE;
     ///
     /// const WIDTH: usize = 1080;
     /// const HEIGHT: usize = 720;
     ///
     /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
     ///
-    /// let color = RGBA { r: 255, g: 255, b: 255, a: 255 };
-    /// let clockwise = true;
-    /// let vertices = vec![(200, 100), (500, 700), (300, 800)]; // clockwise
-    /// canvas.draw_polygon_solid(&vertices, clockwise, color);
+    /// canvas.render_supersampled(4, |c| {
+    ///     c.draw_circle_solid(200 * 4, 100 * 4, 50 * 4, WHITE);
+    /// });
+    ///
+    /// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+    /// let edge = canvas.get_at(235, 135).unwrap().r;
+    /// assert!(edge > 0 && edge < 255);
     /// ```
-    pub fn draw_polygon_solid(
-        &mut self,
-        vertices: &Vec<(isize, isize)>,
-        clockwise: bool,
-        color: RGBA,
-    ) {
-        if vertices.is_empty() {
+    pub fn render_supersampled<F>(&mut self, factor: usize, f: F)
+    where
+        F: FnOnce(&mut Canvas),
+    {
+        if factor <= 1 {
+            f(self);
             return;
         }
 
-        let mut min_vert = 0;
-        let mut max_vert = 0;
-        for i in 0..vertices.len() {
-            if vertices[i].1 < vertices[min_vert].1 {
-                min_vert = i;
-            }
-            if vertices[i].1 > vertices[max_vert].1 {
-                max_vert = i;
+        let mut large = self.resized(self.width * factor, self.height * factor, Filter::Nearest);
+        f(&mut large);
+
+        let samples = (factor * factor) as u32;
+        self.for_each_row_mut(0..self.height, |y, row| {
+            let rows = large
+                .buffer
+                .chunks(large.width)
+                .skip(y * factor)
+                .take(factor);
+            let mut sums = vec![[0u32; 3]; row.len()];
+            for large_row in rows {
+                for (sum, block) in sums.iter_mut().zip(large_row.chunks(factor)) {
+                    for pixel in block {
+                        sum[0] += pixel.r as u32;
+                        sum[1] += pixel.g as u32;
+                        sum[2] += pixel.b as u32;
+                    }
+                }
             }
-        }
-
-        let (start_x, start_y) = vertices[min_vert];
 
-        let vertices = vertices
-            .into_iter()
-            .map(|(x, y)| (x - start_x, y - start_y))
-            .collect::<Vec<_>>();
-
-        let dy = (vertices[max_vert].1 + 1) as usize;
-
-        let mut left_buff = vec![0isize; dy];
-        let mut right_buff = vec![0isize; dy];
-
-        let start_vert = if clockwise { min_vert } else { max_vert };
-        let end_vert = if clockwise { max_vert } else { min_vert };
-
-        let mut vert_index = start_vert;
-        loop {
-            let (x1, y1) = vertices[vert_index % vertices.len()];
-            let (x2, y2) = vertices[(vert_index + 1) % vertices.len()];
-
-            Self::polygon_buffer_line(&mut right_buff, true, x1, y1, x2, y2);
-
-            vert_index += 1;
-            if vert_index % vertices.len() == end_vert {
-                break;
+            for (pixel, sum) in row.iter_mut().zip(sums) {
+                let [r, g, b] = sum.map(|v| ((v + samples / 2) / samples) as u8);
+                *pixel = RGB { r, g, b };
             }
-        }
+        });
+    }
+}
 
-        let mut vert_index = end_vert;
-        loop {
-            let (x1, y1) = vertices[vert_index % vertices.len()];
-            let (x2, y2) = vertices[(vert_index + 1) % vertices.len()];
+impl Canvas {
+    /// Samples the color of the canvas at the specified position given in pixel space using a filter.
+    ///
+    /// Positions outside of the canvas are clamped to the closest edge.
+    pub(crate) fn sample(&self, x: f32, y: f32, filter: Filter) -> RGB {
+        let max_x = self.width as isize - 1;
+        let max_y = self.height as isize - 1;
+        let pixel = |x: isize, y: isize| {
+            self.buffer[y.clamp(0, max_y) as usize * self.width + x.clamp(0, max_x) as usize]
+        };
 
-            Self::polygon_buffer_line(&mut left_buff, false, x1, y1, x2, y2);
+        match filter {
+            Filter::Nearest => pixel(x.floor() as isize, y.floor() as isize),
+            Filter::Bilinear => {
+                let x = x - 0.5;
+                let y = y - 0.5;
+                let x0 = x.floor();
+                let y0 = y.floor();
+                let tx = x - x0;
+                let ty = y - y0;
+                let (x0, y0) = (x0 as isize, y0 as isize);
+
+                let (c00, c10) = (pixel(x0, y0), pixel(x0 + 1, y0));
+                let (c01, c11) = (pixel(x0, y0 + 1), pixel(x0 + 1, y0 + 1));
+                let mix = |a: u8, b: u8, c: u8, d: u8| {
+                    let top = a as f32 + (b as f32 - a as f32) * tx;
+                    let bottom = c as f32 + (d as f32 - c as f32) * tx;
+                    (top + (bottom - top) * ty).round() as u8
+                };
 
-            vert_index += 1;
-            if vert_index % vertices.len() == start_vert {
-                break;
+                RGB {
+                    r: mix(c00.r, c10.r, c01.r, c11.r),
+                    g: mix(c00.g, c10.g, c01.g, c11.g),
+                    b: mix(c00.b, c10.b, c01.b, c11.b),
+                }
             }
         }
+    }
 
-        for i in 0..dy {
-            let y = i as isize + start_y;
-            let x1 = left_buff[i] + start_x;
-            let x2 = right_buff[i] + start_x;
-
-            for x in x1..x2 {
-                self.draw_pixel(x, y, color);
-            }
+    /// Returns the error for the specified position not being inside the canvas.
+    fn out_of_bounds(&self, x: isize, y: isize) -> CanvasError {
+        CanvasError::OutOfBounds {
+            x,
+            y,
+            width: self.width,
+            height: self.height,
         }
     }
-}
-
-impl Canvas {
-    /// Clamps the specified coordinates of a line into the canvas space and returns them.
-    fn clamp_line_coords(
-        &self,
-        x1: isize,
-        y1: isize,
-        x2: isize,
-        y2: isize,
-    ) -> (isize, isize, isize, isize) {
-        let p1_inside = x1 >= 0 && x1 < self.width as isize && y1 >= 0 && y1 < self.height as isize;
-        let p2_inside = x2 >= 0 && x2 < self.width as isize && y2 >= 0 && y2 < self.height as isize;
 
-        if p1_inside && p2_inside {
-            return (x1, y1, x2, y2);
+    /// Returns the index of the pixel at the specified position into the pixel buffer
+    /// or `None` if the position is not inside the canvas.
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        match x < self.width && y < self.height {
+            true => Some(y * self.width + x),
+            false => None,
         }
+    }
 
-        let dx = x2 - x1;
-        let dy = y2 - y1;
-
-        if dx == 0 {
-            let s_y0 = (x1, 0 as isize);
-            let s_yh = (x1, self.width as isize);
-
-            let (x1, y1) = match p1_inside {
-                true => (x1, y1),
-                false => {
-                    if y1 < 0 {
-                        s_y0
-                    } else {
-                        s_yh
-                    }
-                }
-            };
-            let (x2, y2) = match p2_inside {
-                true => (x2, y2),
-                false => {
-                    if y2 < 0 {
-                        s_y0
-                    } else {
-                        s_yh
-                    }
-                }
-            };
-
-            return (x1, y1, x2, y2);
+    /// Calls a function for each row in the specified range with the index and the pixels of the row.
+    ///
+    /// The rows are processed in parallel if the `rayon` feature is enabled.
+    pub(crate) fn for_each_row_mut<F>(&mut self, rows: Range<usize>, f: F)
+    where
+        F: Fn(usize, &mut [RGB]) + Send + Sync,
+    {
+        let rows = rows.start.min(self.height)..rows.end.min(self.height);
+        if rows.is_empty() || self.width == 0 {
+            return;
         }
 
-        let m = dy as f32 / dx as f32;
-        let c = y1 as f32 - m * x1 as f32;
-
-        let s_x0 = (0 as f32, c);
-        let s_xw = (self.width as f32, c + m * self.width as f32);
-        let s_y0 = (-c / m, 0 as f32);
-        let s_yh = ((self.height as f32 - c) / m, self.height as f32);
+        let width = self.width;
+        let buffer = &mut Arc::make_mut(&mut self.buffer)[rows.start * width..rows.end * width];
 
-        let s_x0 = match s_x0.1 >= 0.0 && s_x0.1 < self.height as f32 {
-            true => Some(s_x0),
-            false => None,
-        };
-        let s_xw = match s_xw.1 >= 0.0 && s_xw.1 < self.height as f32 {
-            true => Some(s_xw),
-            false => None,
-        };
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            buffer
+                .par_chunks_mut(width)
+                .enumerate()
+                .for_each(|(i, row)| f(rows.start + i, row));
+        }
 
-        let s_y0 = match s_y0.0 >= 0.0 && s_y0.0 < self.width as f32 {
-            true => Some(s_y0),
-            false => None,
-        };
-        let s_yh = match s_yh.0 >= 0.0 && s_yh.0 < self.width as f32 {
-            true => Some(s_yh),
-            false => None,
-        };
+        #[cfg(not(feature = "rayon"))]
+        for (i, row) in buffer.chunks_mut(width).enumerate() {
+            f(rows.start + i, row);
+        }
+    }
 
-        let mut valid_intersects = [s_x0, s_xw, s_y0, s_yh].into_iter().flatten();
-        let p1 = valid_intersects.next().unwrap();
-        let p2 = valid_intersects.next().unwrap();
+    /// Computes the overlap of an image of the specified size placed at the specified position with the canvas.
+    ///
+    /// Returns the source offset, the destination offset and the size of the overlap
+    /// or `None` if there is no overlap.
+    fn clip_blit(
+        &self,
+        width: usize,
+        height: usize,
+        x: isize,
+        y: isize,
+    ) -> Option<(usize, usize, usize, usize, usize, usize)> {
+        let (dst_x, dst_y, w, h) = Rect::new(x, y, width, height).clip(self.width, self.height)?;
+
+        Some((
+            (dst_x as isize - x) as usize,
+            (dst_y as isize - y) as usize,
+            dst_x,
+            dst_y,
+            w,
+            h,
+        ))
+    }
+}
 
-        let p1 = (p1.0.round() as isize, p1.1.round() as isize);
-        let p2 = (p2.0.round() as isize, p2.1.round() as isize);
+impl PixelTarget for Canvas {
+    fn width(&self) -> usize {
+        self.width
+    }
 
-        let (x1, y1) = if p1_inside {
-            (x1, y1)
-        } else {
-            let dx_p1 = p1.0 - x1;
-            let dy_p1 = p1.1 - y1;
-            let sqr_dist_p1 = dx_p1 * dx_p1 + dy_p1 * dy_p1;
+    fn height(&self) -> usize {
+        self.height
+    }
 
-            let dx_p2 = p2.0 - x1;
-            let dy_p2 = p2.1 - y1;
-            let sqr_dist_p2 = dx_p2 * dx_p2 + dy_p2 * dy_p2;
+    fn blend_span(&mut self, y: usize, x1: usize, x2: usize, color: RGBA) {
+        let start = y * self.width;
+        blend_row(&mut self.buffer_mut()[start + x1..start + x2], color);
+    }
 
-            if sqr_dist_p1 < sqr_dist_p2 {
-                p1
-            } else {
-                p2
-            }
-        };
-        let (x2, y2) = if p2_inside {
-            (x2, y2)
-        } else {
-            let dx_p1 = p1.0 - x2;
-            let dy_p1 = p1.1 - y2;
-            let sqr_dist_p1 = dx_p1 * dx_p1 + dy_p1 * dy_p1;
-
-            let dx_p2 = p2.0 - x2;
-            let dy_p2 = p2.1 - y2;
-            let sqr_dist_p2 = dx_p2 * dx_p2 + dy_p2 * dy_p2;
-
-            if sqr_dist_p1 < sqr_dist_p2 {
-                p1
-            } else {
-                p2
-            }
+    /// Blends a color onto spans `(y, x1, x2)` sorted by row.
+    ///
+    /// The rows are processed in parallel if the `rayon` feature is enabled.
+    fn fill_spans(&mut self, spans: &[(isize, isize, isize)], color: RGBA) {
+        let (Some(first), Some(last)) = (spans.first(), spans.last()) else {
+            return;
         };
+        let first = first.0.max(0);
+        let last = (last.0 + 1).min(self.height as isize);
+        if first >= last {
+            return;
+        }
 
-        (x1, y1, x2, y2)
+        let width = self.width as isize;
+        self.for_each_row_mut(first as usize..last as usize, |y, row| {
+            let y = y as isize;
+            let start = spans.partition_point(|s| s.0 < y);
+            for &(_, x1, x2) in spans[start..].iter().take_while(|s| s.0 == y) {
+                let x1 = x1.clamp(0, width) as usize;
+                let x2 = x2.clamp(0, width) as usize;
+                if x1 < x2 {
+                    blend_row(&mut row[x1..x2], color);
+                }
+            }
+        });
     }
 
-    /// Computes a line for use of drawing solid polygons.
-    fn polygon_buffer_line(
-        buff: &mut Vec<isize>,
-        right: bool,
-        x1: isize,
-        y1: isize,
-        x2: isize,
-        y2: isize,
-    ) {
-        let dx = (x2 - x1).abs();
-        let dy = (y2 - y1).abs();
-
-        let abs_m = dy as f32 / dx as f32;
-        match abs_m <= 1.0 {
-            true => {
-                let (start_x, start_y, end_x, end_y) = if x1 < x2 {
-                    (x1, y1, x2, y2)
-                } else {
-                    (x2, y2, x1, y1)
-                };
-
-                let step = if start_y < end_y { 1 } else { -1 };
-
-                let a = 2 * dy;
-                let b = a - 2 * dx;
-                let mut p = a - dx;
-
-                buff[start_y as usize] = start_x;
-                let mut new_line = false;
-
-                let mut offset = 0isize;
-                for i in 1..=(end_x - start_x) {
-                    match p < 0 {
-                        true => {
-                            p += a;
-                        }
-                        false => {
-                            offset += step;
-                            new_line = true;
-                            p += b;
-                        }
-                    }
+    fn draw_stroke_aa(&mut self, points: &[(isize, isize)], width: u32, color: RGBA) {
+        let Some((stroke, (x, y, w, h))) = Stroke::new(points, width, self.width, self.height)
+        else {
+            return;
+        };
 
-                    if right || new_line {
-                        buff[(start_y + offset) as usize] = start_x + i;
-                        new_line = false;
-                    }
+        self.for_each_row_mut(y..y + h, |py, row| {
+            for (px, pixel) in row.iter_mut().enumerate().skip(x).take(w) {
+                let a = stroke.alpha(px, py, color.a);
+                if a > 0 {
+                    *pixel = pixel.add_rgba(RGBA { a, ..color });
                 }
             }
-            false => {
-                let (start_x, start_y, end_x, end_y) = if y1 < y2 {
-                    (x1, y1, x2, y2)
-                } else {
-                    (x2, y2, x1, y1)
-                };
-
-                let step = if start_x < end_x { 1 } else { -1 };
-
-                let a = 2 * dx;
-                let b = a - 2 * dy;
-                let mut p = a - dy;
+        });
+    }
 
-                buff[start_y as usize] = start_x;
+    fn fill_rect(&mut self, x: isize, y: isize, width: usize, height: usize, color: RGBA) {
+        let Some((x, y, w, h)) = Rect::new(x, y, width, height).clip(self.width, self.height)
+        else {
+            return;
+        };
 
-                let mut offset = 0isize;
-                for i in 1..=(end_y - start_y) {
-                    match p < 0 {
-                        true => {
-                            p += a;
-                        }
-                        false => {
-                            offset += step;
-                            p += b;
-                        }
-                    }
+        self.for_each_row_mut(y..y + h, |_, row| blend_row(&mut row[x..x + w], color));
+    }
+}
 
-                    buff[(start_y + i) as usize] = start_x + offset;
-                }
-            }
-        }
+/// Blends a color onto every pixel of a row, filling it directly if the color is opaque.
+fn blend_row(row: &mut [RGB], color: RGBA) {
+    match color.a {
+        0 => {}
+        255 => fill_span(row, color.to_rgb().0),
+        _ => Blender::new(color).blend_span(row),
     }
 }
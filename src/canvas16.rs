@@ -0,0 +1,210 @@
+use crate::canvas::Canvas;
+use crate::color::{RGB, RGBA};
+use crate::target::PixelTarget;
+
+/// A color with 16 bits per channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RGB16 {
+    pub r: u16,
+    pub g: u16,
+    pub b: u16,
+}
+
+impl RGB16 {
+    /// Widens an 8-bit color, mapping `255` to `65535`.
+    pub fn from_rgb(color: RGB) -> Self {
+        Self {
+            r: color.r as u16 * 257,
+            g: color.g as u16 * 257,
+            b: color.b as u16 * 257,
+        }
+    }
+
+    /// Rounds the color to the closest 8-bit color.
+    pub fn to_rgb(self) -> RGB {
+        let narrow = |v: u16| ((v as u32 + 128) / 257) as u8;
+        RGB {
+            r: narrow(self.r),
+            g: narrow(self.g),
+            b: narrow(self.b),
+        }
+    }
+
+    /// Blends an 8-bit RGBA color onto the color keeping the full 16-bit precision of the result.
+    pub fn add_rgba(self, other: RGBA) -> Self {
+        let alpha = other.a as u32;
+        let mix = |base: u16, color: u8| {
+            ((base as u32 * (255 - alpha) + color as u32 * 257 * alpha + 127) / 255) as u16
+        };
+        Self {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+        }
+    }
+}
+
+/// A canvas storing 16 bits per channel.
+///
+/// Repeatedly blending many translucent layers onto an 8-bit [`Canvas`] rounds after every layer which shows up as banding.
+/// Drawing onto this canvas instead and converting the result once avoids it.
+/// All drawing methods of [`PixelTarget`] are available.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas16::Canvas16;
+/// use drawing_stuff::color::RGBA;
+/// use drawing_stuff::target::PixelTarget;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas16::new(WIDTH, HEIGHT);
+///
+/// // many faint layers
+/// for _ in 0..100 {
+///     canvas.fill_rect(0, 0, WIDTH, HEIGHT, RGBA { r: 255, g: 255, b: 255, a: 2 });
+/// }
+///
+/// let output = canvas.to_canvas();
+/// assert_eq!(139, output.get_at(200, 100).unwrap().r);
+/// assert_eq!(None, canvas.get_at(-1, 100));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Canvas16 {
+    width: usize,
+    height: usize,
+
+    buffer: Vec<RGB16>,
+}
+
+impl Canvas16 {
+    /// Creates a new black canvas.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            buffer: vec![RGB16::default(); width * height],
+        }
+    }
+
+    /// Creates a 16-bit copy of an 8-bit [`Canvas`].
+    pub fn from_canvas(canvas: &Canvas) -> Self {
+        Self {
+            width: canvas.width(),
+            height: canvas.height(),
+            buffer: canvas
+                .buffer()
+                .iter()
+                .map(|&c| RGB16::from_rgb(c))
+                .collect(),
+        }
+    }
+
+    /// Rounds the canvas to a new 8-bit [`Canvas`].
+    pub fn to_canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        for (pixel, color) in canvas.buffer_mut().iter_mut().zip(&self.buffer) {
+            *pixel = color.to_rgb();
+        }
+        canvas
+    }
+
+    /// Returns the width of the canvas.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a reference to the pixel buffer of the canvas.
+    pub fn buffer(&self) -> &Vec<RGB16> {
+        &self.buffer
+    }
+
+    /// Returns a mutable reference to the pixel buffer of the canvas.
+    pub fn buffer_mut(&mut self) -> &mut Vec<RGB16> {
+        &mut self.buffer
+    }
+
+    /// Checks if the pixel specified lays inside of the canvas.
+    pub fn pixel_inside(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    pub fn get_at(&self, x: isize, y: isize) -> Option<&RGB16> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.buffer.get(y as usize * self.width + x as usize)
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `get_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn get(&self, x: usize, y: usize) -> Option<&RGB16> {
+        match x < self.width && y < self.height {
+            true => self.buffer.get(y * self.width + x),
+            false => None,
+        }
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    pub fn set_at(&mut self, x: isize, y: isize, color: RGB16) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.buffer[y as usize * self.width + x as usize] = color;
+        Some(())
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `set_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn set(&mut self, x: usize, y: usize, color: RGB16) -> Option<()> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.buffer[y * self.width + x] = color;
+        Some(())
+    }
+
+    /// Fills the whole canvas with a given color.
+    pub fn fill(&mut self, color: RGB16) {
+        self.buffer.fill(color);
+    }
+}
+
+impl PixelTarget for Canvas16 {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn blend_span(&mut self, y: usize, x1: usize, x2: usize, color: RGBA) {
+        let start = y * self.width;
+        for pixel in &mut self.buffer[start + x1..start + x2] {
+            *pixel = pixel.add_rgba(color);
+        }
+    }
+}
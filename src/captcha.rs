@@ -0,0 +1,201 @@
+//! Seeded images of distorted text, e.g. for simple challenge images of services not wanting a heavyweight dependency.
+//!
+//! The text is drawn using the [`StrokeFont`], every glyph is jittered and rotated on its own and the whole image is
+//! bent by a wave. Noise lines behind and dots on top of the text make it harder to separate the glyphs.
+
+use std::f32::consts::TAU;
+
+use crate::canvas::Canvas;
+use crate::color::{RGBA, WHITE};
+use crate::font::StrokeFont;
+use crate::path::Path;
+use crate::random::Rng;
+use crate::transform::Affine;
+
+/// Longest segment of a distorted stroke in pixels, so the wave bends straight strokes.
+const SEGMENT_LENGTH: f32 = 4.0;
+/// Most segments a single stroke is split into.
+const MAX_SEGMENTS: usize = 256;
+
+/// Generator of distorted text images.
+///
+/// The same seed always produces the same distortion and noise.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::captcha::Captcha;
+///
+/// const WIDTH: usize = 240;
+/// const HEIGHT: usize = 80;
+///
+/// let captcha = Captcha::new(42).size(40.0).stroke_width(4).noise(4, 60);
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+/// captcha.draw(&mut canvas, "K7XQ");
+///
+/// let mut again = Canvas::new(WIDTH, HEIGHT);
+/// captcha.draw(&mut again, "K7XQ");
+/// assert!(canvas.approx_eq(&again, 0));
+///
+/// let mut other = Canvas::new(WIDTH, HEIGHT);
+/// Captcha::new(7).size(40.0).stroke_width(4).draw(&mut other, "K7XQ");
+/// assert!(!canvas.approx_eq(&other, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Captcha {
+    seed: u64,
+
+    size: f32,
+    stroke_width: u32,
+    jitter: f32,
+    rotation: f32,
+    amplitude: f32,
+    wavelength: f32,
+    lines: usize,
+    dots: usize,
+    color: RGBA,
+}
+
+impl Captcha {
+    /// Creates a new generator drawing white text 48 pixels high with a moderate distortion.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            size: 48.0,
+            stroke_width: 4,
+            jitter: 4.0,
+            rotation: 0.35,
+            amplitude: 4.0,
+            wavelength: 120.0,
+            lines: 3,
+            dots: 100,
+            color: WHITE,
+        }
+    }
+
+    /// Sets the height of capital letters in pixels.
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets the width of the strokes of the text and the noise lines in pixels.
+    pub fn stroke_width(mut self, width: u32) -> Self {
+        self.stroke_width = width;
+        self
+    }
+
+    /// Sets the largest distance in pixels every glyph is moved by.
+    pub fn jitter(mut self, jitter: f32) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the largest angle in radians every glyph is rotated by, in either direction.
+    pub fn rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    /// Sets the amplitude and the wavelength in pixels of the vertical wave bending the whole image.
+    ///
+    /// A wavelength of `0.0` disables the wave.
+    pub fn wave(mut self, amplitude: f32, wavelength: f32) -> Self {
+        self.amplitude = amplitude;
+        self.wavelength = wavelength;
+        self
+    }
+
+    /// Sets the number of noise lines drawn across the image and the number of dots scattered over it.
+    pub fn noise(mut self, lines: usize, dots: usize) -> Self {
+        self.lines = lines;
+        self.dots = dots;
+        self
+    }
+
+    /// Sets the color of the text and the noise.
+    pub fn color(mut self, color: RGBA) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Draws a single line of distorted text centered on the canvas.
+    ///
+    /// Control characters are skipped.
+    pub fn draw(&self, canvas: &mut Canvas, text: &str) {
+        let mut rng = Rng::new(self.seed);
+        let (width, height) = (canvas.width() as f32, canvas.height() as f32);
+
+        let phase = rng.range_f32(0.0, TAU);
+        let wave = |(x, y): (f32, f32)| match self.wavelength != 0.0 {
+            true => (
+                x,
+                y + self.amplitude * (TAU * x / self.wavelength + phase).sin(),
+            ),
+            false => (x, y),
+        };
+
+        // noise lines behind the text
+        for _ in 0..self.lines {
+            let from = (0.0, rng.range_f32(0.0, height));
+            let to = (width, rng.range_f32(0.0, height));
+            let points = subdivide(&[from, to]).into_iter().map(wave).collect();
+            Path::new(points).stroke(canvas, (self.stroke_width / 2).max(1), self.color);
+        }
+
+        let glyphs: Vec<char> = text.chars().filter(|ch| !ch.is_control()).collect();
+        if !glyphs.is_empty() {
+            // glyphs are two thirds as wide as they are high
+            let glyph_width = self.size * 2.0 / 3.0;
+            let advance = StrokeFont.advance(self.size);
+            let text_width = (glyphs.len() - 1) as f32 * advance + glyph_width;
+            let origin = ((width - text_width) / 2.0, (height - self.size) / 2.0);
+
+            for (i, &ch) in glyphs.iter().enumerate() {
+                let angle = rng.range_f32(-self.rotation, self.rotation);
+                let offset = (
+                    rng.range_f32(-self.jitter, self.jitter),
+                    rng.range_f32(-self.jitter, self.jitter),
+                );
+                let transform = Affine::rotate_around(angle, glyph_width / 2.0, self.size / 2.0)
+                    .then(Affine::translate(
+                        origin.0 + i as f32 * advance + offset.0,
+                        origin.1 + offset.1,
+                    ));
+
+                for path in StrokeFont.glyph_path(ch, self.size) {
+                    let points = subdivide(&path.points)
+                        .into_iter()
+                        .map(|point| wave(transform.apply(point)))
+                        .collect();
+                    Path::new(points).stroke(canvas, self.stroke_width, self.color);
+                }
+            }
+        }
+
+        // noise dots on top of the text
+        for _ in 0..self.dots {
+            let x = rng.below(canvas.width()) as isize;
+            let y = rng.below(canvas.height()) as isize;
+            let r = 1 + rng.below(2) as u32;
+            canvas.draw_circle_solid(x, y, r, self.color);
+        }
+    }
+}
+
+/// Splits the segments between the points into pieces of at most [`SEGMENT_LENGTH`] pixels.
+fn subdivide(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut subdivided = points.first().copied().into_iter().collect::<Vec<_>>();
+    for w in points.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let length = ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+        let count = ((length / SEGMENT_LENGTH).ceil() as usize).clamp(1, MAX_SEGMENTS);
+        subdivided.extend((1..=count).map(|i| {
+            let t = i as f32 / count as f32;
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+        }));
+    }
+    subdivided
+}
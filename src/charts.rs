@@ -0,0 +1,2504 @@
+//! Simple charts drawing data series into a rectangle of a canvas.
+//!
+//! Every chart maps its data onto the rectangle using an [`Axis`] per direction, draws gridlines at
+//! evenly spaced tick values and frames the data with axis lines.
+//! Charts draw no labels themselves. [`Axis::ticks`] and [`Axis::position`] return the values and positions
+//! of the gridlines and [`Axis::tick_labels`] formats them, e.g. to draw them using [`StrokeFont`](crate::font::StrokeFont).
+//! Distributions of samples are summarized by a [`BoxPlot`], optionally with violins.
+//! A [`Figure`] arranges multiple charts implementing [`Plot`] in a grid of panels, optionally sharing their axes.
+
+use crate::canvas::{Canvas, CanvasView, Draw, Rect};
+use crate::color::RGBA;
+use crate::gradient::Gradient;
+use crate::target::PixelTarget;
+
+/// Distinct colors to tell apart multiple series of a chart.
+pub const SERIES_COLORS: [RGBA; 8] = [
+    RGBA::new(31, 119, 180, 255),
+    RGBA::new(255, 127, 14, 255),
+    RGBA::new(44, 160, 44, 255),
+    RGBA::new(214, 39, 40, 255),
+    RGBA::new(148, 103, 189, 255),
+    RGBA::new(140, 86, 75, 255),
+    RGBA::new(227, 119, 194, 255),
+    RGBA::new(23, 190, 207, 255),
+];
+
+/// The range of data values shown along one direction of a chart.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::charts::Axis;
+///
+/// let axis = Axis::fit([3.2, -1.0, 7.5]);
+/// assert_eq!(Axis::new(-1.0, 7.5), axis);
+///
+/// assert_eq!(vec![0.0, 5.0], axis.ticks(3));
+/// assert_eq!(50.0, Axis::new(0.0, 10.0).position(5.0, 100.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Axis {
+    pub min: f64,
+    pub max: f64,
+
+    pub scale: Scale,
+}
+
+impl Axis {
+    /// Creates a new axis with a linear scale.
+    pub fn new(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            scale: Scale::Linear,
+        }
+    }
+
+    /// Sets how values are mapped onto the axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::charts::{Axis, Scale};
+    ///
+    /// let axis = Axis::new(1.0, 1000.0).scale(Scale::Log);
+    ///
+    /// assert_eq!(vec![1.0, 10.0, 100.0, 1000.0], axis.ticks(3));
+    /// assert!((axis.position(10.0, 300.0) - 100.0).abs() < 1e-9);
+    ///
+    /// // symmetric around zero and linear close to it
+    /// let axis = Axis::new(-100.0, 100.0).scale(Scale::SymLog(1.0));
+    /// assert_eq!(vec![-100.0, -10.0, -1.0, 0.0, 1.0, 10.0, 100.0], axis.ticks(7));
+    /// ```
+    pub fn scale(mut self, scale: Scale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Creates an axis exactly covering all finite values.
+    ///
+    /// Without values the axis covers `0..1`, a single distinct value is centered on an axis of length `1`.
+    pub fn fit<I>(values: I) -> Self
+    where
+        I: IntoIterator<Item = f64>,
+    {
+        let (min, max) = values
+            .into_iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                (min.min(v), max.max(v))
+            });
+
+        match (min.is_finite(), min == max) {
+            (false, _) => Self::new(0.0, 1.0),
+            (true, true) => Self::new(min - 0.5, max + 0.5),
+            (true, false) => Self::new(min, max),
+        }
+    }
+
+    /// Returns the axis extended to include a value, e.g. to keep zero visible.
+    pub fn including(self, value: f64) -> Self {
+        Self {
+            min: self.min.min(value),
+            max: self.max.max(value),
+            ..self
+        }
+    }
+
+    /// Returns round values inside of the axis for gridlines, roughly `count` of them.
+    ///
+    /// On a linear scale the values are evenly spaced by a step of `1`, `2` or `5` times a power of ten.
+    /// Logarithmic scales use powers of ten, adding `2` and `5` times them if there are only few decades.
+    /// Time scales use round steps of seconds, minutes, hours, days, weeks starting on mondays, months or years.
+    pub fn ticks(&self, count: usize) -> Vec<f64> {
+        let range = self.max - self.min;
+        if count == 0 || !range.is_finite() || range <= 0.0 {
+            return Vec::new();
+        }
+
+        match self.scale {
+            Scale::Linear => linear_ticks(self.min, self.max, count),
+            Scale::Time => time_ticks(self.min, self.max, count),
+            Scale::Log => {
+                if self.min <= 0.0 {
+                    return Vec::new();
+                }
+                match log_ticks(self.min, self.max, count) {
+                    ticks if ticks.len() < 2 => linear_ticks(self.min, self.max, count),
+                    ticks => ticks,
+                }
+            }
+            Scale::SymLog(threshold) => {
+                // powers of ten on both sides of the linear part around zero
+                let threshold = threshold.abs().max(f64::MIN_POSITIVE);
+                let negative = match self.min < -threshold {
+                    true => log_ticks(threshold, -self.min, count / 2 + 1),
+                    false => Vec::new(),
+                };
+                let positive = match self.max > threshold {
+                    true => log_ticks(threshold, self.max, count / 2 + 1),
+                    false => Vec::new(),
+                };
+
+                let mut ticks: Vec<f64> = negative.into_iter().rev().map(|v| -v).collect();
+                if self.min <= 0.0 && self.max >= 0.0 {
+                    ticks.push(0.0);
+                }
+                ticks.extend(positive);
+                match ticks.len() < 2 {
+                    true => linear_ticks(self.min, self.max, count),
+                    false => ticks,
+                }
+            }
+        }
+    }
+
+    /// Returns the values of roughly `count` gridlines like [`Axis::ticks`] together with their labels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::charts::{Axis, Locale, TickFormat};
+    ///
+    /// let axis = Axis::new(0.0, 2000.0);
+    /// let labels: Vec<String> = axis
+    ///     .tick_labels(4, TickFormat::Si(1), &Locale::GERMAN)
+    ///     .into_iter()
+    ///     .map(|(_, label)| label)
+    ///     .collect();
+    ///
+    /// assert_eq!(vec!["0,0", "500,0", "1,0k", "1,5k", "2,0k"], labels);
+    /// ```
+    pub fn tick_labels(
+        &self,
+        count: usize,
+        format: TickFormat,
+        locale: &Locale,
+    ) -> Vec<(f64, String)> {
+        self.ticks(count)
+            .into_iter()
+            .map(|tick| (tick, format.format(tick, locale)))
+            .collect()
+    }
+
+    /// Returns the position of a value along an axis of the specified length in pixels, `0` being at [`Axis::min`].
+    ///
+    /// Values which can't be shown on the scale, like zero on a logarithmic one, result in a non-finite position.
+    pub fn position(&self, value: f64, length: f64) -> f64 {
+        let (min, max) = (self.scale.apply(self.min), self.scale.apply(self.max));
+        match max - min {
+            0.0 => length / 2.0,
+            range => (self.scale.apply(value) - min) / range * length,
+        }
+    }
+}
+
+/// How data values are mapped onto an [`Axis`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Scale {
+    #[default]
+    Linear,
+    /// Logarithmic with base ten, for positive values spanning several orders of magnitude.
+    Log,
+    /// Symmetric logarithmic, linear between `-threshold` and `threshold` and logarithmic beyond,
+    /// for wide ranges of values including zero and negative ones.
+    SymLog(f64),
+    /// Linear for unix timestamps in seconds, placing ticks at round points in time in UTC.
+    ///
+    /// Types of date and time libraries can be converted into timestamps, e.g. using `timestamp()` of a chrono `DateTime`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::charts::{Axis, Locale, Scale, TickFormat};
+    ///
+    /// // 2024-01-10 to 2024-05-20
+    /// let axis = Axis::new(1704844800.0, 1716163200.0).scale(Scale::Time);
+    ///
+    /// let labels: Vec<String> = axis
+    ///     .tick_labels(5, TickFormat::DateTime("%b %Y"), &Locale::ENGLISH)
+    ///     .into_iter()
+    ///     .map(|(_, label)| label)
+    ///     .collect();
+    /// assert_eq!(vec!["Feb 2024", "Mar 2024", "Apr 2024", "May 2024"], labels);
+    /// ```
+    Time,
+}
+
+impl Scale {
+    /// Maps a value onto a linear scale.
+    fn apply(&self, value: f64) -> f64 {
+        match *self {
+            Scale::Linear | Scale::Time => value,
+            Scale::Log => value.log10(),
+            Scale::SymLog(threshold) => {
+                let threshold = threshold.abs().max(f64::MIN_POSITIVE);
+                value.signum() * (1.0 + value.abs() / threshold).log10()
+            }
+        }
+    }
+}
+
+/// Returns values evenly spaced by a step of `1`, `2` or `5` times a power of ten, roughly `count` of them.
+fn linear_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    let raw = (max - min) / count as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|f| f * magnitude)
+        .find(|&step| step >= raw)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).ceil() as i64;
+    let last = (max / step + 1e-9).floor() as i64;
+    (first..=last).map(|i| i as f64 * step).collect()
+}
+
+/// Returns round points in time between two unix timestamps in seconds, roughly `count` of them.
+fn time_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    const MINUTE: f64 = 60.0;
+    const HOUR: f64 = 60.0 * MINUTE;
+    const DAY: f64 = 24.0 * HOUR;
+    const WEEK: f64 = 7.0 * DAY;
+    // 1970-01-05 was the first monday after the epoch
+    const MONDAY: f64 = 4.0 * DAY;
+
+    let raw = (max - min) / count as f64;
+    let fixed = [
+        (1.0, 0.0),
+        (2.0, 0.0),
+        (5.0, 0.0),
+        (10.0, 0.0),
+        (15.0, 0.0),
+        (30.0, 0.0),
+        (MINUTE, 0.0),
+        (2.0 * MINUTE, 0.0),
+        (5.0 * MINUTE, 0.0),
+        (10.0 * MINUTE, 0.0),
+        (15.0 * MINUTE, 0.0),
+        (30.0 * MINUTE, 0.0),
+        (HOUR, 0.0),
+        (2.0 * HOUR, 0.0),
+        (3.0 * HOUR, 0.0),
+        (6.0 * HOUR, 0.0),
+        (12.0 * HOUR, 0.0),
+        (DAY, 0.0),
+        (2.0 * DAY, 0.0),
+        (WEEK, MONDAY),
+        (2.0 * WEEK, MONDAY),
+    ];
+    // below a second and beyond dates the calendar math handles
+    if raw < 1.0 || min.abs().max(max.abs()) > 1e15 {
+        return linear_ticks(min, max, count);
+    }
+    if let Some(&(step, offset)) = fixed.iter().find(|(step, _)| *step >= raw) {
+        let first = ((min - offset) / step).ceil() as i64;
+        let last = ((max - offset) / step).floor() as i64;
+        return (first..=last).map(|i| i as f64 * step + offset).collect();
+    }
+
+    // steps of whole months, years beyond half a year
+    let months = [1, 2, 3, 6]
+        .into_iter()
+        .find(|&months| months as f64 * 30.4 * DAY >= raw)
+        .unwrap_or_else(|| {
+            12 * linear_ticks(0.0, (max - min) / (365.25 * DAY), count)
+                .get(1)
+                .map_or(1, |&years| years.max(1.0) as i64)
+        });
+
+    let (first_year, first_month, _) = civil_from_days((min / DAY).floor() as i64);
+    let mut month = (first_year * 12 + first_month as i64 - 1).div_euclid(months) * months;
+    let mut ticks = Vec::new();
+    loop {
+        let tick =
+            days_from_civil(month.div_euclid(12), month.rem_euclid(12) as u32 + 1, 1) as f64 * DAY;
+        if tick > max {
+            break;
+        }
+        if tick >= min {
+            ticks.push(tick);
+        }
+        month += months;
+    }
+    ticks
+}
+
+/// Returns powers of ten between two positive values, roughly `count` of them.
+///
+/// Every decade also gets a tick at `2` and `5` times its power if there are only few decades.
+fn log_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    let (low, high) = (min.log10(), max.log10());
+    if !low.is_finite() || !high.is_finite() {
+        return Vec::new();
+    }
+
+    let decades = high - low;
+    let (mantissas, step): (&[f64], i32) = match decades * 3.0 <= count as f64 {
+        true => (&[1.0, 2.0, 5.0], 1),
+        false => (
+            &[1.0],
+            (decades / count.max(1) as f64).ceil().max(1.0) as i32,
+        ),
+    };
+
+    let tolerance = 1e-9;
+    let mut ticks = Vec::new();
+    let mut exponent = low.floor() as i32;
+    while exponent <= high.ceil() as i32 {
+        for &mantissa in mantissas {
+            let tick = mantissa * 10f64.powi(exponent);
+            if tick >= min * (1.0 - tolerance) && tick <= max * (1.0 + tolerance) {
+                ticks.push(tick);
+            }
+        }
+        exponent += step;
+    }
+    ticks
+}
+
+/// Conventions used when formatting tick labels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub decimal_separator: char,
+    /// Names of the months from January to December.
+    pub months: [&'static str; 12],
+    /// Abbreviated names of the months from January to December.
+    pub short_months: [&'static str; 12],
+}
+
+impl Locale {
+    pub const ENGLISH: Locale = Locale {
+        decimal_separator: '.',
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        short_months: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+    };
+
+    pub const GERMAN: Locale = Locale {
+        decimal_separator: ',',
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        short_months: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+    };
+
+    pub const FRENCH: Locale = Locale {
+        decimal_separator: ',',
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        short_months: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+    };
+
+    pub const SPANISH: Locale = Locale {
+        decimal_separator: ',',
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        short_months: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+    };
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// How the values of ticks are turned into labels.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::charts::{Locale, TickFormat};
+///
+/// let en = Locale::ENGLISH;
+/// assert_eq!("0.3", TickFormat::Auto.format(0.1 + 0.2, &en));
+/// assert_eq!("2.50", TickFormat::Decimal(2).format(2.5, &en));
+/// assert_eq!("4.7µ", TickFormat::Si(1).format(0.0000047, &en));
+/// assert_eq!("12.5%", TickFormat::Percent(1).format(0.125, &en));
+///
+/// // 2024-03-05 14:30:00 UTC
+/// let format = TickFormat::DateTime("%d. %B %Y %H:%M");
+/// assert_eq!("05. März 2024 14:30", format.format(1709649000.0, &Locale::GERMAN));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TickFormat {
+    /// The shortest decimal representation with up to six decimals.
+    #[default]
+    Auto,
+    /// A fixed number of decimals.
+    Decimal(usize),
+    /// A fixed number of decimals after scaling the value by a power of a thousand and appending its SI prefix,
+    /// e.g. `k` for thousands or `m` for thousandths.
+    Si(usize),
+    /// The value multiplied by 100 with a fixed number of decimals and a percent sign.
+    Percent(usize),
+    /// The value as a unix timestamp in seconds, formatted in UTC by a pattern.
+    ///
+    /// The pattern replaces `%Y` by the year, `%m` by the month, `%d` by the day, `%H` by the hour,
+    /// `%M` by the minute and `%S` by the second, all but the year padded to two digits.
+    /// `%B` is replaced by the name of the month, `%b` by its abbreviated name and `%%` by a percent sign.
+    DateTime(&'static str),
+}
+
+impl TickFormat {
+    /// Formats a value into a label.
+    pub fn format(&self, value: f64, locale: &Locale) -> String {
+        match *self {
+            TickFormat::Auto => {
+                let text = format_decimal(value, 6, locale.decimal_separator);
+                match text.contains(locale.decimal_separator) {
+                    true => text
+                        .trim_end_matches('0')
+                        .trim_end_matches(locale.decimal_separator)
+                        .to_string(),
+                    false => text,
+                }
+            }
+            TickFormat::Decimal(decimals) => {
+                format_decimal(value, decimals, locale.decimal_separator)
+            }
+            TickFormat::Si(decimals) => {
+                const PREFIXES: [&str; 9] = ["p", "n", "µ", "m", "", "k", "M", "G", "T"];
+                let mut exponent = match value == 0.0 || !value.is_finite() {
+                    true => 0,
+                    false => (value.abs().log10() / 3.0).floor().clamp(-4.0, 4.0) as i32,
+                };
+                // rounding can carry over into the next prefix, e.g. 999.96 with one decimal
+                let rounded = |exponent: i32| {
+                    let scaled = value / 1000f64.powi(exponent);
+                    let factor = 10f64.powi(decimals as i32);
+                    (scaled * factor).round() / factor
+                };
+                if rounded(exponent).abs() >= 1000.0 && exponent < 4 {
+                    exponent += 1;
+                }
+                let prefix = PREFIXES[(exponent + 4) as usize];
+                let text = format_decimal(rounded(exponent), decimals, locale.decimal_separator);
+                format!("{text}{prefix}")
+            }
+            TickFormat::Percent(decimals) => {
+                let text = format_decimal(value * 100.0, decimals, locale.decimal_separator);
+                format!("{text}%")
+            }
+            TickFormat::DateTime(pattern) => format_datetime(value, pattern, locale),
+        }
+    }
+}
+
+/// Formats a value with a fixed number of decimals, never showing a negative zero.
+fn format_decimal(value: f64, decimals: usize, separator: char) -> String {
+    let text = format!("{value:.decimals$}");
+    let text = match text
+        .trim_start_matches('-')
+        .chars()
+        .all(|c| c == '0' || c == '.')
+    {
+        true => text.trim_start_matches('-').to_string(),
+        false => text,
+    };
+    match separator {
+        '.' => text,
+        _ => text.replace('.', &separator.to_string()),
+    }
+}
+
+/// Formats a unix timestamp in seconds in UTC, see [`TickFormat::DateTime`].
+fn format_datetime(timestamp: f64, pattern: &str, locale: &Locale) -> String {
+    if !timestamp.is_finite() {
+        return String::new();
+    }
+
+    let seconds = timestamp.floor() as i64;
+    let (days, time) = (seconds.div_euclid(86400), seconds.rem_euclid(86400));
+    let (year, month, day) = civil_from_days(days);
+    let (hour, minute, second) = (time / 3600, time / 60 % 60, time % 60);
+
+    let mut output = String::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => output.push_str(&year.to_string()),
+            Some('m') => output.push_str(&format!("{month:02}")),
+            Some('d') => output.push_str(&format!("{day:02}")),
+            Some('H') => output.push_str(&format!("{hour:02}")),
+            Some('M') => output.push_str(&format!("{minute:02}")),
+            Some('S') => output.push_str(&format!("{second:02}")),
+            Some('B') => output.push_str(locale.months[month as usize - 1]),
+            Some('b') => output.push_str(locale.short_months[month as usize - 1]),
+            Some(other) => {
+                output.push('%');
+                if other != '%' {
+                    output.push(other);
+                }
+            }
+            None => output.push('%'),
+        }
+    }
+    output
+}
+
+/// Converts days since the unix epoch into a date `(year, month, day)` of the proleptic gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // shift the epoch to 0000-03-01, making leap days the last day of a year
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Converts a date of the proleptic gregorian calendar into days since the unix epoch.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let day_of_year = (153 * mp + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Colors of the parts of a chart besides the data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartStyle {
+    /// Color filling the rectangle of the chart, `None` keeps the canvas visible.
+    pub background: Option<RGBA>,
+    /// Color of the axis lines at the left and bottom edge.
+    pub axis_color: RGBA,
+    /// Color of the gridlines, `None` draws no gridlines.
+    pub grid_color: Option<RGBA>,
+    /// Rough number of gridlines along each axis.
+    pub ticks: usize,
+}
+
+impl Default for ChartStyle {
+    fn default() -> Self {
+        Self {
+            background: None,
+            axis_color: RGBA::new(255, 255, 255, 255),
+            grid_color: Some(RGBA::new(255, 255, 255, 40)),
+            ticks: 5,
+        }
+    }
+}
+
+/// A chart which can be drawn into any rectangle using any axes, e.g. as a panel of a [`Figure`].
+pub trait Plot {
+    /// Returns the x and y axis the chart uses when drawn on its own.
+    fn axes(&self) -> (Axis, Axis);
+
+    /// Draws the chart into a rectangle using the specified axes instead of its own ones.
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis);
+}
+
+/// A series of points connected by straight lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineSeries {
+    /// Points of the series as `(x, y)` in data coordinates. Points with a non-finite coordinate leave a gap.
+    pub points: Vec<(f64, f64)>,
+    /// How consecutive points are connected.
+    pub step: Step,
+    /// Color of the area between the series and zero, or the closest end of the y axis if zero lays outside of it.
+    /// `None` leaves the area empty.
+    pub fill: Option<RGBA>,
+
+    pub width: u32,
+    pub color: RGBA,
+}
+
+impl LineSeries {
+    /// Returns the points of the series with the corners of the steps inserted.
+    fn stepped(&self) -> Vec<(f64, f64)> {
+        let mut points = Vec::with_capacity(self.points.len() * 3);
+        for (i, &(x, y)) in self.points.iter().enumerate() {
+            if let Some(&(px, py)) = i.checked_sub(1).map(|p| &self.points[p]) {
+                let finite = px.is_finite() && py.is_finite() && x.is_finite() && y.is_finite();
+                match (finite, self.step) {
+                    (false, _) | (true, Step::Linear) => {}
+                    (true, Step::Pre) => points.push((px, y)),
+                    (true, Step::Post) => points.push((x, py)),
+                    (true, Step::Mid) => {
+                        let mid = (px + x) / 2.0;
+                        points.push((mid, py));
+                        points.push((mid, y));
+                    }
+                }
+            }
+            points.push((x, y));
+        }
+        points
+    }
+}
+
+/// How a [`LineSeries`] connects consecutive points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Step {
+    /// Straight lines between the points.
+    #[default]
+    Linear,
+    /// Steps to the value of the next point at the current point, e.g. for monitoring values reported at the end of intervals.
+    Pre,
+    /// Steps to the value of the next point at the next point, e.g. for cumulative distributions.
+    Post,
+    /// Steps to the value of the next point halfway between the points.
+    Mid,
+}
+
+/// A series of vertical error bars, e.g. the spread of repeated measurements.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorBarSeries {
+    /// Bars as `(x, low, high)` in data coordinates. Bars with a non-finite coordinate are left out.
+    pub bars: Vec<(f64, f64, f64)>,
+    /// Width of the caps at both ends of every bar in pixels, `0` draws no caps.
+    pub cap: u32,
+
+    pub color: RGBA,
+}
+
+/// A filled area between two curves, e.g. a confidence band around a [`LineSeries`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BandSeries {
+    /// Points of both curves as `(x, low, high)` in data coordinates sorted by `x`.
+    /// Points with a non-finite coordinate leave a gap.
+    pub points: Vec<(f64, f64, f64)>,
+
+    /// Color of the area, usually translucent to keep the gridlines and other series visible.
+    pub color: RGBA,
+}
+
+/// A chart of one or more [`LineSeries`] with optional [`BandSeries`] below and [`ErrorBarSeries`] above them.
+///
+/// Parts of the series outside of the axes are clipped at the rectangle of the chart.
+/// The filled areas of all series are drawn below their lines.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, LineChart, LineSeries, Step, SERIES_COLORS};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let points: Vec<(f64, f64)> = (0..=100).map(|i| (i as f64, (i as f64 / 10.0).sin())).collect();
+/// let chart = LineChart {
+///     rect: Rect::new(40, 40, 1000, 640),
+///     x_axis: Axis::fit(points.iter().map(|p| p.0)),
+///     y_axis: Axis::new(-1.5, 1.5),
+///     series: vec![LineSeries {
+///         points,
+///         step: Step::Linear,
+///         fill: None,
+///         width: 2,
+///         color: SERIES_COLORS[0],
+///     }],
+///     bands: Vec::new(),
+///     error_bars: Vec::new(),
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&chart);
+///
+/// // the axis line at the bottom edge
+/// assert_eq!(255, canvas.get_at(500, 679).unwrap().r);
+/// // the series at its lowest point in the middle of the chart
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(540, 564));
+/// ```
+///
+/// A measured mean with its confidence band and the spread of the measurements:
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{
+///     Axis, BandSeries, ChartStyle, ErrorBarSeries, LineChart, LineSeries, Step, SERIES_COLORS,
+/// };
+/// use drawing_stuff::color::RGBA;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let chart = LineChart {
+///     rect: Rect::new(0, 0, 401, 201),
+///     x_axis: Axis::new(0.0, 4.0),
+///     y_axis: Axis::new(0.0, 10.0),
+///     series: vec![LineSeries {
+///         points: vec![(0.0, 5.0), (4.0, 5.0)],
+///         step: Step::Linear,
+///         fill: None,
+///         width: 1,
+///         color: SERIES_COLORS[0],
+///     }],
+///     bands: vec![BandSeries {
+///         points: vec![(0.0, 4.0, 6.0), (4.0, 3.0, 7.0)],
+///         color: RGBA { a: 128, ..SERIES_COLORS[0] },
+///     }],
+///     error_bars: vec![ErrorBarSeries {
+///         bars: vec![(1.0, 2.0, 8.0), (3.0, 1.0, 9.0)],
+///         cap: 9,
+///         color: SERIES_COLORS[1],
+///     }],
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&chart);
+///
+/// // inside of the band, but outside of it further left
+/// assert!(canvas.get_at(200, 70).unwrap().r > 0);
+/// assert_eq!(0, canvas.get_at(20, 70).unwrap().r);
+/// // the error bar at x = 1 reaching up to 8 with its cap
+/// assert_eq!(Some(&SERIES_COLORS[1].to_rgb().0), canvas.get_at(100, 50));
+/// assert_eq!(Some(&SERIES_COLORS[1].to_rgb().0), canvas.get_at(104, 40));
+/// ```
+///
+/// A filled step series, e.g. an empirical cumulative distribution:
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, LineChart, LineSeries, Step, SERIES_COLORS};
+/// use drawing_stuff::color::RGBA;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let chart = LineChart {
+///     rect: Rect::new(0, 0, 401, 201),
+///     x_axis: Axis::new(0.0, 4.0),
+///     y_axis: Axis::new(0.0, 1.0),
+///     series: vec![LineSeries {
+///         points: vec![(0.0, 0.25), (1.0, 0.5), (3.0, 1.0)],
+///         step: Step::Post,
+///         fill: Some(RGBA { a: 128, ..SERIES_COLORS[0] }),
+///         width: 1,
+///         color: SERIES_COLORS[0],
+///     }],
+///     bands: Vec::new(),
+///     error_bars: Vec::new(),
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&chart);
+///
+/// // the value of 0.5 holds until x = 3 where it steps up
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(250, 100));
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(300, 50));
+/// // the area below the step is filled, the area above it is not
+/// assert!(canvas.get_at(250, 150).unwrap().r > 0);
+/// assert_eq!(0, canvas.get_at(250, 50).unwrap().r);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineChart {
+    pub rect: Rect,
+    pub x_axis: Axis,
+    pub y_axis: Axis,
+
+    pub series: Vec<LineSeries>,
+    /// Areas drawn below all series.
+    pub bands: Vec<BandSeries>,
+    /// Error bars drawn above all series.
+    pub error_bars: Vec<ErrorBarSeries>,
+    pub style: ChartStyle,
+}
+
+impl Draw for LineChart {
+    fn draw(&self, canvas: &mut Canvas) {
+        self.draw_with(canvas, self.rect, self.x_axis, self.y_axis);
+    }
+}
+
+impl Plot for LineChart {
+    fn axes(&self) -> (Axis, Axis) {
+        (self.x_axis, self.y_axis)
+    }
+
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            true,
+            |view, frame| {
+                for band in &self.bands {
+                    fill_band(view, frame, &band.points, band.color);
+                }
+
+                let baseline = 0.0f64.clamp(y_axis.min.min(y_axis.max), y_axis.max.max(y_axis.min));
+                let stepped: Vec<_> = self.series.iter().map(LineSeries::stepped).collect();
+                for (series, points) in self.series.iter().zip(&stepped) {
+                    if let Some(fill) = series.fill {
+                        let area: Vec<_> = points.iter().map(|&(x, y)| (x, baseline, y)).collect();
+                        fill_band(view, frame, &area, fill);
+                    }
+                }
+
+                for (series, points) in self.series.iter().zip(&stepped) {
+                    let points: Vec<_> = points.iter().map(|&(x, y)| frame.project(x, y)).collect();
+                    frame.stroke(view, &points, series.width, series.color);
+                }
+
+                for error_bars in &self.error_bars {
+                    let half = error_bars.cap as f64 / 2.0;
+                    for &(x, low, high) in &error_bars.bars {
+                        if ![x, low, high].iter().all(|v| v.is_finite()) {
+                            continue;
+                        }
+
+                        let (low, high) = (frame.project(x, low), frame.project(x, high));
+                        frame.stroke(view, &[low, high], 1, error_bars.color);
+                        if error_bars.cap > 0 {
+                            for (px, py) in [low, high] {
+                                let cap = [(px - half, py), (px + half, py)];
+                                frame.stroke(view, &cap, 1, error_bars.color);
+                            }
+                        }
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// A series of values, one per category of a [`BarChart`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarSeries {
+    /// Values of the series, non-finite values leave their bar out.
+    pub values: Vec<f64>,
+
+    pub color: RGBA,
+}
+
+/// A chart of bars grouped by category, every [`BarSeries`] adding one bar per category.
+///
+/// Bars start at zero, or the closest end of the axis if zero lays outside of it.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, BarChart, BarSeries, ChartStyle, SERIES_COLORS};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let chart = BarChart {
+///     rect: Rect::new(0, 0, 400, 200),
+///     y_axis: Axis::new(0.0, 10.0),
+///     series: vec![
+///         BarSeries { values: vec![4.0, 8.0], color: SERIES_COLORS[0] },
+///         BarSeries { values: vec![6.0, 2.0], color: SERIES_COLORS[1] },
+///     ],
+///     gap: 0.2,
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&chart);
+///
+/// // first bar of the second category reaching 80% of the height
+/// assert_eq!(SERIES_COLORS[0].r, canvas.get_at(260, 50).unwrap().r);
+/// assert_eq!(0, canvas.get_at(260, 30).unwrap().r);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarChart {
+    pub rect: Rect,
+    pub y_axis: Axis,
+
+    pub series: Vec<BarSeries>,
+    /// Fraction of the width of every category left empty between the groups of bars.
+    pub gap: f32,
+    pub style: ChartStyle,
+}
+
+impl Draw for BarChart {
+    fn draw(&self, canvas: &mut Canvas) {
+        let (x_axis, y_axis) = self.axes();
+        self.draw_with(canvas, self.rect, x_axis, y_axis);
+    }
+}
+
+impl Plot for BarChart {
+    /// Returns an x axis covering the categories, each of them `1.0` wide, and the y axis of the chart.
+    fn axes(&self) -> (Axis, Axis) {
+        let categories = self.series.iter().map(|s| s.values.len()).max();
+        let categories = categories.unwrap_or(0).max(1);
+        (Axis::new(0.0, categories as f64), self.y_axis)
+    }
+
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            false,
+            |view, frame| {
+                let gap = self.gap.clamp(0.0, 1.0) as f64;
+                let bar_width = (1.0 - gap) / self.series.len().max(1) as f64;
+                let baseline = 0.0f64.clamp(y_axis.min.min(y_axis.max), y_axis.max.max(y_axis.min));
+
+                for (s, series) in self.series.iter().enumerate() {
+                    for (category, &value) in series.values.iter().enumerate() {
+                        if !value.is_finite() {
+                            continue;
+                        }
+
+                        let left = category as f64 + gap / 2.0 + s as f64 * bar_width;
+                        let (x1, y1) = frame.project(left, baseline);
+                        let (x2, y2) = frame.project(left + bar_width, value);
+                        frame.fill_rect(view, (x1, y1), (x2, y2), series.color);
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// Maps data coordinates onto the pixels of the view of a chart.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Frame {
+    x_axis: Axis,
+    y_axis: Axis,
+
+    /// Position of the unclipped chart rectangle relative to the view.
+    offset: (f64, f64),
+    width: f64,
+    height: f64,
+}
+
+impl Frame {
+    /// Returns the position of a data point in pixels relative to the view.
+    pub(crate) fn project(&self, x: f64, y: f64) -> (f64, f64) {
+        let px = self.x_axis.position(x, self.width - 1.0);
+        let py = self.height - 1.0 - self.y_axis.position(y, self.height - 1.0);
+        (px + self.offset.0, py + self.offset.1)
+    }
+
+    /// Strokes a line through points in pixels, leaving gaps at non-finite points.
+    pub(crate) fn stroke<T: PixelTarget>(
+        &self,
+        view: &mut T,
+        points: &[(f64, f64)],
+        width: u32,
+        color: RGBA,
+    ) {
+        // generously larger than the view to keep the clipped end from showing
+        let margin = width as f64 + 2.0;
+        let bounds = (
+            -margin,
+            -margin,
+            view.width() as f64 + margin,
+            view.height() as f64 + margin,
+        );
+
+        for pair in points.windows(2) {
+            let Some((a, b)) = clip_segment(pair[0], pair[1], bounds) else {
+                continue;
+            };
+            let (x1, y1) = (a.0.round() as isize, a.1.round() as isize);
+            let (x2, y2) = (b.0.round() as isize, b.1.round() as isize);
+            match width {
+                0 => {}
+                1 => view.draw_line(x1, y1, x2, y2, color),
+                _ => view.draw_polyline_capped(x1, y1, x2, y2, width, color),
+            }
+        }
+    }
+
+    /// Fills the axis aligned rectangle between two corners in pixels, including both corners.
+    pub(crate) fn fill_rect<T: PixelTarget>(
+        &self,
+        view: &mut T,
+        a: (f64, f64),
+        b: (f64, f64),
+        color: RGBA,
+    ) {
+        let Some((x1, y1, x2, y2)) = pixel_rect(view, a, b) else {
+            return;
+        };
+        view.fill_rect(
+            x1,
+            y1,
+            (x2 - x1) as usize + 1,
+            (y2 - y1) as usize + 1,
+            color,
+        );
+    }
+
+    /// Draws the outline of the axis aligned rectangle between two corners in pixels.
+    pub(crate) fn outline_rect<T: PixelTarget>(
+        &self,
+        view: &mut T,
+        a: (f64, f64),
+        b: (f64, f64),
+        color: RGBA,
+    ) {
+        let Some((x1, y1, x2, y2)) = pixel_rect(view, a, b) else {
+            return;
+        };
+        view.draw_hline(x1, x2, y1, color);
+        if y2 == y1 {
+            return;
+        }
+        view.draw_hline(x1, x2, y2, color);
+        if y2 - y1 > 1 {
+            view.draw_vline(x1, y1 + 1, y2 - 1, color);
+            if x2 != x1 {
+                view.draw_vline(x2, y1 + 1, y2 - 1, color);
+            }
+        }
+    }
+}
+
+/// Strokes an anti-aliased line through points in pixels like [`Frame::stroke`].
+///
+/// Connected segments are drawn in short runs, keeping the area scanned per run small.
+pub(crate) fn stroke_aa<T: PixelTarget>(
+    view: &mut T,
+    points: &[(f64, f64)],
+    width: u32,
+    color: RGBA,
+) {
+    let margin = width as f64 + 2.0;
+    let bounds = (
+        -margin,
+        -margin,
+        view.width() as f64 + margin,
+        view.height() as f64 + margin,
+    );
+
+    let mut run: Vec<(isize, isize)> = Vec::new();
+    for pair in points.windows(2) {
+        let Some((a, b)) = clip_segment(pair[0], pair[1], bounds) else {
+            view.draw_stroke_aa(&run, width, color);
+            run.clear();
+            continue;
+        };
+        let a = (a.0.round() as isize, a.1.round() as isize);
+        let b = (b.0.round() as isize, b.1.round() as isize);
+
+        if run.last() != Some(&a) || run.len() > 32 {
+            view.draw_stroke_aa(&run, width, color);
+            run.clear();
+            run.push(a);
+        }
+        run.push(b);
+    }
+    view.draw_stroke_aa(&run, width, color);
+}
+
+/// Fills the area between the lower and upper curve of a band column by column,
+/// so every pixel is blended once even where the curves cross.
+fn fill_band(view: &mut CanvasView, frame: &Frame, points: &[(f64, f64, f64)], color: RGBA) {
+    let width = view.width() as f64;
+    // first column not filled by a previous segment
+    let mut next = 0.0f64;
+    for pair in points.windows(2) {
+        let finite = |p: &(f64, f64, f64)| p.0.is_finite() && p.1.is_finite() && p.2.is_finite();
+        if !finite(&pair[0]) || !finite(&pair[1]) {
+            continue;
+        }
+
+        let (x1, low1) = frame.project(pair[0].0, pair[0].1);
+        let (_, high1) = frame.project(pair[0].0, pair[0].2);
+        let (x2, low2) = frame.project(pair[1].0, pair[1].1);
+        let (_, high2) = frame.project(pair[1].0, pair[1].2);
+        if x2 <= x1 {
+            continue;
+        }
+
+        let start = x1.ceil().max(next);
+        let end = (x2.floor() + 1.0).min(width);
+        next = next.max(end);
+
+        let mut px = start;
+        while px < end {
+            let t = (px - x1) / (x2 - x1);
+            let low = low1 + (low2 - low1) * t;
+            let high = high1 + (high2 - high1) * t;
+            let (top, bottom) = (low.min(high).round(), low.max(high).round());
+            view.draw_vline(px as isize, top as isize, bottom as isize, color);
+            px += 1.0;
+        }
+    }
+}
+
+/// Draws the background, gridlines and axes of a chart around the data drawn by `data`.
+///
+/// Gridlines along the x axis are only drawn if `x_grid` is set.
+pub(crate) fn draw_chart<F>(
+    canvas: &mut Canvas,
+    rect: Rect,
+    x_axis: Axis,
+    y_axis: Axis,
+    style: &ChartStyle,
+    x_grid: bool,
+    data: F,
+) where
+    F: FnOnce(&mut CanvasView, &Frame),
+{
+    let Some((x, y, _, _)) = rect.clip(canvas.width(), canvas.height()) else {
+        return;
+    };
+
+    let frame = Frame {
+        x_axis,
+        y_axis,
+        offset: ((rect.x - x as isize) as f64, (rect.y - y as isize) as f64),
+        width: rect.width as f64,
+        height: rect.height as f64,
+    };
+    let mut view = canvas.sub_canvas_mut(rect);
+
+    if let Some(background) = style.background {
+        let (width, height) = (view.width(), view.height());
+        view.fill_rect(0, 0, width, height, background);
+    }
+
+    if let Some(grid) = style.grid_color {
+        let (left, top) = frame.project(x_axis.min, y_axis.max);
+        let (right, bottom) = frame.project(x_axis.max, y_axis.min);
+        if x_grid {
+            for tick in x_axis.ticks(style.ticks) {
+                let px = frame.project(tick, 0.0).0.round() as isize;
+                view.draw_vline(px, top as isize, bottom as isize, grid);
+            }
+        }
+        for tick in y_axis.ticks(style.ticks) {
+            let py = frame.project(0.0, tick).1.round() as isize;
+            view.draw_hline(left as isize, right as isize, py, grid);
+        }
+    }
+
+    data(&mut view, &frame);
+
+    let (left, top) = frame.project(x_axis.min, y_axis.max);
+    let (right, bottom) = frame.project(x_axis.max, y_axis.min);
+    let (left, top, right, bottom) = (left as isize, top as isize, right as isize, bottom as isize);
+    view.draw_vline(left, top, bottom, style.axis_color);
+    view.draw_hline(left + 1, right, bottom, style.axis_color);
+}
+
+/// Rounds two corners in pixels to `(x1, y1, x2, y2)` with `x1 <= x2` and `y1 <= y2`,
+/// limited to just outside of the view to keep huge values from overflowing.
+fn pixel_rect<T: PixelTarget>(
+    view: &T,
+    a: (f64, f64),
+    b: (f64, f64),
+) -> Option<(isize, isize, isize, isize)> {
+    let limit = |v: f64, max: usize| v.round().clamp(-1.0, max as f64 + 1.0) as isize;
+    let (x1, x2) = (
+        limit(a.0.min(b.0), view.width()),
+        limit(a.0.max(b.0), view.width()),
+    );
+    let (y1, y2) = (
+        limit(a.1.min(b.1), view.height()),
+        limit(a.1.max(b.1), view.height()),
+    );
+    match x1 <= x2 && y1 <= y2 {
+        true => Some((x1, y1, x2, y2)),
+        false => None,
+    }
+}
+
+/// Clips a line segment to the rectangle `(min_x, min_y, max_x, max_y)` (Liang–Barsky).
+///
+/// Returns `None` if the segment lays outside of the rectangle or has a non-finite end.
+pub(crate) fn clip_segment(
+    a: (f64, f64),
+    b: (f64, f64),
+    (min_x, min_y, max_x, max_y): (f64, f64, f64, f64),
+) -> Option<((f64, f64), (f64, f64))> {
+    if ![a.0, a.1, b.0, b.1].iter().all(|v| v.is_finite()) {
+        return None;
+    }
+
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    for (p, q) in [
+        (-dx, a.0 - min_x),
+        (dx, max_x - a.0),
+        (-dy, a.1 - min_y),
+        (dy, max_y - a.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+            continue;
+        }
+
+        let t = q / p;
+        match p < 0.0 {
+            true => t0 = t0.max(t),
+            false => t1 = t1.min(t),
+        }
+    }
+
+    match t0 <= t1 {
+        true => Some((
+            (a.0 + t0 * dx, a.1 + t0 * dy),
+            (a.0 + t1 * dx, a.1 + t1 * dy),
+        )),
+        false => None,
+    }
+}
+
+/// Shape drawn at every point of a [`ScatterPlot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Marker {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+    Triangle,
+    /// A diagonal cross `x`.
+    Cross,
+    /// An upright cross `+`.
+    Plus,
+}
+
+impl Marker {
+    /// Draws the marker centered on a position with a size of `size` pixels.
+    pub fn draw<T: PixelTarget>(self, canvas: &mut T, x: isize, y: isize, size: u32, color: RGBA) {
+        let r = (size / 2) as isize;
+        match self {
+            Marker::Circle => canvas.draw_circle_solid(x, y, r as u32, color),
+            Marker::Square => {
+                let side = 2 * r as usize + 1;
+                canvas.fill_rect(x - r, y - r, side, side, color);
+            }
+            Marker::Diamond => {
+                let vertices = vec![(x, y - r), (x + r, y), (x, y + r), (x - r, y)];
+                canvas.draw_polygon_solid(&vertices, true, color);
+            }
+            Marker::Triangle => {
+                let vertices = vec![(x, y - r), (x + r, y + r), (x - r, y + r)];
+                canvas.draw_polygon_solid(&vertices, true, color);
+            }
+            Marker::Cross => {
+                canvas.draw_line(x - r, y - r, x + r, y + r, color);
+                canvas.draw_line(x - r, y + r, x + r, y - r, color);
+            }
+            Marker::Plus => {
+                canvas.draw_hline(x - r, x + r, y, color);
+                canvas.draw_vline(x, y - r, y + r, color);
+            }
+        }
+    }
+}
+
+/// A chart drawing a marker at every data point.
+///
+/// Markers are clipped at the rectangle of the chart, points with a non-finite coordinate are left out.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, Marker, ScatterPlot, SERIES_COLORS};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let points = vec![(1.0, 1.0), (2.0, 4.0), (3.0, 9.0)];
+/// let plot = ScatterPlot {
+///     rect: Rect::new(0, 0, 401, 201),
+///     x_axis: Axis::new(0.0, 4.0),
+///     y_axis: Axis::new(0.0, 10.0),
+///     points,
+///     colors: vec![SERIES_COLORS[1]],
+///     color: SERIES_COLORS[0],
+///     marker: Marker::Square,
+///     size: 7,
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&plot);
+///
+/// // the first point has its own color, the others use the color of the plot
+/// assert_eq!(Some(&SERIES_COLORS[1].to_rgb().0), canvas.get_at(103, 180));
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(200, 120));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScatterPlot {
+    pub rect: Rect,
+    pub x_axis: Axis,
+    pub y_axis: Axis,
+
+    /// Points as `(x, y)` in data coordinates.
+    pub points: Vec<(f64, f64)>,
+    /// Colors of the individual points, points without a color of their own use `color`.
+    pub colors: Vec<RGBA>,
+    pub color: RGBA,
+
+    pub marker: Marker,
+    /// Size of the markers in pixels.
+    pub size: u32,
+    pub style: ChartStyle,
+}
+
+impl Draw for ScatterPlot {
+    fn draw(&self, canvas: &mut Canvas) {
+        self.draw_with(canvas, self.rect, self.x_axis, self.y_axis);
+    }
+}
+
+impl Plot for ScatterPlot {
+    fn axes(&self) -> (Axis, Axis) {
+        (self.x_axis, self.y_axis)
+    }
+
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            true,
+            |view, frame| {
+                let margin = self.size as f64;
+                for (i, &(x, y)) in self.points.iter().enumerate() {
+                    let (px, py) = frame.project(x, y);
+                    let visible = px >= -margin
+                        && px <= view.width() as f64 + margin
+                        && py >= -margin
+                        && py <= view.height() as f64 + margin;
+                    if !visible {
+                        continue;
+                    }
+
+                    let color = self.colors.get(i).copied().unwrap_or(self.color);
+                    let (px, py) = (px.round() as isize, py.round() as isize);
+                    self.marker.draw(view, px, py, self.size, color);
+                }
+            },
+        );
+    }
+}
+
+impl Canvas {
+    /// Draws a matrix of values stored row by row as a grid of cells filling a rectangle, coloring each cell using a gradient.
+    ///
+    /// The values are looked up in the gradient as they are, so the stops of the gradient should span the range of the data.
+    /// Translucent colors are blended onto the canvas. Does nothing if there are less than `cols * rows` values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::gradient::Gradient;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let gradient = Gradient::new(&[
+    ///     (0.0, RGBA::new(0, 0, 255, 255)),
+    ///     (100.0, RGBA::new(255, 0, 0, 255)),
+    /// ]);
+    /// let temperatures = [0.0, 50.0, 100.0, 25.0, 75.0, 0.0];
+    /// canvas.draw_heatmap(Rect::new(0, 0, 300, 200), &temperatures, 3, 2, &gradient);
+    ///
+    /// assert_eq!(255, canvas.get_at(250, 50).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(250, 150).unwrap().b);
+    /// ```
+    pub fn draw_heatmap(
+        &mut self,
+        rect: Rect,
+        values: &[f32],
+        cols: usize,
+        rows: usize,
+        gradient: &Gradient,
+    ) {
+        self.draw_heatmap_with(rect, values, cols, rows, gradient, false);
+    }
+
+    /// Draws a matrix of values like [`Canvas::draw_heatmap`], bilinearly interpolating between the centers of the cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::gradient::Gradient;
+    ///
+    /// let mut canvas = Canvas::new(1080, 720);
+    ///
+    /// let gradient = Gradient::new(&[
+    ///     (0.0, RGBA::new(0, 0, 0, 255)),
+    ///     (1.0, RGBA::new(255, 255, 255, 255)),
+    /// ]);
+    /// canvas.draw_heatmap_smooth(Rect::new(0, 0, 200, 100), &[0.0, 1.0], 2, 1, &gradient);
+    ///
+    /// assert_eq!(0, canvas.get_at(10, 50).unwrap().r);
+    /// assert_eq!(128, canvas.get_at(100, 50).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(190, 50).unwrap().r);
+    ///
+    /// // too few values for the grid draw nothing
+    /// canvas.draw_heatmap_smooth(Rect::new(0, 0, 200, 100), &[1.0; 4], usize::MAX, 2, &gradient);
+    /// assert_eq!(0, canvas.get_at(10, 50).unwrap().r);
+    /// ```
+    pub fn draw_heatmap_smooth(
+        &mut self,
+        rect: Rect,
+        values: &[f32],
+        cols: usize,
+        rows: usize,
+        gradient: &Gradient,
+    ) {
+        self.draw_heatmap_with(rect, values, cols, rows, gradient, true);
+    }
+
+    fn draw_heatmap_with(
+        &mut self,
+        rect: Rect,
+        values: &[f32],
+        cols: usize,
+        rows: usize,
+        gradient: &Gradient,
+        smooth: bool,
+    ) {
+        if cols == 0 || rows == 0 || cols.checked_mul(rows).is_none_or(|n| values.len() < n) {
+            return;
+        }
+        let Some((x, y, w, h)) = rect.clip(self.width(), self.height()) else {
+            return;
+        };
+
+        let at = |col: usize, row: usize| values[row * cols + col];
+        // position of a pixel center in cells, measured from the top-left corner of the matrix
+        let cell_x =
+            |px: usize| ((px as isize - rect.x) as f32 + 0.5) / rect.width as f32 * cols as f32;
+        let cell_y =
+            |py: usize| ((py as isize - rect.y) as f32 + 0.5) / rect.height as f32 * rows as f32;
+
+        self.for_each_row_mut(y..y + h, |py, pixels| {
+            let v = cell_y(py);
+            for (i, pixel) in pixels[x..x + w].iter_mut().enumerate() {
+                let u = cell_x(x + i);
+                let value = match smooth {
+                    false => at((u as usize).min(cols - 1), (v as usize).min(rows - 1)),
+                    true => {
+                        // interpolate between the centers of the four closest cells
+                        let (u, v) = (
+                            (u - 0.5).clamp(0.0, (cols - 1) as f32),
+                            (v - 0.5).clamp(0.0, (rows - 1) as f32),
+                        );
+                        let (c0, r0) = (u as usize, v as usize);
+                        let (c1, r1) = ((c0 + 1).min(cols - 1), (r0 + 1).min(rows - 1));
+                        let (tu, tv) = (u - c0 as f32, v - r0 as f32);
+                        let top = at(c0, r0) + (at(c1, r0) - at(c0, r0)) * tu;
+                        let bottom = at(c0, r1) + (at(c1, r1) - at(c0, r1)) * tu;
+                        top + (bottom - top) * tv
+                    }
+                };
+                *pixel = pixel.add_rgba(gradient.sample(value));
+            }
+        });
+    }
+}
+
+/// A chart counting how many values fall into each of a number of equally sized bins.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, Histogram, SERIES_COLORS};
+/// use drawing_stuff::color::WHITE;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let histogram = Histogram {
+///     rect: Rect::new(40, 40, 1000, 640),
+///     values: vec![0.5, 1.5, 1.7, 2.2, 2.5, 2.9, 3.1],
+///     bins: 4,
+///     range: Some(Axis::new(0.0, 4.0)),
+///     color: SERIES_COLORS[0],
+///     outline: Some(WHITE),
+///     style: ChartStyle::default(),
+/// };
+/// assert_eq!(vec![1, 2, 3, 1], histogram.counts());
+///
+/// canvas.draw(&histogram);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub rect: Rect,
+
+    pub values: Vec<f64>,
+    pub bins: usize,
+    /// Range of values divided into bins, `None` fits the range to the values.
+    /// Values outside of the range are not counted.
+    pub range: Option<Axis>,
+
+    pub color: RGBA,
+    /// Color of the outline drawn around every bar, `None` draws no outlines.
+    pub outline: Option<RGBA>,
+    pub style: ChartStyle,
+}
+
+impl Histogram {
+    /// Returns the range of values divided into bins.
+    pub fn range(&self) -> Axis {
+        self.range
+            .unwrap_or_else(|| Axis::fit(self.values.iter().copied()))
+    }
+
+    /// Returns the number of values in every bin.
+    pub fn counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.bins];
+        let range = self.range();
+        let (min, max) = (range.min.min(range.max), range.max.max(range.min));
+        if self.bins == 0 || min == max {
+            return counts;
+        }
+
+        for &value in &self.values {
+            if !(min..=max).contains(&value) {
+                continue;
+            }
+            // the upper end of the range belongs to the last bin
+            let bin = ((value - min) / (max - min) * self.bins as f64) as usize;
+            counts[bin.min(self.bins - 1)] += 1;
+        }
+        counts
+    }
+}
+
+impl Draw for Histogram {
+    fn draw(&self, canvas: &mut Canvas) {
+        let (x_axis, y_axis) = self.axes();
+        self.draw_with(canvas, self.rect, x_axis, y_axis);
+    }
+}
+
+impl Plot for Histogram {
+    /// Returns the range of values as x axis and an y axis from zero to the highest count.
+    fn axes(&self) -> (Axis, Axis) {
+        let highest = self.counts().into_iter().max().unwrap_or(0).max(1);
+        let range = self.range();
+        let (min, max) = (range.min.min(range.max), range.max.max(range.min));
+        (Axis::new(min, max), Axis::new(0.0, highest as f64))
+    }
+
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        let counts = self.counts();
+        let range = self.range();
+        let (min, max) = (range.min.min(range.max), range.max.max(range.min));
+
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            false,
+            |view, frame| {
+                let bin_width = (max - min) / self.bins.max(1) as f64;
+                for (i, &count) in counts.iter().enumerate() {
+                    if count == 0 {
+                        continue;
+                    }
+
+                    let left = min + i as f64 * bin_width;
+                    let a = frame.project(left, 0.0);
+                    let b = frame.project(left + bin_width, count as f64);
+                    frame.fill_rect(view, a, b, self.color);
+                    if let Some(outline) = self.outline {
+                        frame.outline_rect(view, a, b, outline);
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// Summary statistics of samples shown by a [`BoxPlot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxStats {
+    /// Lowest sample within `1.5` interquartile ranges below the first quartile.
+    pub lower_whisker: f64,
+    pub first_quartile: f64,
+    pub median: f64,
+    pub third_quartile: f64,
+    /// Highest sample within `1.5` interquartile ranges above the third quartile.
+    pub upper_whisker: f64,
+    /// Samples beyond the whiskers in ascending order.
+    pub outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Computes the statistics of the finite samples, `None` if there are none.
+    ///
+    /// Quartiles are interpolated linearly between the closest samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::charts::BoxStats;
+    ///
+    /// let stats = BoxStats::from_samples(&[1.0, 2.0, 3.0, 4.0, 5.0, 20.0]).unwrap();
+    ///
+    /// assert_eq!(3.5, stats.median);
+    /// assert_eq!((2.25, 4.75), (stats.first_quartile, stats.third_quartile));
+    /// assert_eq!((1.0, 5.0), (stats.lower_whisker, stats.upper_whisker));
+    /// assert_eq!(vec![20.0], stats.outliers);
+    /// ```
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        let first_quartile = quantile(&sorted, 0.25);
+        let third_quartile = quantile(&sorted, 0.75);
+        let reach = 1.5 * (third_quartile - first_quartile);
+        let (low, high) = (first_quartile - reach, third_quartile + reach);
+        let inside = |v: &&f64| (low..=high).contains(*v);
+
+        Some(Self {
+            lower_whisker: *sorted.iter().find(inside).unwrap_or(&first_quartile),
+            first_quartile,
+            median: quantile(&sorted, 0.5),
+            third_quartile,
+            upper_whisker: *sorted.iter().rev().find(inside).unwrap_or(&third_quartile),
+            outliers: sorted
+                .iter()
+                .copied()
+                .filter(|v| !(low..=high).contains(v))
+                .collect(),
+        })
+    }
+}
+
+/// A chart of box-and-whisker plots summarizing the distribution of samples, one box per category.
+///
+/// Every box spans the first to the third quartile with a line at the median, whiskers reaching the furthest samples
+/// within `1.5` interquartile ranges and markers at the outliers beyond.
+/// A violin shows the density of the samples estimated with a gaussian kernel behind a narrower box.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, BoxPlot, ChartStyle, SERIES_COLORS};
+/// use drawing_stuff::color::{RGBA, WHITE};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let plot = BoxPlot {
+///     rect: Rect::new(0, 0, 400, 201),
+///     y_axis: Axis::new(0.0, 10.0),
+///     samples: vec![
+///         vec![2.0, 3.0, 4.0, 5.0, 6.0],
+///         vec![4.0, 5.0, 5.5, 6.0, 7.0, 9.5],
+///     ],
+///     color: SERIES_COLORS[0],
+///     outline: WHITE,
+///     violin: Some(RGBA { a: 80, ..SERIES_COLORS[1] }),
+///     gap: 0.4,
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&plot);
+///
+/// // the median of the first box at 4.0 and its box from 3.0 to 5.0
+/// assert_eq!(255, canvas.get_at(100, 120).unwrap().r);
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(95, 110));
+/// // the outlier of the second box
+/// assert_eq!(255, canvas.get_at(300, 10).unwrap().r);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxPlot {
+    pub rect: Rect,
+    pub y_axis: Axis,
+
+    /// Samples of every category, non-finite samples are ignored.
+    pub samples: Vec<Vec<f64>>,
+    /// Color filling the boxes.
+    pub color: RGBA,
+    /// Color of the outlines, medians, whiskers and outliers.
+    pub outline: RGBA,
+    /// Color of the violins behind the boxes, `None` draws no violins.
+    pub violin: Option<RGBA>,
+    /// Fraction of the width of every category left empty between the boxes.
+    pub gap: f32,
+    pub style: ChartStyle,
+}
+
+impl BoxPlot {
+    /// Returns the statistics of the samples of every category.
+    pub fn stats(&self) -> Vec<Option<BoxStats>> {
+        self.samples
+            .iter()
+            .map(|samples| BoxStats::from_samples(samples))
+            .collect()
+    }
+}
+
+impl Draw for BoxPlot {
+    fn draw(&self, canvas: &mut Canvas) {
+        let (x_axis, y_axis) = self.axes();
+        self.draw_with(canvas, self.rect, x_axis, y_axis);
+    }
+}
+
+impl Plot for BoxPlot {
+    /// Returns an x axis covering the categories, each of them `1.0` wide, and the y axis of the chart.
+    fn axes(&self) -> (Axis, Axis) {
+        let categories = self.samples.len().max(1);
+        (Axis::new(0.0, categories as f64), self.y_axis)
+    }
+
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            false,
+            |view, frame| {
+                let gap = self.gap.clamp(0.0, 1.0) as f64;
+                let half = (1.0 - gap) / 2.0;
+
+                for (category, samples) in self.samples.iter().enumerate() {
+                    let Some(stats) = BoxStats::from_samples(samples) else {
+                        continue;
+                    };
+                    let center = category as f64 + 0.5;
+
+                    // a narrow box keeps the violin behind it visible
+                    let half = match self.violin {
+                        Some(violin) => {
+                            fill_violin(view, frame, samples, center, half, violin);
+                            half / 4.0
+                        }
+                        None => half,
+                    };
+
+                    let a = frame.project(center - half, stats.first_quartile);
+                    let b = frame.project(center + half, stats.third_quartile);
+                    frame.fill_rect(view, a, b, self.color);
+                    frame.outline_rect(view, a, b, self.outline);
+
+                    let line = |view: &mut CanvasView, x1: f64, y1: f64, x2: f64, y2: f64| {
+                        let points = [frame.project(x1, y1), frame.project(x2, y2)];
+                        frame.stroke(view, &points, 1, self.outline);
+                    };
+                    let (left, right) = (center - half, center + half);
+                    let (cap_left, cap_right) = (center - half / 2.0, center + half / 2.0);
+                    line(view, left, stats.median, right, stats.median);
+                    line(
+                        view,
+                        center,
+                        stats.third_quartile,
+                        center,
+                        stats.upper_whisker,
+                    );
+                    line(
+                        view,
+                        center,
+                        stats.first_quartile,
+                        center,
+                        stats.lower_whisker,
+                    );
+                    line(
+                        view,
+                        cap_left,
+                        stats.upper_whisker,
+                        cap_right,
+                        stats.upper_whisker,
+                    );
+                    line(
+                        view,
+                        cap_left,
+                        stats.lower_whisker,
+                        cap_right,
+                        stats.lower_whisker,
+                    );
+
+                    for &outlier in &stats.outliers {
+                        let (px, py) = frame.project(center, outlier);
+                        if py < -5.0 || py > view.height() as f64 + 5.0 {
+                            continue;
+                        }
+                        let (px, py) = (px.round() as isize, py.round() as isize);
+                        Marker::Circle.draw(view, px, py, 5, self.outline);
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// Returns the quantile `q` of sorted samples, interpolating linearly between the closest samples.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    sorted[below] + (sorted[above] - sorted[below]) * (position - below as f64)
+}
+
+/// Fills a violin showing the density of samples, estimated by a gaussian kernel using Silverman's rule of thumb
+/// for its bandwidth, between the lowest and the highest sample.
+///
+/// The violin is centered on `center` and `half` wide at the highest density in data coordinates.
+fn fill_violin(
+    view: &mut CanvasView,
+    frame: &Frame,
+    samples: &[f64],
+    center: f64,
+    half: f64,
+    color: RGBA,
+) {
+    let samples: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let deviation = (samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let mut sorted = samples.clone();
+    sorted.sort_by(f64::total_cmp);
+    let iqr = quantile(&sorted, 0.75) - quantile(&sorted, 0.25);
+    let spread = match iqr > 0.0 {
+        true => deviation.min(iqr / 1.34),
+        false => deviation,
+    };
+    let bandwidth = 0.9 * spread * n.powf(-0.2);
+    if bandwidth <= 0.0 {
+        return;
+    }
+
+    const STEPS: usize = 64;
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    let density = |y: f64| {
+        samples
+            .iter()
+            .map(|v| (-0.5 * ((y - v) / bandwidth).powi(2)).exp())
+            .sum::<f64>()
+    };
+    let profile: Vec<(f64, f64)> = (0..=STEPS)
+        .map(|i| {
+            let y = min + (max - min) * i as f64 / STEPS as f64;
+            (y, density(y))
+        })
+        .collect();
+    let highest = profile.iter().map(|p| p.1).fold(0.0, f64::max);
+
+    // the half width of the violin in pixels at every step
+    let (cx, _) = frame.project(center, min);
+    let rows: Vec<(f64, f64)> = profile
+        .iter()
+        .map(|&(y, d)| {
+            let (px, py) = frame.project(center + half * d / highest, y);
+            (py, px - cx)
+        })
+        .collect();
+
+    // rows are assigned to the step starting below them, the last one also takes its end
+    let height = view.height() as f64;
+    for (i, pair) in rows.windows(2).enumerate() {
+        let ((y1, w1), (y2, w2)) = (pair[0], pair[1]);
+        let (start, end) = (y1.min(y2), y1.max(y2));
+        let end = match i == STEPS - 1 {
+            true => end.floor() + 1.0,
+            false => end.ceil(),
+        };
+
+        let mut py = start.ceil().max(0.0);
+        while py < end.min(height) {
+            let t = (py - y1) / (y2 - y1);
+            let w = match t.is_finite() {
+                true => w1 + (w2 - w1) * t,
+                false => w1.max(w2),
+            };
+            let (left, right) = ((cx - w).round(), (cx + w).round());
+            view.draw_hline(left as isize, right as isize, py as isize, color);
+            py += 1.0;
+        }
+    }
+}
+
+/// A chart of the graph of a function `y = f(x)`.
+///
+/// The function is sampled adaptively, so steep or curvy parts of the graph get more samples than flat ones.
+/// Non-finite results, e.g. at poles, leave a gap.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, Figure, FunctionPlot, SharedAxis, SERIES_COLORS};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let plot = FunctionPlot {
+///     rect: Rect::new(0, 0, 1001, 501),
+///     function: |x: f64| x * x,
+///     x_axis: Axis::new(-2.0, 2.0),
+///     y_axis: None,
+///     width: 2,
+///     color: SERIES_COLORS[0],
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&plot);
+///
+/// // the y axis is fitted to the graph, which touches its bottom at zero
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(500, 499));
+///
+/// // next to the graph of a cubic function, both with a shared y axis
+/// let cubic = FunctionPlot {
+///     rect: Rect::new(0, 0, 0, 0),
+///     function: |x: f64| x * x * x,
+///     x_axis: Axis::new(-2.0, 2.0),
+///     y_axis: None,
+///     width: 2,
+///     color: SERIES_COLORS[1],
+///     style: ChartStyle::default(),
+/// };
+/// let mut figure = Figure::new(Rect::new(0, 0, 1001, 501), 1, 2).share_y(SharedAxis::Row);
+/// figure.set(0, 0, plot);
+/// figure.set(0, 1, cubic);
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+/// canvas.draw(&figure);
+///
+/// // the shared y axis ranges from -8 to 8, so the parabola touches its middle
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(250, 250));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FunctionPlot<F> {
+    pub rect: Rect,
+    pub function: F,
+
+    /// Range of `x` the function is plotted over.
+    pub x_axis: Axis,
+    /// Range of `y` shown, `None` fits it to the graph.
+    pub y_axis: Option<Axis>,
+
+    pub width: u32,
+    pub color: RGBA,
+    pub style: ChartStyle,
+}
+
+impl<F> Draw for FunctionPlot<F>
+where
+    F: Fn(f64) -> f64,
+{
+    fn draw(&self, canvas: &mut Canvas) {
+        let (x_axis, y_axis) = self.axes();
+        self.draw_with(canvas, self.rect, x_axis, y_axis);
+    }
+}
+
+impl<F> Plot for FunctionPlot<F>
+where
+    F: Fn(f64) -> f64,
+{
+    /// Returns the x axis of the plot and its y axis, fitted to the graph if not set.
+    fn axes(&self) -> (Axis, Axis) {
+        let (start, end) = (self.x_axis.min, self.x_axis.max);
+        let y_axis = self.y_axis.unwrap_or_else(|| {
+            let samples = self.rect.width.max(1000);
+            Axis::fit((0..samples).map(|i| {
+                let t = i as f64 / (samples - 1) as f64;
+                (self.function)(start + (end - start) * t)
+            }))
+        });
+        (self.x_axis, y_axis)
+    }
+
+    /// Draws the graph over the range of the specified x axis.
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        let (start, end) = (x_axis.min, x_axis.max);
+        let segments = rect.width.div_ceil(4).max(1);
+
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            true,
+            |view, frame| {
+                let curve = |x: f64| frame.project(x, (self.function)(x));
+                let points = sample_adaptive(curve, start, end, segments, 0.5);
+                frame.stroke(view, &points, self.width, self.color);
+            },
+        );
+    }
+}
+
+/// Samples a curve given in pixels over `start..=end`, starting with `segments` equal segments.
+///
+/// Segments bending away from a straight line by more than `tolerance` pixels or crossing into non-finite values are split in half.
+pub(crate) fn sample_adaptive<F>(
+    curve: F,
+    start: f64,
+    end: f64,
+    segments: usize,
+    tolerance: f64,
+) -> Vec<(f64, f64)>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    let segments = segments.max(1);
+    let t = |i: usize| start + (end - start) * i as f64 / segments as f64;
+
+    let mut points = vec![curve(start)];
+    let mut previous = points[0];
+    for i in 0..segments {
+        let next = curve(t(i + 1));
+        subdivide(
+            &curve,
+            (t(i), previous),
+            (t(i + 1), next),
+            tolerance,
+            12,
+            &mut points,
+        );
+        previous = next;
+    }
+    points
+}
+
+/// Pushes the samples between two samples of a curve, excluding the first one.
+fn subdivide<F>(
+    curve: &F,
+    (t0, p0): (f64, (f64, f64)),
+    (t1, p1): (f64, (f64, f64)),
+    tolerance: f64,
+    depth: u32,
+    points: &mut Vec<(f64, f64)>,
+) where
+    F: Fn(f64) -> (f64, f64),
+{
+    let tm = (t0 + t1) / 2.0;
+    let pm = curve(tm);
+
+    let finite = |p: (f64, f64)| p.0.is_finite() && p.1.is_finite();
+    let split = match (finite(p0), finite(pm), finite(p1)) {
+        (true, true, true) => chord_distance(pm, p0, p1) > tolerance,
+        (false, false, false) => false,
+        _ => true,
+    };
+
+    if depth > 0 && split {
+        subdivide(curve, (t0, p0), (tm, pm), tolerance, depth - 1, points);
+        subdivide(curve, (tm, pm), (t1, p1), tolerance, depth - 1, points);
+    } else {
+        points.push(pm);
+        points.push(p1);
+    }
+}
+
+/// Returns the distance of a point to the line segment between `a` and `b`.
+fn chord_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = match length {
+        0.0 => 0.0,
+        _ => (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length).clamp(0.0, 1.0),
+    };
+    (p.0 - a.0 - t * dx).hypot(p.1 - a.1 - t * dy)
+}
+
+/// How the curve of a [`ParametricPlot`] is sampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sampling {
+    /// A fixed number of evenly spaced segments.
+    Count(usize),
+    /// Segments are split until they are no further than the tolerance in pixels from the curve.
+    Adaptive(f64),
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Self::Adaptive(0.5)
+    }
+}
+
+/// A chart of a parametric curve `(x(t), y(t))`, e.g. a Lissajous figure, an orbit or a phase plot.
+///
+/// Non-finite points of the curve leave a gap.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, ParametricPlot, Sampling, SERIES_COLORS};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let lissajous = ParametricPlot {
+///     rect: Rect::new(0, 0, 501, 501),
+///     curve: |t: f64| ((3.0 * t).sin(), (2.0 * t).sin()),
+///     t_range: (0.0, std::f64::consts::TAU),
+///     x_axis: Some(Axis::new(-1.0, 1.0)),
+///     y_axis: Some(Axis::new(-1.0, 1.0)),
+///     sampling: Sampling::default(),
+///     width: 3,
+///     color: SERIES_COLORS[0],
+///     antialiased: true,
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&lissajous);
+///
+/// // the curve passes through the center
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(250, 250));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParametricPlot<F> {
+    pub rect: Rect,
+    pub curve: F,
+
+    /// Range of `t` the curve is plotted over.
+    pub t_range: (f64, f64),
+    /// Range of `x` shown, `None` fits it to the curve.
+    pub x_axis: Option<Axis>,
+    /// Range of `y` shown, `None` fits it to the curve.
+    pub y_axis: Option<Axis>,
+
+    pub sampling: Sampling,
+    pub width: u32,
+    pub color: RGBA,
+    pub antialiased: bool,
+    pub style: ChartStyle,
+}
+
+impl<F> Draw for ParametricPlot<F>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    fn draw(&self, canvas: &mut Canvas) {
+        let (x_axis, y_axis) = self.axes();
+        self.draw_with(canvas, self.rect, x_axis, y_axis);
+    }
+}
+
+impl<F> Plot for ParametricPlot<F>
+where
+    F: Fn(f64) -> (f64, f64),
+{
+    /// Returns the axes of the plot, each fitted to the curve if not set.
+    fn axes(&self) -> (Axis, Axis) {
+        let (start, end) = self.t_range;
+        let fitted: Vec<_> = match (self.x_axis, self.y_axis) {
+            (Some(_), Some(_)) => Vec::new(),
+            _ => (0..=1000)
+                .map(|i| (self.curve)(start + (end - start) * i as f64 / 1000.0))
+                .collect(),
+        };
+        let x_axis = self
+            .x_axis
+            .unwrap_or_else(|| Axis::fit(fitted.iter().map(|p| p.0)));
+        let y_axis = self
+            .y_axis
+            .unwrap_or_else(|| Axis::fit(fitted.iter().map(|p| p.1)));
+        (x_axis, y_axis)
+    }
+
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, x_axis: Axis, y_axis: Axis) {
+        let (start, end) = self.t_range;
+        let t = |i: usize, count: usize| start + (end - start) * i as f64 / count as f64;
+
+        draw_chart(
+            canvas,
+            rect,
+            x_axis,
+            y_axis,
+            &self.style,
+            true,
+            |view, frame| {
+                let curve = |t: f64| {
+                    let (x, y) = (self.curve)(t);
+                    frame.project(x, y)
+                };
+                let points: Vec<_> = match self.sampling {
+                    Sampling::Count(count) => {
+                        let count = count.max(1);
+                        (0..=count).map(|i| curve(t(i, count))).collect()
+                    }
+                    Sampling::Adaptive(tolerance) => {
+                        let segments = (rect.width + rect.height).div_ceil(4);
+                        sample_adaptive(curve, start, end, segments, tolerance.max(0.01))
+                    }
+                };
+
+                match self.antialiased {
+                    true => stroke_aa(view, &points, self.width, self.color),
+                    false => frame.stroke(view, &points, self.width, self.color),
+                }
+            },
+        );
+    }
+}
+
+/// Which panels of a [`Figure`] use the same range for one of their axes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SharedAxis {
+    /// Every panel uses its own axis.
+    #[default]
+    Independent,
+    /// Panels in the same row share the axis.
+    Row,
+    /// Panels in the same column share the axis.
+    Column,
+    /// All panels share the axis.
+    All,
+}
+
+/// A grid of charts, each drawn into its own panel of a rectangle in one render call.
+///
+/// The rectangles of the charts themselves are ignored, every [`Plot`] is drawn into the rectangle of its panel.
+/// Shared axes cover the axes of all panels sharing them, so e.g. stacked charts can be compared at a glance.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{
+///     Axis, ChartStyle, Figure, LineChart, LineSeries, SharedAxis, Step, SERIES_COLORS,
+/// };
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let chart = |points: Vec<(f64, f64)>| LineChart {
+///     rect: Rect::new(0, 0, 0, 0),
+///     x_axis: Axis::fit(points.iter().map(|p| p.0)),
+///     y_axis: Axis::new(0.0, 1.0),
+///     series: vec![LineSeries {
+///         points,
+///         step: Step::Linear,
+///         fill: None,
+///         width: 1,
+///         color: SERIES_COLORS[0],
+///     }],
+///     bands: Vec::new(),
+///     error_bars: Vec::new(),
+///     style: ChartStyle::default(),
+/// };
+///
+/// // two charts stacked on top of each other with a common x axis
+/// let mut figure = Figure::new(Rect::new(0, 0, 1000, 610), 2, 1)
+///     .spacing(10)
+///     .share_x(SharedAxis::Column);
+/// figure.set(0, 0, chart(vec![(0.0, 0.5), (50.0, 0.5)]));
+/// figure.set(1, 0, chart(vec![(0.0, 0.5), (100.0, 0.5)]));
+///
+/// assert_eq!(Rect::new(0, 310, 1000, 300), figure.panel_rect(1, 0));
+///
+/// canvas.draw(&figure);
+///
+/// // the upper series only covers half of the shared axis
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(400, 150));
+/// assert_eq!(0, canvas.get_at(600, 150).unwrap().r);
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(600, 460));
+/// ```
+pub struct Figure<'a> {
+    rect: Rect,
+    rows: usize,
+    columns: usize,
+    spacing: usize,
+    share_x: SharedAxis,
+    share_y: SharedAxis,
+
+    panels: Vec<Option<Box<dyn Plot + 'a>>>,
+}
+
+impl<'a> Figure<'a> {
+    /// Creates a new figure dividing a rectangle into a grid of empty panels.
+    pub fn new(rect: Rect, rows: usize, columns: usize) -> Self {
+        Self {
+            rect,
+            rows,
+            columns,
+            spacing: 0,
+            share_x: SharedAxis::Independent,
+            share_y: SharedAxis::Independent,
+            panels: (0..rows * columns).map(|_| None).collect(),
+        }
+    }
+
+    /// Sets the space between neighboring panels in pixels.
+    pub fn spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets which panels share their x axis.
+    pub fn share_x(mut self, share: SharedAxis) -> Self {
+        self.share_x = share;
+        self
+    }
+
+    /// Sets which panels share their y axis.
+    pub fn share_y(mut self, share: SharedAxis) -> Self {
+        self.share_y = share;
+        self
+    }
+
+    /// Puts a chart into a panel, replacing the previous one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the panel is outside of the grid.
+    pub fn set<T>(&mut self, row: usize, column: usize, plot: T)
+    where
+        T: Plot + 'a,
+    {
+        assert!(
+            row < self.rows && column < self.columns,
+            "panel outside of the grid"
+        );
+        self.panels[row * self.columns + column] = Some(Box::new(plot));
+    }
+
+    /// Returns the rectangle of a panel.
+    ///
+    /// The space left after the spacing is divided as evenly as possible between the panels.
+    pub fn panel_rect(&self, row: usize, column: usize) -> Rect {
+        let split = |start: isize, length: usize, count: usize, index: usize| {
+            let count = count.max(1);
+            let available = length.saturating_sub(self.spacing.saturating_mul(count - 1));
+            let from = index * available / count;
+            let to = (index + 1) * available / count;
+            (start + (from + index * self.spacing) as isize, to - from)
+        };
+        let (x, width) = split(self.rect.x, self.rect.width, self.columns, column);
+        let (y, height) = split(self.rect.y, self.rect.height, self.rows, row);
+        Rect::new(x, y, width, height)
+    }
+
+    /// Draws all charts into their panels.
+    pub fn render(&self, canvas: &mut Canvas) {
+        let axes: Vec<Option<(Axis, Axis)>> = self
+            .panels
+            .iter()
+            .map(|panel| panel.as_ref().map(|plot| plot.axes()))
+            .collect();
+
+        for (i, panel) in self.panels.iter().enumerate() {
+            let Some(plot) = panel else {
+                continue;
+            };
+            let (row, column) = (i / self.columns, i % self.columns);
+            let shared = |share: SharedAxis, pick: fn(&(Axis, Axis)) -> Axis| {
+                let mut group = axes.iter().enumerate().filter_map(|(j, axes)| {
+                    let in_group = match share {
+                        SharedAxis::Independent => j == i,
+                        SharedAxis::Row => j / self.columns == row,
+                        SharedAxis::Column => j % self.columns == column,
+                        SharedAxis::All => true,
+                    };
+                    axes.as_ref().filter(|_| in_group).map(pick)
+                });
+                let first = group.next().expect("the panel itself is part of its group");
+                group.fold(first, |axis, other| {
+                    axis.including(other.min).including(other.max)
+                })
+            };
+
+            let x_axis = shared(self.share_x, |axes| axes.0);
+            let y_axis = shared(self.share_y, |axes| axes.1);
+            plot.draw_with(canvas, self.panel_rect(row, column), x_axis, y_axis);
+        }
+    }
+}
+
+impl Draw for Figure<'_> {
+    fn draw(&self, canvas: &mut Canvas) {
+        self.render(canvas);
+    }
+}
@@ -7,7 +7,7 @@ pub struct RGBA {
 }
 
 impl RGBA {
-    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
         Self { r, g, b, a }
     }
 
@@ -21,9 +21,87 @@ impl RGBA {
             self.a,
         )
     }
+
+    /// Unpacks a color from a 32-bit value in the specified byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::{PixelOrder, RGBA};
+    ///
+    /// let color = RGBA::from_u32(0xFF804020, PixelOrder::RGBA);
+    ///
+    /// assert_eq!(RGBA::new(0xFF, 0x80, 0x40, 0x20), color);
+    /// assert_eq!(0x20FF8040, color.to_u32(PixelOrder::ARGB));
+    /// ```
+    pub fn from_u32(value: u32, order: PixelOrder) -> Self {
+        order.unpack(value)
+    }
+
+    /// Packs the color into a 32-bit value in the specified byte order.
+    pub fn to_u32(self, order: PixelOrder) -> u32 {
+        order.pack(self)
+    }
+
+    /// Composites the color over a possibly translucent destination color (Porter-Duff source-over).
+    ///
+    /// Unlike [`RGB::add_rgba`], which assumes an opaque destination, the alpha of the result accounts for both colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::{RGBA, TRANSPARANT};
+    ///
+    /// let red = RGBA::new(255, 0, 0, 128);
+    /// let blue = RGBA::new(0, 0, 255, 128);
+    ///
+    /// assert_eq!(red, red.over(TRANSPARANT));
+    /// assert_eq!(RGBA::new(170, 0, 85, 192), red.over(blue));
+    /// ```
+    pub fn over(self, dst: RGBA) -> RGBA {
+        let src_alpha = self.a as u32;
+        let dst_weight = dst.a as u32 * (255 - src_alpha);
+        let alpha = src_alpha * 255 + dst_weight;
+        if alpha == 0 {
+            return RGBA::new(0, 0, 0, 0);
+        }
+
+        let mix = |src: u8, dst: u8| {
+            ((src as u32 * src_alpha * 255 + dst as u32 * dst_weight + alpha / 2) / alpha) as u8
+        };
+        RGBA {
+            r: mix(self.r, dst.r),
+            g: mix(self.g, dst.g),
+            b: mix(self.b, dst.b),
+            a: ((alpha + 127) / 255) as u8,
+        }
+    }
+
+    /// Looks up an opaque color by its CSS name, ignoring case.
+    ///
+    /// Returns `None` if there is no color with the name. All names are listed in [`NAMED_COLORS`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::RGBA;
+    ///
+    /// assert_eq!(Some(RGBA::new(102, 51, 153, 255)), RGBA::named("rebeccapurple"));
+    /// assert_eq!(Some(RGBA::new(100, 149, 237, 255)), RGBA::named("CornflowerBlue"));
+    /// assert_eq!(None, RGBA::named("notacolor"));
+    /// ```
+    pub fn named(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let index = NAMED_COLORS
+            .binary_search_by(|(n, _)| n.cmp(&name.as_str()))
+            .ok()?;
+        let RGB { r, g, b } = NAMED_COLORS[index].1;
+        Some(Self { r, g, b, a: 255 })
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
 pub struct RGB {
     pub r: u8,
     pub g: u8,
@@ -31,11 +109,39 @@ pub struct RGB {
 }
 
 impl RGB {
+    /// Unpacks a color from a 32-bit value in the format `0x00RRGGBB`, ignoring the most significant byte.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::{PixelOrder, RGB};
+    ///
+    /// let color = RGB::from_u32(0x00FF8040);
+    ///
+    /// assert_eq!(RGB { r: 0xFF, g: 0x80, b: 0x40 }, color);
+    /// assert_eq!(0x4080FFFF, color.to_u32(PixelOrder::BGRA));
+    /// ```
+    pub fn from_u32(value: u32) -> Self {
+        let [_, r, g, b] = value.to_be_bytes();
+        Self { r, g, b }
+    }
+
+    /// Packs the color into a 32-bit value in the specified byte order with an opaque alpha channel.
+    pub fn to_u32(self, order: PixelOrder) -> u32 {
+        order.pack(RGBA::new(self.r, self.g, self.b, 255))
+    }
+
     /// Adds an RGBA value onto a RGB value returning the result.
     /// This simply performs a linear interpolation between the two.
+    ///
+    /// Since 0.2.2 the interpolation is computed exactly with integers and rounded down.
+    /// Before, floating point errors made about one in a thousand results one lower.
     pub fn add_rgba(self, other: RGBA) -> Self {
-        let (other, alpha) = other.to_rgb();
-        self.lerp(&other, alpha as f64 / 255.0)
+        match other.a {
+            0 => self,
+            255 => other.to_rgb().0,
+            _ => Blender::new(other).blend(self),
+        }
     }
 
     /// Performs a linear interpolation between two RGB values returning the result.
@@ -46,6 +152,647 @@ impl RGB {
             b: ((1.0 - a) * self.b as f64 + a * other.b as f64) as u8,
         }
     }
+
+    /// Performs a linear interpolation between two RGB values in the [`OKLab`] color space returning the result.
+    ///
+    /// Compared to [`RGB::lerp`] the midpoints keep their brightness and saturation instead of turning dark and muddy.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// let red = RGB { r: 255, g: 0, b: 0 };
+    /// let green = RGB { r: 0, g: 255, b: 0 };
+    ///
+    /// assert_eq!(RGB { r: 127, g: 127, b: 0 }, red.lerp(&green, 0.5));
+    /// assert_eq!(RGB { r: 208, g: 168, b: 0 }, red.lerp_oklab(&green, 0.5));
+    /// ```
+    pub fn lerp_oklab(&self, other: &Self, a: f64) -> Self {
+        OKLab::from(*self)
+            .lerp(OKLab::from(*other), a as f32)
+            .into()
+    }
+
+    /// Composites a premultiplied color with the specified alpha over the color.
+    pub(crate) fn add_premultiplied(self, premul: RGB, alpha: u8) -> RGB {
+        let over = |premul: u8, base: u8| {
+            (premul as u32 + (base as u32 * (255 - alpha as u32) + 127) / 255).min(255) as u8
+        };
+        RGB {
+            r: over(premul.r, self.r),
+            g: over(premul.g, self.g),
+            b: over(premul.b, self.b),
+        }
+    }
+}
+
+/// Number of bytes blended at once, a multiple of the 3 channels filling whole 128 and 256-bit registers with 16-bit lanes.
+const BLEND_BLOCK: usize = 48;
+
+/// Blends a single RGBA color onto many RGB values using integer arithmetic.
+///
+/// The color is premultiplied once, so blending a channel only takes a 16-bit multiply-add and a division by `255` done with shifts.
+/// Spans are blended as bytes in blocks which line the channels up with the lanes of SIMD registers.
+/// On x86-64 a copy of the loop compiled for AVX2 is used if the CPU supports it, detected at runtime,
+/// otherwise the baseline SIMD instructions of the target are used (SSE2 on x86-64, NEON on aarch64).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Blender {
+    premultiplied: [u16; 3],
+    inverse_alpha: u16,
+}
+
+impl Blender {
+    pub(crate) fn new(color: RGBA) -> Self {
+        let alpha = color.a as u16;
+        Self {
+            premultiplied: [
+                color.r as u16 * alpha,
+                color.g as u16 * alpha,
+                color.b as u16 * alpha,
+            ],
+            inverse_alpha: 255 - alpha,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn blend(&self, base: RGB) -> RGB {
+        RGB {
+            r: self.mix(base.r, self.premultiplied[0]),
+            g: self.mix(base.g, self.premultiplied[1]),
+            b: self.mix(base.b, self.premultiplied[2]),
+        }
+    }
+
+    /// Blends the color onto every value of a span.
+    pub(crate) fn blend_span(&self, span: &mut [RGB]) {
+        // SAFETY: `RGB` is `repr(C)` and consists of three `u8`, so it has no padding
+        // and a span of `RGB` is a valid span of three times as many `u8`.
+        let bytes =
+            unsafe { std::slice::from_raw_parts_mut(span.as_mut_ptr() as *mut u8, span.len() * 3) };
+
+        #[cfg(target_arch = "x86_64")]
+        if bytes.len() >= BLEND_BLOCK && std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the CPU supports AVX2.
+            unsafe { self.blend_bytes_avx2(bytes) };
+            return;
+        }
+
+        self.blend_bytes(bytes);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[target_feature(enable = "avx2")]
+    unsafe fn blend_bytes_avx2(&self, bytes: &mut [u8]) {
+        self.blend_bytes(bytes);
+    }
+
+    /// Blends the color onto the bytes of interleaved `R, G, B` values.
+    #[inline(always)]
+    fn blend_bytes(&self, bytes: &mut [u8]) {
+        let pattern: [u16; BLEND_BLOCK] = std::array::from_fn(|i| self.premultiplied[i % 3]);
+
+        let mut blocks = bytes.chunks_exact_mut(BLEND_BLOCK);
+        for block in &mut blocks {
+            for (v, &p) in block.iter_mut().zip(&pattern) {
+                *v = self.mix(*v, p);
+            }
+        }
+        for (v, &p) in blocks.into_remainder().iter_mut().zip(&pattern) {
+            *v = self.mix(*v, p);
+        }
+    }
+
+    #[inline(always)]
+    fn mix(&self, base: u8, premultiplied: u16) -> u8 {
+        div255(base as u16 * self.inverse_alpha + premultiplied)
+    }
+}
+
+/// Divides a value of at most `255 * 255` by `255` rounding down, without an actual division.
+#[inline(always)]
+fn div255(v: u16) -> u8 {
+    ((v + (v >> 8) + 1) >> 8) as u8
+}
+
+/// Sets every value of a span to a color.
+///
+/// The span is filled as bytes in blocks, which is faster than copying the three byte values one by one.
+pub(crate) fn fill_span(span: &mut [RGB], color: RGB) {
+    // SAFETY: `RGB` is `repr(C)` and consists of three `u8`, so it has no padding
+    // and a span of `RGB` is a valid span of three times as many `u8`.
+    let bytes =
+        unsafe { std::slice::from_raw_parts_mut(span.as_mut_ptr() as *mut u8, span.len() * 3) };
+    let pattern: [u8; BLEND_BLOCK] = std::array::from_fn(|i| [color.r, color.g, color.b][i % 3]);
+
+    let mut blocks = bytes.chunks_exact_mut(BLEND_BLOCK);
+    for block in &mut blocks {
+        block.copy_from_slice(&pattern);
+    }
+    let remainder = blocks.into_remainder();
+    let len = remainder.len();
+    remainder.copy_from_slice(&pattern[..len]);
+}
+
+/// Packs colors into 32-bit values in the specified byte order with an opaque alpha channel.
+///
+/// Like [`Blender::blend_span`] a copy compiled for AVX2 is used on x86-64 CPUs supporting it.
+pub(crate) fn pack_span(order: PixelOrder, src: &[RGB], dst: &mut [u32]) {
+    #[cfg(target_arch = "x86_64")]
+    if src.len() >= 16 && std::is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU supports AVX2.
+        unsafe { pack_span_avx2(order, src, dst) };
+        return;
+    }
+
+    pack_span_generic(order, src, dst);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn pack_span_avx2(order: PixelOrder, src: &[RGB], dst: &mut [u32]) {
+    pack_span_generic(order, src, dst);
+}
+
+#[inline(always)]
+fn pack_span_generic(order: PixelOrder, src: &[RGB], dst: &mut [u32]) {
+    #[inline(always)]
+    fn pack_with<F: Fn(RGB) -> u32>(src: &[RGB], dst: &mut [u32], pack: F) {
+        for (d, &c) in dst.iter_mut().zip(src) {
+            *d = pack(c);
+        }
+    }
+
+    // matching once outside of the loop keeps the loop free of branches
+    match order {
+        PixelOrder::XRGB => pack_with(src, dst, |c| c.to_u32(PixelOrder::XRGB)),
+        PixelOrder::ARGB => pack_with(src, dst, |c| c.to_u32(PixelOrder::ARGB)),
+        PixelOrder::RGBA => pack_with(src, dst, |c| c.to_u32(PixelOrder::RGBA)),
+        PixelOrder::ABGR => pack_with(src, dst, |c| c.to_u32(PixelOrder::ABGR)),
+        PixelOrder::BGRA => pack_with(src, dst, |c| c.to_u32(PixelOrder::BGRA)),
+    }
+}
+
+/// A color given by hue, saturation and value.
+///
+/// The hue is given in degrees in the range `0..360`, saturation and value in the range `0..=1`.
+/// Converting an [`RGB`] color to HSV and back results in the same color.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::{HSV, RGB};
+///
+/// // distinct hues for chart series
+/// let base = HSV { h: 210.0, s: 0.7, v: 0.9 };
+/// let series: Vec<RGB> = (0..5).map(|i| base.with_hue_rotated(i as f32 * 72.0).into()).collect();
+///
+/// let orange = RGB { r: 255, g: 128, b: 0 };
+/// assert_eq!(orange, RGB::from(HSV::from(orange)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HSV {
+    pub h: f32,
+    pub s: f32,
+    pub v: f32,
+}
+
+impl HSV {
+    /// Converts an RGBA color, returning its alpha separately.
+    pub fn from_rgba(color: RGBA) -> (Self, u8) {
+        let (rgb, a) = color.to_rgb();
+        (rgb.into(), a)
+    }
+
+    /// Converts the color to RGBA with the specified alpha.
+    pub fn to_rgba(self, a: u8) -> RGBA {
+        let RGB { r, g, b } = self.into();
+        RGBA { r, g, b, a }
+    }
+
+    /// Returns the color with its hue rotated by an angle in degrees.
+    pub fn with_hue_rotated(self, degrees: f32) -> Self {
+        Self {
+            h: (self.h + degrees).rem_euclid(360.0),
+            ..self
+        }
+    }
+}
+
+impl From<RGB> for HSV {
+    fn from(color: RGB) -> Self {
+        let (h, max, min) = hue_max_min(color);
+        let s = match max {
+            0.0 => 0.0,
+            _ => (max - min) / max,
+        };
+        Self { h, s, v: max }
+    }
+}
+
+impl From<HSV> for RGB {
+    fn from(color: HSV) -> Self {
+        let v = color.v.clamp(0.0, 1.0);
+        let chroma = v * color.s.clamp(0.0, 1.0);
+        from_hue_chroma(color.h, chroma, v - chroma)
+    }
+}
+
+/// A color given by hue, saturation and lightness.
+///
+/// The hue is given in degrees in the range `0..360`, saturation and lightness in the range `0..=1`.
+/// Converting an [`RGB`] color to HSL and back results in the same color.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::{HSL, RGB};
+///
+/// let teal = RGB { r: 0, g: 128, b: 128 };
+/// let hsl = HSL::from(teal);
+///
+/// assert_eq!(180.0, hsl.h);
+/// assert_eq!(RGB { r: 128, g: 0, b: 128 }, hsl.with_hue_rotated(120.0).into());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HSL {
+    pub h: f32,
+    pub s: f32,
+    pub l: f32,
+}
+
+impl HSL {
+    /// Converts an RGBA color, returning its alpha separately.
+    pub fn from_rgba(color: RGBA) -> (Self, u8) {
+        let (rgb, a) = color.to_rgb();
+        (rgb.into(), a)
+    }
+
+    /// Converts the color to RGBA with the specified alpha.
+    pub fn to_rgba(self, a: u8) -> RGBA {
+        let RGB { r, g, b } = self.into();
+        RGBA { r, g, b, a }
+    }
+
+    /// Returns the color with its hue rotated by an angle in degrees.
+    pub fn with_hue_rotated(self, degrees: f32) -> Self {
+        Self {
+            h: (self.h + degrees).rem_euclid(360.0),
+            ..self
+        }
+    }
+}
+
+impl From<RGB> for HSL {
+    fn from(color: RGB) -> Self {
+        let (h, max, min) = hue_max_min(color);
+        let l = (max + min) / 2.0;
+        let s = match max - min {
+            0.0 => 0.0,
+            delta => delta / (1.0 - (2.0 * l - 1.0).abs()),
+        };
+        Self { h, s, l }
+    }
+}
+
+impl From<HSL> for RGB {
+    fn from(color: HSL) -> Self {
+        let l = color.l.clamp(0.0, 1.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * color.s.clamp(0.0, 1.0);
+        from_hue_chroma(color.h, chroma, l - chroma / 2.0)
+    }
+}
+
+/// A color in the perceptual OKLab color space.
+///
+/// Equal distances in OKLab roughly appear as equal differences in color, which makes it well suited for interpolation.
+/// The lightness `l` is in the range `0..=1`, `a` and `b` are roughly in the range `-0.4..=0.4`.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::{OKLab, RGB};
+///
+/// let white = OKLab::from(RGB { r: 255, g: 255, b: 255 });
+///
+/// assert!((white.l - 1.0).abs() < 1e-3);
+/// assert_eq!(RGB { r: 255, g: 255, b: 255 }, white.into());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OKLab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+impl OKLab {
+    /// Performs a linear interpolation between two OKLab colors returning the result.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            l: self.l + (other.l - self.l) * t,
+            a: self.a + (other.a - self.a) * t,
+            b: self.b + (other.b - self.b) * t,
+        }
+    }
+}
+
+impl From<RGB> for OKLab {
+    fn from(color: RGB) -> Self {
+        let linear = [color.r, color.g, color.b].map(srgb_to_linear);
+        let lms = mul_matrix(&LINEAR_SRGB_TO_LMS, linear).map(f32::cbrt);
+        let [l, a, b] = mul_matrix(&LMS_TO_OKLAB, lms);
+        Self { l, a, b }
+    }
+}
+
+impl From<OKLab> for RGB {
+    fn from(color: OKLab) -> Self {
+        let lms = mul_matrix(&OKLAB_TO_LMS, [color.l, color.a, color.b]).map(|v| v * v * v);
+        let [r, g, b] = mul_matrix(&LMS_TO_LINEAR_SRGB, lms).map(linear_to_srgb);
+        RGB { r, g, b }
+    }
+}
+
+/// A color in the cylindrical form of [`OKLab`] given by lightness, chroma and hue.
+///
+/// The hue is given in degrees in the range `0..360`.
+/// Unlike the hue of [`HSV`] and [`HSL`], rotating it keeps the perceived lightness of the color.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct OKLCH {
+    pub l: f32,
+    pub c: f32,
+    pub h: f32,
+}
+
+impl OKLCH {
+    /// Returns the color with its hue rotated by an angle in degrees.
+    pub fn with_hue_rotated(self, degrees: f32) -> Self {
+        Self {
+            h: (self.h + degrees).rem_euclid(360.0),
+            ..self
+        }
+    }
+}
+
+impl From<OKLab> for OKLCH {
+    fn from(color: OKLab) -> Self {
+        Self {
+            l: color.l,
+            c: color.a.hypot(color.b),
+            h: color.b.atan2(color.a).to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+impl From<OKLCH> for OKLab {
+    fn from(color: OKLCH) -> Self {
+        let (sin, cos) = color.h.to_radians().sin_cos();
+        Self {
+            l: color.l,
+            a: color.c * cos,
+            b: color.c * sin,
+        }
+    }
+}
+
+impl From<RGB> for OKLCH {
+    fn from(color: RGB) -> Self {
+        OKLab::from(color).into()
+    }
+}
+
+impl From<OKLCH> for RGB {
+    fn from(color: OKLCH) -> Self {
+        OKLab::from(color).into()
+    }
+}
+
+/// Color space colors are interpolated in, e.g. by gradients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interpolation {
+    /// Interpolates the sRGB encoded channels directly.
+    #[default]
+    SRGB,
+    /// Interpolates in [`OKLab`], avoiding the dark and muddy midpoints of sRGB interpolation.
+    OKLab,
+}
+
+impl Interpolation {
+    /// Interpolates between two colors, `t` being in the range `0..=1`.
+    pub fn lerp(self, from: RGB, to: RGB, t: f64) -> RGB {
+        match self {
+            Interpolation::SRGB => from.lerp(&to, t),
+            Interpolation::OKLab => from.lerp_oklab(&to, t),
+        }
+    }
+}
+
+/// Linear sRGB to the LMS cone response used by OKLab.
+const LINEAR_SRGB_TO_LMS: [[f32; 3]; 3] = [
+    [0.412_221_46, 0.536_332_55, 0.051_445_995],
+    [0.211_903_5, 0.680_699_5, 0.107_396_96],
+    [0.088_302_46, 0.281_718_85, 0.629_978_7],
+];
+
+/// Nonlinear LMS to OKLab.
+const LMS_TO_OKLAB: [[f32; 3]; 3] = [
+    [0.210_454_26, 0.793_617_8, -0.004_072_047],
+    [1.977_998_5, -2.428_592_2, 0.450_593_7],
+    [0.025_904_037, 0.782_771_77, -0.808_675_77],
+];
+
+/// OKLab to nonlinear LMS.
+const OKLAB_TO_LMS: [[f32; 3]; 3] = [
+    [1.0, 0.396_337_78, 0.215_803_76],
+    [1.0, -0.105_561_346, -0.063_854_17],
+    [1.0, -0.089_484_18, -1.291_485_5],
+];
+
+/// LMS cone response to linear sRGB.
+const LMS_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [4.076_741_7, -3.307_711_6, 0.230_969_94],
+    [-1.268_438, 2.609_757_4, -0.341_319_38],
+    [-0.004_196_086_3, -0.703_418_6, 1.707_614_7],
+];
+
+fn mul_matrix(matrix: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// Returns the hue in degrees as well as the largest and smallest channel in the range `0..=1`.
+fn hue_max_min(color: RGB) -> (f32, f32, f32) {
+    let [r, g, b] = [color.r, color.g, color.b].map(|v| v as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let h = match delta {
+        0.0 => 0.0,
+        _ if max == r => 60.0 * ((g - b) / delta).rem_euclid(6.0),
+        _ if max == g => 60.0 * ((b - r) / delta + 2.0),
+        _ => 60.0 * ((r - g) / delta + 4.0),
+    };
+    (h, max, min)
+}
+
+/// Builds a color from its hue in degrees, chroma and the value added to every channel.
+fn from_hue_chroma(h: f32, chroma: f32, m: f32) -> RGB {
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let to_u8 = |v: f32| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    RGB {
+        r: to_u8(r),
+        g: to_u8(g),
+        b: to_u8(b),
+    }
+}
+
+/// RGB color space the channels of a color are encoded in.
+///
+/// Both color spaces share the sRGB transfer function and white point but differ in their primaries,
+/// Display-P3 covers a wider gamut used by many modern displays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    #[default]
+    SRGB,
+    DisplayP3,
+}
+
+impl ColorSpace {
+    /// Converts a color from this color space into another one.
+    ///
+    /// Colors outside of the gamut of the target color space are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::{ColorSpace, RGB};
+    ///
+    /// let red = RGB { r: 255, g: 0, b: 0 };
+    /// let p3 = ColorSpace::SRGB.convert(ColorSpace::DisplayP3, red);
+    ///
+    /// assert_eq!(RGB { r: 234, g: 51, b: 35 }, p3);
+    /// ```
+    pub fn convert(self, to: ColorSpace, color: RGB) -> RGB {
+        let matrix = match (self, to) {
+            (ColorSpace::SRGB, ColorSpace::DisplayP3) => &SRGB_TO_DISPLAY_P3,
+            (ColorSpace::DisplayP3, ColorSpace::SRGB) => &DISPLAY_P3_TO_SRGB,
+            _ => return color,
+        };
+
+        let linear = [color.r, color.g, color.b].map(srgb_to_linear);
+        let mapped = matrix.map(|row| row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2]);
+        let [r, g, b] = mapped.map(linear_to_srgb);
+        RGB { r, g, b }
+    }
+}
+
+/// Linear sRGB to linear Display-P3.
+const SRGB_TO_DISPLAY_P3: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_7, 0.072_397_4, 0.910_519_9],
+];
+
+/// Linear Display-P3 to linear sRGB.
+const DISPLAY_P3_TO_SRGB: [[f32; 3]; 3] = [
+    [1.224_940_1, -0.224_940_4, 0.0],
+    [-0.042_056_9, 1.042_057_1, 0.0],
+    [-0.019_637_6, -0.078_636_1, 1.098_273_5],
+];
+
+/// Decodes a channel using the sRGB transfer function.
+pub(crate) fn srgb_to_linear(v: u8) -> f32 {
+    let v = v as f32 / 255.0;
+    match v <= 0.04045 {
+        true => v / 12.92,
+        false => ((v + 0.055) / 1.055).powf(2.4),
+    }
+}
+
+/// Encodes a linear channel using the sRGB transfer function.
+pub(crate) fn linear_to_srgb(v: f32) -> u8 {
+    let v = v.clamp(0.0, 1.0);
+    let v = match v <= 0.003_130_8 {
+        true => v * 12.92,
+        false => 1.055 * v.powf(1.0 / 2.4) - 0.055,
+    };
+    (v * 255.0).round() as u8
+}
+
+/// A single color channel of an [`RGB`] color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+}
+
+/// Byte order used when packing a color into a 32-bit value, from the most to the least significant byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelOrder {
+    /// `0RGB`, the unused most significant byte is zero. Used by e.g. minifb and softbuffer.
+    XRGB,
+    ARGB,
+    RGBA,
+    ABGR,
+    BGRA,
+}
+
+impl PixelOrder {
+    /// Packs a color into a 32-bit value.
+    pub fn pack(self, color: RGBA) -> u32 {
+        let RGBA { r, g, b, a } = color;
+        let bytes = match self {
+            PixelOrder::XRGB => [0, r, g, b],
+            PixelOrder::ARGB => [a, r, g, b],
+            PixelOrder::RGBA => [r, g, b, a],
+            PixelOrder::ABGR => [a, b, g, r],
+            PixelOrder::BGRA => [b, g, r, a],
+        };
+        u32::from_be_bytes(bytes)
+    }
+
+    /// Unpacks a 32-bit value into a color.
+    ///
+    /// The alpha channel of `XRGB` is always opaque.
+    pub fn unpack(self, value: u32) -> RGBA {
+        let [x, y, z, w] = value.to_be_bytes();
+        match self {
+            PixelOrder::XRGB => RGBA::new(y, z, w, 255),
+            PixelOrder::ARGB => RGBA::new(y, z, w, x),
+            PixelOrder::RGBA => RGBA::new(x, y, z, w),
+            PixelOrder::ABGR => RGBA::new(w, z, y, x),
+            PixelOrder::BGRA => RGBA::new(z, y, x, w),
+        }
+    }
+}
+
+/// Layout of a single pixel when exporting a canvas as raw bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteFormat {
+    /// Three bytes per pixel in the order `R, G, B`.
+    RGB8,
+    /// Four bytes per pixel in the order `R, G, B, A` with an opaque alpha channel.
+    RGBA8,
+}
+
+impl ByteFormat {
+    /// Returns the number of bytes a single pixel takes up.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            ByteFormat::RGB8 => 3,
+            ByteFormat::RGBA8 => 4,
+        }
+    }
 }
 
 //== constants =====
@@ -91,3 +838,158 @@ pub const BLUE: RGBA = RGBA {
     b: 255,
     a: 255,
 };
+
+//== named colors =====
+
+/// All CSS named colors sorted by name, see [`RGBA::named`].
+#[rustfmt::skip]
+pub const NAMED_COLORS: &[(&str, RGB)] = &[
+    ("aliceblue", RGB { r: 240, g: 248, b: 255 }),
+    ("antiquewhite", RGB { r: 250, g: 235, b: 215 }),
+    ("aqua", RGB { r: 0, g: 255, b: 255 }),
+    ("aquamarine", RGB { r: 127, g: 255, b: 212 }),
+    ("azure", RGB { r: 240, g: 255, b: 255 }),
+    ("beige", RGB { r: 245, g: 245, b: 220 }),
+    ("bisque", RGB { r: 255, g: 228, b: 196 }),
+    ("black", RGB { r: 0, g: 0, b: 0 }),
+    ("blanchedalmond", RGB { r: 255, g: 235, b: 205 }),
+    ("blue", RGB { r: 0, g: 0, b: 255 }),
+    ("blueviolet", RGB { r: 138, g: 43, b: 226 }),
+    ("brown", RGB { r: 165, g: 42, b: 42 }),
+    ("burlywood", RGB { r: 222, g: 184, b: 135 }),
+    ("cadetblue", RGB { r: 95, g: 158, b: 160 }),
+    ("chartreuse", RGB { r: 127, g: 255, b: 0 }),
+    ("chocolate", RGB { r: 210, g: 105, b: 30 }),
+    ("coral", RGB { r: 255, g: 127, b: 80 }),
+    ("cornflowerblue", RGB { r: 100, g: 149, b: 237 }),
+    ("cornsilk", RGB { r: 255, g: 248, b: 220 }),
+    ("crimson", RGB { r: 220, g: 20, b: 60 }),
+    ("cyan", RGB { r: 0, g: 255, b: 255 }),
+    ("darkblue", RGB { r: 0, g: 0, b: 139 }),
+    ("darkcyan", RGB { r: 0, g: 139, b: 139 }),
+    ("darkgoldenrod", RGB { r: 184, g: 134, b: 11 }),
+    ("darkgray", RGB { r: 169, g: 169, b: 169 }),
+    ("darkgreen", RGB { r: 0, g: 100, b: 0 }),
+    ("darkgrey", RGB { r: 169, g: 169, b: 169 }),
+    ("darkkhaki", RGB { r: 189, g: 183, b: 107 }),
+    ("darkmagenta", RGB { r: 139, g: 0, b: 139 }),
+    ("darkolivegreen", RGB { r: 85, g: 107, b: 47 }),
+    ("darkorange", RGB { r: 255, g: 140, b: 0 }),
+    ("darkorchid", RGB { r: 153, g: 50, b: 204 }),
+    ("darkred", RGB { r: 139, g: 0, b: 0 }),
+    ("darksalmon", RGB { r: 233, g: 150, b: 122 }),
+    ("darkseagreen", RGB { r: 143, g: 188, b: 143 }),
+    ("darkslateblue", RGB { r: 72, g: 61, b: 139 }),
+    ("darkslategray", RGB { r: 47, g: 79, b: 79 }),
+    ("darkslategrey", RGB { r: 47, g: 79, b: 79 }),
+    ("darkturquoise", RGB { r: 0, g: 206, b: 209 }),
+    ("darkviolet", RGB { r: 148, g: 0, b: 211 }),
+    ("deeppink", RGB { r: 255, g: 20, b: 147 }),
+    ("deepskyblue", RGB { r: 0, g: 191, b: 255 }),
+    ("dimgray", RGB { r: 105, g: 105, b: 105 }),
+    ("dimgrey", RGB { r: 105, g: 105, b: 105 }),
+    ("dodgerblue", RGB { r: 30, g: 144, b: 255 }),
+    ("firebrick", RGB { r: 178, g: 34, b: 34 }),
+    ("floralwhite", RGB { r: 255, g: 250, b: 240 }),
+    ("forestgreen", RGB { r: 34, g: 139, b: 34 }),
+    ("fuchsia", RGB { r: 255, g: 0, b: 255 }),
+    ("gainsboro", RGB { r: 220, g: 220, b: 220 }),
+    ("ghostwhite", RGB { r: 248, g: 248, b: 255 }),
+    ("gold", RGB { r: 255, g: 215, b: 0 }),
+    ("goldenrod", RGB { r: 218, g: 165, b: 32 }),
+    ("gray", RGB { r: 128, g: 128, b: 128 }),
+    ("green", RGB { r: 0, g: 128, b: 0 }),
+    ("greenyellow", RGB { r: 173, g: 255, b: 47 }),
+    ("grey", RGB { r: 128, g: 128, b: 128 }),
+    ("honeydew", RGB { r: 240, g: 255, b: 240 }),
+    ("hotpink", RGB { r: 255, g: 105, b: 180 }),
+    ("indianred", RGB { r: 205, g: 92, b: 92 }),
+    ("indigo", RGB { r: 75, g: 0, b: 130 }),
+    ("ivory", RGB { r: 255, g: 255, b: 240 }),
+    ("khaki", RGB { r: 240, g: 230, b: 140 }),
+    ("lavender", RGB { r: 230, g: 230, b: 250 }),
+    ("lavenderblush", RGB { r: 255, g: 240, b: 245 }),
+    ("lawngreen", RGB { r: 124, g: 252, b: 0 }),
+    ("lemonchiffon", RGB { r: 255, g: 250, b: 205 }),
+    ("lightblue", RGB { r: 173, g: 216, b: 230 }),
+    ("lightcoral", RGB { r: 240, g: 128, b: 128 }),
+    ("lightcyan", RGB { r: 224, g: 255, b: 255 }),
+    ("lightgoldenrodyellow", RGB { r: 250, g: 250, b: 210 }),
+    ("lightgray", RGB { r: 211, g: 211, b: 211 }),
+    ("lightgreen", RGB { r: 144, g: 238, b: 144 }),
+    ("lightgrey", RGB { r: 211, g: 211, b: 211 }),
+    ("lightpink", RGB { r: 255, g: 182, b: 193 }),
+    ("lightsalmon", RGB { r: 255, g: 160, b: 122 }),
+    ("lightseagreen", RGB { r: 32, g: 178, b: 170 }),
+    ("lightskyblue", RGB { r: 135, g: 206, b: 250 }),
+    ("lightslategray", RGB { r: 119, g: 136, b: 153 }),
+    ("lightslategrey", RGB { r: 119, g: 136, b: 153 }),
+    ("lightsteelblue", RGB { r: 176, g: 196, b: 222 }),
+    ("lightyellow", RGB { r: 255, g: 255, b: 224 }),
+    ("lime", RGB { r: 0, g: 255, b: 0 }),
+    ("limegreen", RGB { r: 50, g: 205, b: 50 }),
+    ("linen", RGB { r: 250, g: 240, b: 230 }),
+    ("magenta", RGB { r: 255, g: 0, b: 255 }),
+    ("maroon", RGB { r: 128, g: 0, b: 0 }),
+    ("mediumaquamarine", RGB { r: 102, g: 205, b: 170 }),
+    ("mediumblue", RGB { r: 0, g: 0, b: 205 }),
+    ("mediumorchid", RGB { r: 186, g: 85, b: 211 }),
+    ("mediumpurple", RGB { r: 147, g: 112, b: 219 }),
+    ("mediumseagreen", RGB { r: 60, g: 179, b: 113 }),
+    ("mediumslateblue", RGB { r: 123, g: 104, b: 238 }),
+    ("mediumspringgreen", RGB { r: 0, g: 250, b: 154 }),
+    ("mediumturquoise", RGB { r: 72, g: 209, b: 204 }),
+    ("mediumvioletred", RGB { r: 199, g: 21, b: 133 }),
+    ("midnightblue", RGB { r: 25, g: 25, b: 112 }),
+    ("mintcream", RGB { r: 245, g: 255, b: 250 }),
+    ("mistyrose", RGB { r: 255, g: 228, b: 225 }),
+    ("moccasin", RGB { r: 255, g: 228, b: 181 }),
+    ("navajowhite", RGB { r: 255, g: 222, b: 173 }),
+    ("navy", RGB { r: 0, g: 0, b: 128 }),
+    ("oldlace", RGB { r: 253, g: 245, b: 230 }),
+    ("olive", RGB { r: 128, g: 128, b: 0 }),
+    ("olivedrab", RGB { r: 107, g: 142, b: 35 }),
+    ("orange", RGB { r: 255, g: 165, b: 0 }),
+    ("orangered", RGB { r: 255, g: 69, b: 0 }),
+    ("orchid", RGB { r: 218, g: 112, b: 214 }),
+    ("palegoldenrod", RGB { r: 238, g: 232, b: 170 }),
+    ("palegreen", RGB { r: 152, g: 251, b: 152 }),
+    ("paleturquoise", RGB { r: 175, g: 238, b: 238 }),
+    ("palevioletred", RGB { r: 219, g: 112, b: 147 }),
+    ("papayawhip", RGB { r: 255, g: 239, b: 213 }),
+    ("peachpuff", RGB { r: 255, g: 218, b: 185 }),
+    ("peru", RGB { r: 205, g: 133, b: 63 }),
+    ("pink", RGB { r: 255, g: 192, b: 203 }),
+    ("plum", RGB { r: 221, g: 160, b: 221 }),
+    ("powderblue", RGB { r: 176, g: 224, b: 230 }),
+    ("purple", RGB { r: 128, g: 0, b: 128 }),
+    ("rebeccapurple", RGB { r: 102, g: 51, b: 153 }),
+    ("red", RGB { r: 255, g: 0, b: 0 }),
+    ("rosybrown", RGB { r: 188, g: 143, b: 143 }),
+    ("royalblue", RGB { r: 65, g: 105, b: 225 }),
+    ("saddlebrown", RGB { r: 139, g: 69, b: 19 }),
+    ("salmon", RGB { r: 250, g: 128, b: 114 }),
+    ("sandybrown", RGB { r: 244, g: 164, b: 96 }),
+    ("seagreen", RGB { r: 46, g: 139, b: 87 }),
+    ("seashell", RGB { r: 255, g: 245, b: 238 }),
+    ("sienna", RGB { r: 160, g: 82, b: 45 }),
+    ("silver", RGB { r: 192, g: 192, b: 192 }),
+    ("skyblue", RGB { r: 135, g: 206, b: 235 }),
+    ("slateblue", RGB { r: 106, g: 90, b: 205 }),
+    ("slategray", RGB { r: 112, g: 128, b: 144 }),
+    ("slategrey", RGB { r: 112, g: 128, b: 144 }),
+    ("snow", RGB { r: 255, g: 250, b: 250 }),
+    ("springgreen", RGB { r: 0, g: 255, b: 127 }),
+    ("steelblue", RGB { r: 70, g: 130, b: 180 }),
+    ("tan", RGB { r: 210, g: 180, b: 140 }),
+    ("teal", RGB { r: 0, g: 128, b: 128 }),
+    ("thistle", RGB { r: 216, g: 191, b: 216 }),
+    ("tomato", RGB { r: 255, g: 99, b: 71 }),
+    ("turquoise", RGB { r: 64, g: 224, b: 208 }),
+    ("violet", RGB { r: 238, g: 130, b: 238 }),
+    ("wheat", RGB { r: 245, g: 222, b: 179 }),
+    ("white", RGB { r: 255, g: 255, b: 255 }),
+    ("whitesmoke", RGB { r: 245, g: 245, b: 245 }),
+    ("yellow", RGB { r: 255, g: 255, b: 0 }),
+    ("yellowgreen", RGB { r: 154, g: 205, b: 50 }),
+];
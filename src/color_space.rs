@@ -0,0 +1,40 @@
+use crate::canvas::Canvas;
+use crate::color::ColorSpace;
+
+impl Canvas {
+    /// Converts the pixels of the canvas into another color space and tags the canvas with it.
+    ///
+    /// Colors outside of the gamut of the target color space are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::{ColorSpace, RGB};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 255, g: 0, b: 0 });
+    ///
+    /// // output for a wide-gamut display
+    /// canvas.convert_color_space(ColorSpace::DisplayP3);
+    ///
+    /// assert_eq!(ColorSpace::DisplayP3, canvas.color_space());
+    /// assert_eq!(Some(&RGB { r: 234, g: 51, b: 35 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn convert_color_space(&mut self, to: ColorSpace) {
+        let from = self.color_space();
+        if from == to {
+            return;
+        }
+
+        self.for_each_row_mut(0..self.height(), |_, row| {
+            for pixel in row {
+                *pixel = from.convert(to, *pixel);
+            }
+        });
+        self.set_color_space(to);
+    }
+}
@@ -0,0 +1,688 @@
+use std::fmt::Write;
+
+use crate::canvas::{Canvas, Draw};
+use crate::color::{RGB, RGBA};
+
+/// A single recorded drawing operation, mirroring one of the drawing methods of [`Canvas`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrawCommand {
+    /// See [`Canvas::fill`].
+    Fill { color: RGB },
+    /// See [`Canvas::draw_pixel`].
+    Pixel { x: isize, y: isize, color: RGBA },
+    /// See [`Canvas::draw_line`].
+    Line {
+        x1: isize,
+        y1: isize,
+        x2: isize,
+        y2: isize,
+        color: RGBA,
+    },
+    /// See [`Canvas::draw_polyline`] and [`Canvas::draw_polyline_capped`].
+    Polyline {
+        x1: isize,
+        y1: isize,
+        x2: isize,
+        y2: isize,
+        width: u32,
+        capped: bool,
+        color: RGBA,
+    },
+    /// See [`Canvas::draw_circle`] and [`Canvas::draw_circle_solid`].
+    Circle {
+        x: isize,
+        y: isize,
+        r: u32,
+        solid: bool,
+        color: RGBA,
+    },
+    /// See [`Canvas::fill_rect`].
+    Rect {
+        x: isize,
+        y: isize,
+        width: usize,
+        height: usize,
+        color: RGBA,
+    },
+    /// See [`Canvas::draw_polygon`] and [`Canvas::draw_polygon_solid`].
+    Polygon {
+        vertices: Vec<(isize, isize)>,
+        clockwise: bool,
+        solid: bool,
+        color: RGBA,
+    },
+}
+
+impl Draw for DrawCommand {
+    fn draw(&self, canvas: &mut Canvas) {
+        match *self {
+            DrawCommand::Fill { color } => canvas.fill(color),
+            DrawCommand::Pixel { x, y, color } => {
+                canvas.draw_pixel(x, y, color);
+            }
+            DrawCommand::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color,
+            } => canvas.draw_line(x1, y1, x2, y2, color),
+            DrawCommand::Polyline {
+                x1,
+                y1,
+                x2,
+                y2,
+                width,
+                capped,
+                color,
+            } => match capped {
+                true => canvas.draw_polyline_capped(x1, y1, x2, y2, width, color),
+                false => canvas.draw_polyline(x1, y1, x2, y2, width, color),
+            },
+            DrawCommand::Circle {
+                x,
+                y,
+                r,
+                solid,
+                color,
+            } => match solid {
+                true => canvas.draw_circle_solid(x, y, r, color),
+                false => canvas.draw_circle(x, y, r, color),
+            },
+            DrawCommand::Rect {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => canvas.fill_rect(x, y, width, height, color),
+            DrawCommand::Polygon {
+                ref vertices,
+                clockwise,
+                solid,
+                color,
+            } => match solid {
+                true => canvas.draw_polygon_solid(vertices, clockwise, color),
+                false => canvas.draw_polygon(vertices, color),
+            },
+        }
+    }
+}
+
+/// A recorded list of drawing operations which can be dumped to JSON and loaded back.
+///
+/// This allows other tools to generate drawings rasterized by this crate
+/// and turns rendering bugs into reproducible command dumps.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{RGB, WHITE};
+/// use drawing_stuff::commands::{CommandList, DrawCommand};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut commands = CommandList::new();
+/// commands.push(DrawCommand::Fill { color: RGB { r: 20, g: 20, b: 20 } });
+/// commands.push(DrawCommand::Circle { x: 200, y: 100, r: 50, solid: true, color: WHITE });
+///
+/// let json = commands.to_json();
+/// let loaded = CommandList::from_json(&json).unwrap();
+/// assert_eq!(commands, loaded);
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+/// canvas.draw(&loaded);
+/// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommandList {
+    commands: Vec<DrawCommand>,
+}
+
+impl CommandList {
+    /// Creates a new empty command list.
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    /// Records a command.
+    pub fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+
+    /// Returns the recorded commands.
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Returns the number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Checks if no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes all recorded commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Dumps the commands as a JSON array with one object per command.
+    ///
+    /// Every object has an `"op"` field naming the command, colors are arrays of their channels
+    /// and polygon vertices are arrays of `[x, y]` pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::WHITE;
+    /// use drawing_stuff::commands::{CommandList, DrawCommand};
+    ///
+    /// let mut commands = CommandList::new();
+    /// commands.push(DrawCommand::Pixel { x: 200, y: 100, color: WHITE });
+    ///
+    /// assert_eq!(
+    ///     r#"[{"op":"pixel","x":200,"y":100,"color":[255,255,255,255]}]"#,
+    ///     commands.to_json()
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let rgba = |c: RGBA| format!("[{},{},{},{}]", c.r, c.g, c.b, c.a);
+
+        let mut json = String::from("[");
+        for (i, command) in self.commands.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+
+            // writing into a string never fails
+            let _ = match command {
+                DrawCommand::Fill { color } => write!(
+                    json,
+                    r#"{{"op":"fill","color":[{},{},{}]}}"#,
+                    color.r, color.g, color.b
+                ),
+                DrawCommand::Pixel { x, y, color } => write!(
+                    json,
+                    r#"{{"op":"pixel","x":{x},"y":{y},"color":{}}}"#,
+                    rgba(*color)
+                ),
+                DrawCommand::Line {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color,
+                } => write!(
+                    json,
+                    r#"{{"op":"line","x1":{x1},"y1":{y1},"x2":{x2},"y2":{y2},"color":{}}}"#,
+                    rgba(*color)
+                ),
+                DrawCommand::Polyline {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    width,
+                    capped,
+                    color,
+                } => write!(
+                    json,
+                    r#"{{"op":"polyline","x1":{x1},"y1":{y1},"x2":{x2},"y2":{y2},"width":{width},"capped":{capped},"color":{}}}"#,
+                    rgba(*color)
+                ),
+                DrawCommand::Circle {
+                    x,
+                    y,
+                    r,
+                    solid,
+                    color,
+                } => write!(
+                    json,
+                    r#"{{"op":"circle","x":{x},"y":{y},"r":{r},"solid":{solid},"color":{}}}"#,
+                    rgba(*color)
+                ),
+                DrawCommand::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    color,
+                } => write!(
+                    json,
+                    r#"{{"op":"rect","x":{x},"y":{y},"width":{width},"height":{height},"color":{}}}"#,
+                    rgba(*color)
+                ),
+                DrawCommand::Polygon {
+                    vertices,
+                    clockwise,
+                    solid,
+                    color,
+                } => {
+                    let vertices: Vec<String> =
+                        vertices.iter().map(|(x, y)| format!("[{x},{y}]")).collect();
+                    write!(
+                        json,
+                        r#"{{"op":"polygon","vertices":[{}],"clockwise":{clockwise},"solid":{solid},"color":{}}}"#,
+                        vertices.join(","),
+                        rgba(*color)
+                    )
+                }
+            };
+        }
+        json.push(']');
+        json
+    }
+
+    /// Loads commands from JSON created by [`CommandList::to_json`].
+    ///
+    /// Returns `None` if the JSON is malformed, nests deeper than commands do, describes an unknown command
+    /// or holds whole numbers beyond the range of `i32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::commands::CommandList;
+    ///
+    /// let polygon = r#"[{"op":"polygon","vertices":[[0,0],[10,0],[0,1e1]],"clockwise":true,"solid":false,"color":[255,255,255,255]}]"#;
+    /// assert_eq!(1, CommandList::from_json(polygon).unwrap().len());
+    ///
+    /// // escapes are decoded even though no command uses strings apart from the keys
+    /// assert!(CommandList::from_json(r#"[{"\u006fp":"fill","color":[0,0,0]}]"#).is_some());
+    ///
+    /// assert!(CommandList::from_json(&"[".repeat(200_000)).is_none());
+    /// assert!(CommandList::from_json(r#"[{"op":"fill","color":[+1,0,0]}]"#).is_none());
+    /// assert!(CommandList::from_json(r#"[{"op":"fill","color":[01,0,0]}]"#).is_none());
+    /// assert!(CommandList::from_json(r#"[{"op":"circle","x":1e300,"y":0,"r":5,"solid":true,"color":[255,255,255,255]}]"#).is_none());
+    /// ```
+    pub fn from_json(json: &str) -> Option<Self> {
+        let mut parser = Parser {
+            bytes: json.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return None;
+        }
+
+        let commands = match value {
+            Json::Array(items) => items.iter().map(command_from_json).collect::<Option<_>>()?,
+            _ => return None,
+        };
+        Some(Self { commands })
+    }
+}
+
+impl Draw for CommandList {
+    fn draw(&self, canvas: &mut Canvas) {
+        for command in &self.commands {
+            command.draw(canvas);
+        }
+    }
+}
+
+fn command_from_json(value: &Json) -> Option<DrawCommand> {
+    let int = |key: &str| value.get(key)?.as_int();
+    let uint = |key: &str| int(key).and_then(|v| u32::try_from(v).ok());
+    let size = |key: &str| int(key).and_then(|v| usize::try_from(v).ok());
+    let bool = |key: &str| value.get(key)?.as_bool();
+    let channels = |key: &str| -> Option<Vec<u8>> {
+        value
+            .get(key)?
+            .as_array()?
+            .iter()
+            .map(|v| v.as_int().and_then(|v| u8::try_from(v).ok()))
+            .collect()
+    };
+    let rgba = |key: &str| match channels(key)?.as_slice() {
+        &[r, g, b, a] => Some(RGBA { r, g, b, a }),
+        _ => None,
+    };
+
+    let command = match value.get("op")?.as_str()? {
+        "fill" => match channels("color")?.as_slice() {
+            &[r, g, b] => DrawCommand::Fill {
+                color: RGB { r, g, b },
+            },
+            _ => return None,
+        },
+        "pixel" => DrawCommand::Pixel {
+            x: int("x")?,
+            y: int("y")?,
+            color: rgba("color")?,
+        },
+        "line" => DrawCommand::Line {
+            x1: int("x1")?,
+            y1: int("y1")?,
+            x2: int("x2")?,
+            y2: int("y2")?,
+            color: rgba("color")?,
+        },
+        "polyline" => DrawCommand::Polyline {
+            x1: int("x1")?,
+            y1: int("y1")?,
+            x2: int("x2")?,
+            y2: int("y2")?,
+            width: uint("width")?,
+            capped: bool("capped")?,
+            color: rgba("color")?,
+        },
+        "circle" => DrawCommand::Circle {
+            x: int("x")?,
+            y: int("y")?,
+            r: uint("r")?,
+            solid: bool("solid")?,
+            color: rgba("color")?,
+        },
+        "rect" => DrawCommand::Rect {
+            x: int("x")?,
+            y: int("y")?,
+            width: size("width")?,
+            height: size("height")?,
+            color: rgba("color")?,
+        },
+        "polygon" => DrawCommand::Polygon {
+            vertices: value
+                .get("vertices")?
+                .as_array()?
+                .iter()
+                .map(|v| match v.as_array()? {
+                    [x, y] => Some((x.as_int()?, y.as_int()?)),
+                    _ => None,
+                })
+                .collect::<Option<_>>()?,
+            clockwise: bool("clockwise")?,
+            solid: bool("solid")?,
+            color: rgba("color")?,
+        },
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// A parsed JSON value.
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns whole numbers within the range of `i32`, keeping coordinates far from overflowing while drawing.
+    fn as_int(&self) -> Option<isize> {
+        match *self {
+            Json::Number(v) if v.fract() == 0.0 && v.abs() <= i32::MAX as f64 => Some(v as isize),
+            _ => None,
+        }
+    }
+
+    fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Json::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Deepest nesting of arrays and objects accepted: the list, a command, its vertices and a single vertex.
+///
+/// Limiting it keeps malicious input from overflowing the stack of the recursive parser.
+const MAX_DEPTH: usize = 4;
+
+/// A minimal JSON parser supporting everything needed for command dumps.
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+
+    /// Number of arrays and objects currently open.
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        match self.peek()? == byte {
+            true => {
+                self.pos += 1;
+                Some(())
+            }
+            false => None,
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Json) -> Option<Json> {
+        let end = self.pos + literal.len();
+        match self.bytes.get(self.pos..end)? == literal.as_bytes() {
+            true => {
+                self.pos = end;
+                Some(value)
+            }
+            false => None,
+        }
+    }
+
+    fn value(&mut self) -> Option<Json> {
+        match self.peek()? {
+            b'n' => self.literal("null", Json::Null),
+            b't' => self.literal("true", Json::Bool(true)),
+            b'f' => self.literal("false", Json::Bool(false)),
+            b'"' => self.string().map(Json::String),
+            b'[' | b'{' if self.depth >= MAX_DEPTH => None,
+            b'[' => {
+                self.depth += 1;
+                let array = self.array();
+                self.depth -= 1;
+                array
+            }
+            b'{' => {
+                self.depth += 1;
+                let object = self.object();
+                self.depth -= 1;
+                object
+            }
+            _ => self.number().map(Json::Number),
+        }
+    }
+
+    fn array(&mut self) -> Option<Json> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Some(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Some(Json::Array(items));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn object(&mut self) -> Option<Json> {
+        self.expect(b'{')?;
+        let mut fields = Vec::new();
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Some(Json::Object(fields));
+        }
+        loop {
+            self.peek()?;
+            let key = self.string()?;
+            self.expect(b':')?;
+            fields.push((key, self.value()?));
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Some(Json::Object(fields));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if self.bytes.get(self.pos)? != &b'"' {
+            return None;
+        }
+        self.pos += 1;
+
+        let mut string = String::new();
+        loop {
+            let start = self.pos;
+            while !matches!(self.bytes.get(self.pos)?, b'"' | b'\\' | 0..=0x1f) {
+                self.pos += 1;
+            }
+            string.push_str(std::str::from_utf8(&self.bytes[start..self.pos]).ok()?);
+
+            match self.bytes[self.pos] {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(string);
+                }
+                b'\\' => {
+                    let escaped = *self.bytes.get(self.pos + 1)?;
+                    self.pos += 2;
+                    string.push(match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return None,
+                    });
+                }
+                // control characters have to be escaped
+                _ => return None,
+            }
+        }
+    }
+
+    /// Parses the digits of a `\uXXXX` escape, combining UTF-16 surrogate pairs.
+    fn unicode_escape(&mut self) -> Option<char> {
+        let high = self.hex4()?;
+        match high {
+            0xd800..=0xdbff => {
+                if self.bytes.get(self.pos..self.pos + 2)? != b"\\u" {
+                    return None;
+                }
+                self.pos += 2;
+                let low = self.hex4()?;
+                if !(0xdc00..=0xdfff).contains(&low) {
+                    return None;
+                }
+                char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))
+            }
+            _ => char::from_u32(high),
+        }
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = std::str::from_utf8(self.bytes.get(self.pos..self.pos + 4)?).ok()?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        self.pos += 4;
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    /// Parses a number following the JSON grammar `-?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?`.
+    fn number(&mut self) -> Option<f64> {
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let start = parser.pos;
+            while parser.bytes.get(parser.pos).is_some_and(u8::is_ascii_digit) {
+                parser.pos += 1;
+            }
+            parser.pos - start
+        };
+
+        if self.bytes.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        match self.bytes.get(self.pos)? {
+            b'0' => self.pos += 1,
+            b'1'..=b'9' => {
+                digits(self);
+            }
+            _ => return None,
+        }
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            if digits(self) == 0 {
+                return None;
+            }
+        }
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                return None;
+            }
+        }
+
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()?
+            .parse()
+            .ok()
+    }
+}
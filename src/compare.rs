@@ -0,0 +1,184 @@
+use crate::canvas::Canvas;
+use crate::color::RGB;
+use crate::error::CanvasError;
+
+/// Summary of the differences between two equally sized canvases, e.g. for rendering regression tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffReport {
+    /// Number of pixels differing in at least one channel.
+    pub differing_pixels: usize,
+    /// Largest absolute difference of a single channel.
+    pub max_delta: u8,
+    /// Total number of pixels compared.
+    pub total_pixels: usize,
+}
+
+impl DiffReport {
+    /// Checks if the canvases are exactly equal.
+    pub fn is_identical(&self) -> bool {
+        self.differing_pixels == 0
+    }
+
+    /// Returns the fraction of pixels differing between the canvases.
+    pub fn differing_ratio(&self) -> f64 {
+        match self.total_pixels {
+            0 => 0.0,
+            total => self.differing_pixels as f64 / total as f64,
+        }
+    }
+}
+
+impl Canvas {
+    /// Compares the canvas with another one pixel by pixel.
+    ///
+    /// Returns [`CanvasError::SizeMismatch`] if the canvases are not the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let expected = Canvas::new(WIDTH, HEIGHT);
+    /// let mut actual = Canvas::new(WIDTH, HEIGHT);
+    /// actual.set_at(200, 100, RGB { r: 3, g: 0, b: 0 });
+    ///
+    /// let report = actual.diff(&expected).unwrap();
+    ///
+    /// assert_eq!(1, report.differing_pixels);
+    /// assert_eq!(3, report.max_delta);
+    /// ```
+    pub fn diff(&self, other: &Canvas) -> Result<DiffReport, CanvasError> {
+        self.check_same_size(other)?;
+
+        let mut report = DiffReport {
+            differing_pixels: 0,
+            max_delta: 0,
+            total_pixels: self.buffer().len(),
+        };
+        for (a, b) in self.buffer().iter().zip(other.buffer()) {
+            let delta = channel_delta(*a, *b);
+            if delta > 0 {
+                report.differing_pixels += 1;
+                report.max_delta = report.max_delta.max(delta);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Creates a canvas visualizing the differences to another canvas.
+    ///
+    /// Equal pixels are shown as a dimmed grayscale copy of the canvas,
+    /// differing pixels are highlighted in red which gets brighter the larger the difference.
+    ///
+    /// Returns [`CanvasError::SizeMismatch`] if the canvases are not the same size.
+    pub fn diff_canvas(&self, other: &Canvas) -> Result<Canvas, CanvasError> {
+        self.check_same_size(other)?;
+
+        let mut output = self.clone();
+        for (pixel, b) in output.buffer_mut().iter_mut().zip(other.buffer()) {
+            let delta = channel_delta(*pixel, *b);
+            *pixel = match delta {
+                0 => {
+                    let gray = ((pixel.r as u32 + pixel.g as u32 + pixel.b as u32) / 12) as u8;
+                    RGB {
+                        r: gray,
+                        g: gray,
+                        b: gray,
+                    }
+                }
+                _ => RGB {
+                    r: 128 + delta / 2,
+                    g: 0,
+                    b: 0,
+                },
+            };
+        }
+        Ok(output)
+    }
+
+    /// Checks if the canvas equals another one with every channel differing by at most `tolerance`.
+    ///
+    /// Canvases of different sizes are never equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let expected = Canvas::new(WIDTH, HEIGHT);
+    /// let mut actual = Canvas::new(WIDTH, HEIGHT);
+    /// actual.fill(RGB { r: 1, g: 1, b: 1 });
+    ///
+    /// assert!(actual.approx_eq(&expected, 1));
+    /// assert!(!actual.approx_eq(&expected, 0));
+    /// ```
+    pub fn approx_eq(&self, other: &Canvas, tolerance: u8) -> bool {
+        self.width() == other.width()
+            && self.height() == other.height()
+            && self
+                .buffer()
+                .iter()
+                .zip(other.buffer())
+                .all(|(a, b)| channel_delta(*a, *b) <= tolerance)
+    }
+
+    /// Returns a 64-bit hash of the size and pixels of the canvas, e.g. for snapshot tests or skipping identical frames.
+    ///
+    /// The hash is stable across platforms and versions of the crate, so it can be stored.
+    /// It uses FNV-1a and is not suitable for anything security related.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// let previous = canvas.content_hash();
+    ///
+    /// canvas.draw_pixel(200, 100, WHITE);
+    ///
+    /// assert_ne!(previous, canvas.content_hash());
+    /// assert_ne!(Canvas::new(2, 1).content_hash(), Canvas::new(1, 2).content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let size = [self.width() as u64, self.height() as u64];
+        size.iter()
+            .flat_map(|v| v.to_le_bytes())
+            .chain(self.as_bytes().iter().copied())
+            .fold(OFFSET_BASIS, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(PRIME)
+            })
+    }
+
+    fn check_same_size(&self, other: &Canvas) -> Result<(), CanvasError> {
+        match self.width() == other.width() && self.height() == other.height() {
+            true => Ok(()),
+            false => Err(CanvasError::SizeMismatch {
+                expected: (self.width(), self.height()),
+                found: (other.width(), other.height()),
+            }),
+        }
+    }
+}
+
+/// Returns the largest absolute difference of a single channel.
+fn channel_delta(a: RGB, b: RGB) -> u8 {
+    a.r.abs_diff(b.r)
+        .max(a.g.abs_diff(b.g))
+        .max(a.b.abs_diff(b.b))
+}
@@ -0,0 +1,269 @@
+use crate::canvas::Canvas;
+use crate::color::RGB;
+use crate::error::CanvasError;
+
+/// Edge length of the square tiles a canvas is split into when computing a [`DeltaFrame`].
+pub const TILE_SIZE: usize = 32;
+
+const MAGIC: &[u8; 4] = b"DSD1";
+
+/// The changes between two frames of a canvas, e.g. for streaming a canvas to a remote viewer.
+///
+/// The canvas is split into tiles of [`TILE_SIZE`] pixels. Only tiles which changed are stored,
+/// each compressed using run-length encoding.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::delta::DeltaFrame;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// // renderer
+/// let previous = Canvas::new(WIDTH, HEIGHT);
+/// let mut canvas = previous.clone();
+/// canvas.draw_circle_solid(200, 100, 10, WHITE);
+///
+/// let bytes = canvas.delta_since(&previous).to_bytes();
+///
+/// // viewer
+/// let mut view = Canvas::new(WIDTH, HEIGHT);
+/// let delta = DeltaFrame::from_bytes(&bytes).unwrap();
+/// view.apply_delta(&delta).unwrap();
+///
+/// assert!(view.approx_eq(&canvas, 0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeltaFrame {
+    width: usize,
+    height: usize,
+
+    tiles: Vec<DeltaTile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DeltaTile {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+
+    /// Runs of equal pixels, each stored as the run length minus one followed by the color.
+    runs: Vec<u8>,
+}
+
+impl DeltaFrame {
+    /// Returns the width of the canvas the frame applies to.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas the frame applies to.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the number of changed tiles.
+    pub fn tile_count(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Checks if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Serializes the frame into bytes, e.g. for sending it over the network.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&(self.width as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.height as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.tiles.len() as u32).to_le_bytes());
+        for tile in &self.tiles {
+            for v in [tile.x, tile.y, tile.width, tile.height, tile.runs.len()] {
+                bytes.extend_from_slice(&(v as u32).to_le_bytes());
+            }
+            bytes.extend_from_slice(&tile.runs);
+        }
+        bytes
+    }
+
+    /// Deserializes a frame created by [`DeltaFrame::to_bytes`].
+    ///
+    /// Returns `None` if the bytes are not a valid frame or contain an empty tile.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::delta::DeltaFrame;
+    ///
+    /// let mut bytes = b"DSD1".to_vec();
+    /// // a 4x4 canvas with one tile of width 0
+    /// for v in [4u32, 4, 1, 0, 0, 0, 4, 0] {
+    ///     bytes.extend_from_slice(&v.to_le_bytes());
+    /// }
+    ///
+    /// assert!(DeltaFrame::from_bytes(&bytes).is_none());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = bytes.strip_prefix(MAGIC)?;
+        let width = read_u32(&mut reader)?;
+        let height = read_u32(&mut reader)?;
+        let count = read_u32(&mut reader)?;
+
+        let mut tiles = Vec::new();
+        for _ in 0..count {
+            let [x, y, width, height, len] = [(); 5].map(|_| read_u32(&mut reader));
+            let (width, height) = (width?, height?);
+            if width == 0 || height == 0 {
+                return None;
+            }
+            let runs = reader.get(..len?)?.to_vec();
+            reader = &reader[runs.len()..];
+            tiles.push(DeltaTile {
+                x: x?,
+                y: y?,
+                width,
+                height,
+                runs,
+            });
+        }
+
+        Some(Self {
+            width,
+            height,
+            tiles,
+        })
+    }
+}
+
+impl Canvas {
+    /// Computes the changes from a previous frame to the canvas.
+    ///
+    /// If the previous frame has a different size every tile is stored.
+    pub fn delta_since(&self, previous: &Canvas) -> DeltaFrame {
+        let same_size = self.width() == previous.width() && self.height() == previous.height();
+
+        let mut tiles = Vec::new();
+        for y in (0..self.height()).step_by(TILE_SIZE) {
+            for x in (0..self.width()).step_by(TILE_SIZE) {
+                let w = TILE_SIZE.min(self.width() - x);
+                let h = TILE_SIZE.min(self.height() - y);
+                let rows = |canvas: &Canvas| {
+                    let width = canvas.width();
+                    (y..y + h).map(move |row| row * width + x..row * width + x + w)
+                };
+
+                let changed = !same_size
+                    || rows(self)
+                        .any(|range| self.buffer()[range.clone()] != previous.buffer()[range]);
+                if !changed {
+                    continue;
+                }
+
+                let pixels = rows(self).flat_map(|range| &self.buffer()[range]);
+                tiles.push(DeltaTile {
+                    x,
+                    y,
+                    width: w,
+                    height: h,
+                    runs: encode_runs(pixels),
+                });
+            }
+        }
+
+        DeltaFrame {
+            width: self.width(),
+            height: self.height(),
+            tiles,
+        }
+    }
+
+    /// Applies the changes of a [`DeltaFrame`] to the canvas.
+    ///
+    /// Returns [`CanvasError::SizeMismatch`] if the frame was computed for a canvas of a different size
+    /// and [`CanvasError::OutOfBounds`] if a tile lays outside of the canvas or is corrupted.
+    pub fn apply_delta(&mut self, delta: &DeltaFrame) -> Result<(), CanvasError> {
+        if self.width() != delta.width || self.height() != delta.height {
+            return Err(CanvasError::SizeMismatch {
+                expected: (self.width(), self.height()),
+                found: (delta.width, delta.height),
+            });
+        }
+
+        let width = self.width();
+        for tile in &delta.tiles {
+            let out_of_bounds = CanvasError::OutOfBounds {
+                x: tile.x as isize,
+                y: tile.y as isize,
+                width: self.width(),
+                height: self.height(),
+            };
+            if tile.x + tile.width > self.width() || tile.y + tile.height > self.height() {
+                return Err(out_of_bounds);
+            }
+
+            if run_length(&tile.runs) != Some(tile.width * tile.height) {
+                return Err(out_of_bounds);
+            }
+            let pixels = decode_runs(&tile.runs);
+
+            let buffer = self.buffer_mut();
+            for (row, src) in pixels.chunks(tile.width).enumerate() {
+                let start = (tile.y + row) * width + tile.x;
+                buffer[start..start + tile.width].copy_from_slice(src);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_u32(reader: &mut &[u8]) -> Option<usize> {
+    let (v, rest) = reader.split_first_chunk::<4>()?;
+    *reader = rest;
+    Some(u32::from_le_bytes(*v) as usize)
+}
+
+fn encode_runs<'a>(pixels: impl Iterator<Item = &'a RGB>) -> Vec<u8> {
+    let mut runs = Vec::new();
+    let mut current: Option<(RGB, u8)> = None;
+    for &pixel in pixels {
+        current = match current {
+            Some((color, len)) if color == pixel && len < u8::MAX => Some((color, len + 1)),
+            Some((color, len)) => {
+                runs.extend_from_slice(&[len, color.r, color.g, color.b]);
+                Some((pixel, 0))
+            }
+            None => Some((pixel, 0)),
+        };
+    }
+    if let Some((color, len)) = current {
+        runs.extend_from_slice(&[len, color.r, color.g, color.b]);
+    }
+    runs
+}
+
+/// Returns the number of pixels the runs expand to, or `None` if a run is incomplete.
+fn run_length(runs: &[u8]) -> Option<usize> {
+    match runs.len() % 4 {
+        0 => Some(runs.chunks_exact(4).map(|run| run[0] as usize + 1).sum()),
+        _ => None,
+    }
+}
+
+fn decode_runs(runs: &[u8]) -> Vec<RGB> {
+    runs.chunks_exact(4)
+        .flat_map(|run| {
+            let color = RGB {
+                r: run[1],
+                g: run[2],
+                b: run[3],
+            };
+            std::iter::repeat_n(color, run[0] as usize + 1)
+        })
+        .collect()
+}
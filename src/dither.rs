@@ -0,0 +1,343 @@
+use crate::canvas::Canvas;
+use crate::color::RGB;
+use crate::indexed::{nearest_index, IndexedCanvas};
+use crate::random::Rng;
+
+/// A square matrix of thresholds in `0.0..1.0` used for ordered dithering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdMatrix {
+    size: usize,
+    values: Vec<f32>,
+}
+
+impl ThresholdMatrix {
+    /// Creates a threshold matrix from ranks `0..size * size` stored row by row.
+    ///
+    /// Returns `None` if the number of ranks does not match the size.
+    pub fn from_ranks(size: usize, ranks: &[usize]) -> Option<Self> {
+        let n = size * size;
+        if n == 0 || ranks.len() != n {
+            return None;
+        }
+
+        Some(Self {
+            size,
+            values: ranks.iter().map(|&r| (r as f32 + 0.5) / n as f32).collect(),
+        })
+    }
+
+    /// Creates a Bayer matrix of size `2^order`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::dither::ThresholdMatrix;
+    ///
+    /// let bayer = ThresholdMatrix::bayer(2);
+    /// assert_eq!(4, bayer.size());
+    /// ```
+    pub fn bayer(order: u32) -> Self {
+        let mut ranks = vec![0usize];
+        let mut size = 1;
+
+        for _ in 0..order {
+            let next_size = size * 2;
+            let mut next = vec![0; next_size * next_size];
+            for y in 0..size {
+                for x in 0..size {
+                    let r = 4 * ranks[y * size + x];
+                    next[y * next_size + x] = r;
+                    next[y * next_size + x + size] = r + 2;
+                    next[(y + size) * next_size + x] = r + 3;
+                    next[(y + size) * next_size + x + size] = r + 1;
+                }
+            }
+            ranks = next;
+            size = next_size;
+        }
+
+        Self::from_ranks(size, &ranks).unwrap()
+    }
+
+    /// Generates a blue-noise matrix of the specified size using the void-and-cluster algorithm.
+    ///
+    /// Blue noise has no low frequency structure, so dithering with it looks like fine grain
+    /// instead of the cross-hatch pattern of Bayer matrices.
+    /// Generation takes `O(size^4)` time, so sizes up to 64 are reasonable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::dither::ThresholdMatrix;
+    ///
+    /// let blue_noise = ThresholdMatrix::blue_noise(16, 42);
+    /// assert_eq!(16, blue_noise.size());
+    /// ```
+    pub fn blue_noise(size: usize, seed: u64) -> Self {
+        let n = size * size;
+        if n <= 1 {
+            return Self::from_ranks(size.max(1), &[0]).unwrap();
+        }
+
+        let field = EnergyField::new(size, 1.5);
+        let mut rng = Rng::new(seed);
+
+        // random initial pattern of about a tenth of the points
+        let ones = (n / 10).max(1);
+        let mut pattern = vec![false; n];
+        let mut placed = 0;
+        while placed < ones {
+            let i = rng.below(n);
+            if !pattern[i] {
+                pattern[i] = true;
+                placed += 1;
+            }
+        }
+
+        // evenly distribute the initial points by moving the tightest cluster into the largest void
+        let mut energy = field.energy_of(&pattern);
+        loop {
+            let cluster = field.extreme(&energy, &pattern, true);
+            pattern[cluster] = false;
+            field.toggle(&mut energy, cluster, false);
+
+            let void = field.extreme(&energy, &pattern, false);
+            pattern[void] = true;
+            field.toggle(&mut energy, void, true);
+
+            if void == cluster {
+                break;
+            }
+        }
+
+        let mut ranks = vec![0usize; n];
+
+        // rank the initial points by repeatedly removing the tightest cluster
+        let mut prototype = pattern.clone();
+        let mut prototype_energy = energy.clone();
+        for rank in (0..ones).rev() {
+            let cluster = field.extreme(&prototype_energy, &prototype, true);
+            prototype[cluster] = false;
+            field.toggle(&mut prototype_energy, cluster, false);
+            ranks[cluster] = rank;
+        }
+
+        // rank the remaining points by repeatedly filling the largest void
+        for rank in ones..n {
+            let void = field.extreme(&energy, &pattern, false);
+            pattern[void] = true;
+            field.toggle(&mut energy, void, true);
+            ranks[void] = rank;
+        }
+
+        Self::from_ranks(size, &ranks).unwrap()
+    }
+
+    /// Returns the width and height of the matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the threshold at the specified position, repeating the matrix endlessly.
+    pub fn threshold(&self, x: usize, y: usize) -> f32 {
+        self.values[(y % self.size) * self.size + x % self.size]
+    }
+}
+
+/// Toroidal gaussian energy used by the void-and-cluster algorithm.
+struct EnergyField {
+    size: usize,
+    kernel: Vec<f32>,
+}
+
+impl EnergyField {
+    fn new(size: usize, sigma: f32) -> Self {
+        let mut kernel = vec![0.0; size * size];
+        for dy in 0..size {
+            for dx in 0..size {
+                // shortest distance on the torus
+                let x = dx.min(size - dx) as f32;
+                let y = dy.min(size - dy) as f32;
+                kernel[dy * size + dx] = (-(x * x + y * y) / (2.0 * sigma * sigma)).exp();
+            }
+        }
+
+        Self { size, kernel }
+    }
+
+    fn energy_of(&self, pattern: &[bool]) -> Vec<f32> {
+        let mut energy = vec![0.0; pattern.len()];
+        for (i, _) in pattern.iter().enumerate().filter(|(_, &p)| p) {
+            self.toggle(&mut energy, i, true);
+        }
+        energy
+    }
+
+    /// Adds or removes the energy contributed by a point.
+    fn toggle(&self, energy: &mut [f32], index: usize, add: bool) {
+        let (px, py) = (index % self.size, index / self.size);
+        let sign = if add { 1.0 } else { -1.0 };
+
+        for y in 0..self.size {
+            let dy = (y + self.size - py) % self.size;
+            for x in 0..self.size {
+                let dx = (x + self.size - px) % self.size;
+                energy[y * self.size + x] += sign * self.kernel[dy * self.size + dx];
+            }
+        }
+    }
+
+    /// Returns the tightest cluster (highest energy set point) or the largest void (lowest energy unset point).
+    fn extreme(&self, energy: &[f32], pattern: &[bool], cluster: bool) -> usize {
+        let candidates = energy
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| pattern[*i] == cluster);
+
+        let best = match cluster {
+            true => candidates.max_by(|a, b| a.1.total_cmp(b.1)),
+            false => candidates.min_by(|a, b| a.1.total_cmp(b.1)),
+        };
+
+        best.map(|(i, _)| i).unwrap_or(0)
+    }
+}
+
+impl Canvas {
+    /// Reduces every channel of the canvas to the specified number of levels using ordered dithering.
+    ///
+    /// Does nothing if less than two levels are specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    /// use drawing_stuff::dither::ThresholdMatrix;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 128, g: 128, b: 128 });
+    ///
+    /// let matrix = ThresholdMatrix::blue_noise(16, 42);
+    /// canvas.dither_ordered(&matrix, 2);
+    ///
+    /// let pixel = canvas.get_at(200, 100).unwrap();
+    /// assert!(pixel.r == 0 || pixel.r == 255);
+    /// ```
+    pub fn dither_ordered(&mut self, matrix: &ThresholdMatrix, levels: u8) {
+        if levels < 2 {
+            return;
+        }
+
+        let width = self.width();
+        let step = 255.0 / (levels - 1) as f32;
+
+        for (y, row) in self.buffer_mut().chunks_mut(width.max(1)).enumerate() {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let threshold = matrix.threshold(x, y);
+                let quantize = |v: u8| {
+                    let q = v as f32 / step;
+                    let level = match q.fract() > threshold {
+                        true => q.ceil(),
+                        false => q.floor(),
+                    };
+                    (level * step).round() as u8
+                };
+
+                pixel.r = quantize(pixel.r);
+                pixel.g = quantize(pixel.g);
+                pixel.b = quantize(pixel.b);
+            }
+        }
+    }
+
+    /// Reduces the canvas to the colors of a palette using Floyd–Steinberg error diffusion.
+    ///
+    /// The quantization error of every pixel is spread onto its unprocessed neighbours,
+    /// rows are processed in alternating directions to avoid directional artifacts.
+    /// Call [`IndexedCanvas::to_canvas`] on the result to get a quantized true-color canvas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette holds more than 256 colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 64, g: 64, b: 64 });
+    ///
+    /// let palette = [RGB { r: 0, g: 0, b: 0 }, RGB { r: 255, g: 255, b: 255 }];
+    /// let indexed = canvas.dither_to_palette(&palette);
+    ///
+    /// // roughly a quarter of the pixels turn white
+    /// let white = indexed.buffer().iter().filter(|&&i| i == 1).count();
+    /// assert!((white as f32 / (WIDTH * HEIGHT) as f32 - 0.25).abs() < 0.01);
+    /// ```
+    pub fn dither_to_palette(&self, palette: &[RGB]) -> IndexedCanvas {
+        let width = self.width();
+        let mut indexed = IndexedCanvas::new(width, self.height(), palette.to_vec());
+        if palette.is_empty() {
+            return indexed;
+        }
+
+        // error rows padded by one pixel on both sides
+        let mut current = vec![[0.0f32; 3]; width + 2];
+        let mut next = vec![[0.0f32; 3]; width + 2];
+
+        let rows = self.buffer().chunks(width.max(1));
+        let indices = indexed.buffer_mut().chunks_mut(width.max(1));
+        for (y, (row, row_indices)) in rows.zip(indices).enumerate() {
+            let forward = y % 2 == 0;
+            let step: isize = match forward {
+                true => 1,
+                false => -1,
+            };
+
+            for i in 0..width {
+                let x = match forward {
+                    true => i,
+                    false => width - 1 - i,
+                };
+                let pixel = row[x];
+                let error = current[x + 1];
+                let wanted = [
+                    pixel.r as f32 + error[0],
+                    pixel.g as f32 + error[1],
+                    pixel.b as f32 + error[2],
+                ];
+                let clamped = wanted.map(|v| v.round().clamp(0.0, 255.0) as u8);
+                let [r, g, b] = clamped;
+
+                let index = nearest_index(palette, RGB { r, g, b });
+                row_indices[x] = index;
+
+                let chosen = palette[index as usize];
+                let chosen = [chosen.r as f32, chosen.g as f32, chosen.b as f32];
+                let ahead = (x as isize + 1 + step) as usize;
+                let behind = (x as isize + 1 - step) as usize;
+                for c in 0..3 {
+                    let e = wanted[c] - chosen[c];
+                    current[ahead][c] += e * 7.0 / 16.0;
+                    next[behind][c] += e * 3.0 / 16.0;
+                    next[x + 1][c] += e * 5.0 / 16.0;
+                    next[ahead][c] += e / 16.0;
+                }
+            }
+
+            std::mem::swap(&mut current, &mut next);
+            next.fill([0.0; 3]);
+        }
+
+        indexed
+    }
+}
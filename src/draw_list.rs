@@ -0,0 +1,75 @@
+use crate::canvas::{Canvas, Draw};
+
+/// A list of drawables rendered in the order of their layers.
+///
+/// Drawables on lower layers are drawn first, so drawables on higher layers end up on top.
+/// Drawables on the same layer are drawn in the order they were added.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{RED, WHITE};
+/// use drawing_stuff::draw_list::DrawList;
+/// use drawing_stuff::drawables::Circle;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let mut list = DrawList::new();
+/// list.push(1, Circle { center: (200, 100), radius: 15, solid: true, color: RED });
+/// list.push(0, Circle { center: (200, 100), radius: 50, solid: true, color: WHITE });
+///
+/// list.render(&mut canvas);
+/// assert_eq!(0, canvas.get_at(200, 100).unwrap().g);
+/// ```
+#[derive(Default)]
+pub struct DrawList<'a> {
+    items: Vec<(i32, Box<dyn Draw + 'a>)>,
+}
+
+impl<'a> DrawList<'a> {
+    /// Creates a new empty draw list.
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Adds a drawable on the specified layer.
+    pub fn push<T>(&mut self, layer: i32, drawable: T)
+    where
+        T: Draw + 'a,
+    {
+        let index = self.items.partition_point(|(l, _)| *l <= layer);
+        self.items.insert(index, (layer, Box::new(drawable)));
+    }
+
+    /// Returns the number of drawables in the list.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Checks if the list contains no drawables.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Removes all drawables from the list.
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Draws all drawables of the list onto a [`Canvas`] ordered by their layers.
+    pub fn render(&self, canvas: &mut Canvas) {
+        for (_, drawable) in &self.items {
+            drawable.draw(canvas);
+        }
+    }
+}
+
+impl Draw for DrawList<'_> {
+    fn draw(&self, canvas: &mut Canvas) {
+        self.render(canvas);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::canvas::{Canvas, Draw};
+use crate::blur::blur_buffer;
+use crate::canvas::{Canvas, Draw, Rect};
 use crate::color::RGBA;
+use crate::mask::{rasterize_layer, rasterize_mask, Mask};
+use crate::target::PixelTarget;
+use crate::transform::Affine;
 
 #[derive(Debug)]
 pub enum AnkerType {
@@ -20,12 +24,22 @@ pub struct Line {
 
 impl Draw for Line {
     fn draw(&self, canvas: &mut Canvas) {
+        self.draw_onto(canvas);
+    }
+
+    fn draw_mask(&self, mask: &mut Mask) {
+        self.draw_onto(mask);
+    }
+}
+
+impl Line {
+    fn draw_onto<T: PixelTarget>(&self, target: &mut T) {
         if self.width == 0 {
             return;
         };
 
         if self.width == 1 {
-            canvas.draw_line(
+            target.draw_line(
                 self.end1.0,
                 self.end1.1,
                 self.end2.0,
@@ -36,7 +50,7 @@ impl Draw for Line {
         }
 
         match self.capped {
-            true => canvas.draw_polyline_capped(
+            true => target.draw_polyline_capped(
                 self.end1.0,
                 self.end1.1,
                 self.end2.0,
@@ -44,7 +58,7 @@ impl Draw for Line {
                 self.width,
                 self.color,
             ),
-            false => canvas.draw_polyline(
+            false => target.draw_polyline(
                 self.end1.0,
                 self.end1.1,
                 self.end2.0,
@@ -68,13 +82,26 @@ pub struct Circle {
 
 impl Draw for Circle {
     fn draw(&self, canvas: &mut Canvas) {
+        self.draw_onto(canvas);
+    }
+
+    fn draw_mask(&self, mask: &mut Mask) {
+        self.draw_onto(mask);
+    }
+}
+
+impl Circle {
+    fn draw_onto<T: PixelTarget>(&self, target: &mut T) {
         match self.solid {
-            true => canvas.draw_circle_solid(self.center.0, self.center.1, self.radius, self.color),
-            false => canvas.draw_circle(self.center.0, self.center.1, self.radius, self.color),
+            true => target.draw_circle_solid(self.center.0, self.center.1, self.radius, self.color),
+            false => target.draw_circle(self.center.0, self.center.1, self.radius, self.color),
         }
     }
 }
 
+/// An axis aligned square.
+///
+/// A solid square covers the same pixels as a solid [`Rectangle`] of the same width and height.
 #[derive(Debug)]
 pub struct Square {
     pub anker: (isize, isize),
@@ -88,6 +115,22 @@ pub struct Square {
 
 impl Draw for Square {
     fn draw(&self, canvas: &mut Canvas) {
+        self.draw_onto(canvas);
+    }
+
+    fn draw_mask(&self, mask: &mut Mask) {
+        self.draw_onto(mask);
+    }
+}
+
+impl Square {
+    fn draw_onto<T: PixelTarget>(&self, target: &mut T) {
+        if self.solid {
+            let rect = solid_rect(self.anker, self.length, self.length, &self.anker_type);
+            target.fill_rect(rect.x, rect.y, rect.width, rect.height, self.color);
+            return;
+        }
+
         let vertices = match self.anker_type {
             AnkerType::CENTER => vec![
                 (
@@ -118,13 +161,38 @@ impl Draw for Square {
             ],
         };
 
-        match self.solid {
-            true => canvas.draw_polygon_solid(&vertices, true, self.color),
-            false => canvas.draw_polygon(&vertices, self.color),
-        }
+        target.draw_polygon(&vertices, self.color);
     }
 }
 
+/// An axis aligned rectangle.
+///
+/// A solid rectangle covers the pixels of the polygon through the corners of its outline:
+/// the row of the bottom edge is included, the column of the right edge is not.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Draw};
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::drawables::{AnkerType, Rectangle};
+///
+/// let mut canvas = Canvas::new(16, 16);
+///
+/// let rectangle = Rectangle {
+///     anker: (1, 1),
+///     width: 5,
+///     height: 3,
+///     anker_type: AnkerType::CORNER,
+///     solid: true,
+///     color: WHITE,
+/// };
+/// rectangle.draw(&mut canvas);
+///
+/// assert_eq!(255, canvas.get_at(5, 4).unwrap().r);
+/// assert_eq!(0, canvas.get_at(6, 4).unwrap().r);
+/// assert_eq!(0, canvas.get_at(5, 5).unwrap().r);
+/// ```
 #[derive(Debug)]
 pub struct Rectangle {
     pub anker: (isize, isize),
@@ -139,6 +207,22 @@ pub struct Rectangle {
 
 impl Draw for Rectangle {
     fn draw(&self, canvas: &mut Canvas) {
+        self.draw_onto(canvas);
+    }
+
+    fn draw_mask(&self, mask: &mut Mask) {
+        self.draw_onto(mask);
+    }
+}
+
+impl Rectangle {
+    fn draw_onto<T: PixelTarget>(&self, target: &mut T) {
+        if self.solid {
+            let rect = solid_rect(self.anker, self.width, self.height, &self.anker_type);
+            target.fill_rect(rect.x, rect.y, rect.width, rect.height, self.color);
+            return;
+        }
+
         let vertices = match self.anker_type {
             AnkerType::CENTER => vec![
                 (
@@ -169,10 +253,30 @@ impl Draw for Rectangle {
             ],
         };
 
-        match self.solid {
-            true => canvas.draw_polygon_solid(&vertices, true, self.color),
-            false => canvas.draw_polygon(&vertices, self.color),
-        }
+        target.draw_polygon(&vertices, self.color);
+    }
+}
+
+/// Returns the pixels covered by a solid [`Square`] or [`Rectangle`].
+///
+/// Solid rectangles used to be filled as a polygon through the corners of their outline,
+/// which covers the row of the bottom edge but not the column of the right edge.
+/// The extents are kept, so solid and outlined shapes line up like they always did.
+fn solid_rect(anker: (isize, isize), width: u32, height: u32, anker_type: &AnkerType) -> Rect {
+    let (x, y, width, height) = match anker_type {
+        AnkerType::CENTER => (
+            anker.0 - width as isize / 2,
+            anker.1 - height as isize / 2,
+            2 * (width as usize / 2),
+            2 * (height as usize / 2),
+        ),
+        AnkerType::CORNER => (anker.0, anker.1, width as usize, height as usize),
+    };
+
+    // a polygon without any height covers no pixels
+    match height {
+        0 => Rect::new(x, y, 0, 0),
+        _ => Rect::new(x, y, width, height + 1),
     }
 }
 
@@ -188,9 +292,226 @@ pub struct Polygon {
 
 impl Draw for Polygon {
     fn draw(&self, canvas: &mut Canvas) {
+        self.draw_onto(canvas);
+    }
+
+    fn draw_mask(&self, mask: &mut Mask) {
+        self.draw_onto(mask);
+    }
+}
+
+impl Polygon {
+    fn draw_onto<T: PixelTarget>(&self, target: &mut T) {
+        match self.solid {
+            true => target.draw_polygon_solid(&self.vertices, self.clockwise, self.color),
+            false => target.draw_polygon(&self.vertices, self.color),
+        }
+    }
+}
+
+/// A polygon with sub-pixel vertices which get transformed and rounded only when drawn.
+///
+/// Animating the transform instead of the vertices keeps rotating or scaling polygons
+/// from accumulating rounding errors frame by frame.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::drawables::PolygonF;
+/// use drawing_stuff::transform::Affine;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let mut triangle = PolygonF {
+///     vertices: vec![(0.0, -50.0), (43.3, 25.0), (-43.3, 25.0)],
+///     transform: Affine::IDENTITY,
+///     clockwise: true,
+///     solid: true,
+///     color: WHITE,
+/// };
+///
+/// for frame in 0..60 {
+///     let angle = frame as f32 / 60.0 * std::f32::consts::TAU;
+///     triangle.transform = Affine::rotate(angle).then(Affine::translate(540.0, 360.0));
+///     canvas.draw(&triangle);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PolygonF {
+    pub vertices: Vec<(f32, f32)>,
+    pub transform: Affine,
+
+    pub clockwise: bool,
+    pub solid: bool,
+
+    pub color: RGBA,
+}
+
+impl PolygonF {
+    /// Returns the vertices with the transform applied, rounded to the closest pixels.
+    pub fn baked_vertices(&self) -> Vec<(isize, isize)> {
+        self.vertices
+            .iter()
+            .map(|&v| {
+                let (x, y) = self.transform.apply(v);
+                (x.round() as isize, y.round() as isize)
+            })
+            .collect()
+    }
+}
+
+impl Draw for PolygonF {
+    fn draw(&self, canvas: &mut Canvas) {
+        self.draw_onto(canvas);
+    }
+
+    fn draw_mask(&self, mask: &mut Mask) {
+        self.draw_onto(mask);
+    }
+}
+
+impl PolygonF {
+    fn draw_onto<T: PixelTarget>(&self, target: &mut T) {
+        let vertices = self.baked_vertices();
+
         match self.solid {
-            true => canvas.draw_polygon_solid(&self.vertices, self.clockwise, self.color),
-            false => canvas.draw_polygon(&self.vertices, self.color),
+            // mirroring transforms flip the winding order
+            true => {
+                let clockwise = self.clockwise == (self.transform.determinant() >= 0.0);
+                target.draw_polygon_solid(&vertices, clockwise, self.color)
+            }
+            false => target.draw_polygon(&vertices, self.color),
         }
     }
 }
+
+#[derive(Debug)]
+pub struct Sprite<'a> {
+    pub source: &'a Canvas,
+    pub region: Rect,
+
+    pub position: (isize, isize),
+}
+
+impl Draw for Sprite<'_> {
+    fn draw(&self, canvas: &mut Canvas) {
+        canvas.blit_region(self.source, self.region, self.position.0, self.position.1);
+    }
+}
+
+/// Draws another drawable with softened edges, e.g. for soft shapes and shadows.
+///
+/// The drawable is rasterized into a temporary layer the size of the canvas, which is blurred by `blur_radius` and composited onto the canvas.
+/// Parts of the drawable outside of the canvas don't contribute to the blur.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::drawables::{Blurred, Circle};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let shadow = Blurred {
+///     drawable: Circle { center: (200, 100), radius: 30, solid: true, color: WHITE },
+///     blur_radius: 8,
+/// };
+/// canvas.draw(&shadow);
+///
+/// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+/// let edge = canvas.get_at(200, 130).unwrap().r;
+/// assert!(edge > 0 && edge < 255);
+/// ```
+#[derive(Debug)]
+pub struct Blurred<T> {
+    pub drawable: T,
+
+    pub blur_radius: u32,
+}
+
+impl<T> Draw for Blurred<T>
+where
+    T: Draw,
+{
+    fn draw(&self, canvas: &mut Canvas) {
+        if self.blur_radius == 0 {
+            self.drawable.draw(canvas);
+            return;
+        }
+
+        let (width, height) = (canvas.width(), canvas.height());
+        let radius = self.blur_radius as usize;
+        let (mut colors, mut coverage) = rasterize_layer(width, height, &self.drawable);
+        coverage.blur(self.blur_radius);
+
+        // blurring the premultiplied colors keeps fully transparent pixels from bleeding black into the edges
+        blur_buffer(colors.buffer_mut(), width, height, radius);
+
+        for (i, pixel) in canvas.buffer_mut().iter_mut().enumerate() {
+            let premul = colors.buffer()[i];
+            *pixel = pixel.add_premultiplied(premul, coverage.data()[i]);
+        }
+    }
+}
+
+/// Draws another drawable on top of a soft drop shadow.
+///
+/// The coverage of the drawable is rasterized into a mask the size of the canvas, blurred by `blur_radius`
+/// and filled with `color` at `offset` before the drawable itself is drawn.
+/// Parts of the drawable outside of the canvas don't cast a shadow.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{RGB, RGBA, WHITE};
+/// use drawing_stuff::drawables::{Circle, WithShadow};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+/// canvas.fill(RGB { r: 200, g: 200, b: 200 });
+///
+/// let button = WithShadow {
+///     drawable: Circle { center: (200, 100), radius: 30, solid: true, color: WHITE },
+///     offset: (6, 6),
+///     blur_radius: 4,
+///     color: RGBA { r: 0, g: 0, b: 0, a: 128 },
+/// };
+/// canvas.draw(&button);
+///
+/// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+/// assert!(canvas.get_at(225, 125).unwrap().r < 200);
+/// assert_eq!(200, canvas.get_at(175, 75).unwrap().r);
+/// ```
+#[derive(Debug)]
+pub struct WithShadow<T> {
+    pub drawable: T,
+
+    pub offset: (isize, isize),
+    pub blur_radius: u32,
+    pub color: RGBA,
+}
+
+impl<T> Draw for WithShadow<T>
+where
+    T: Draw,
+{
+    fn draw(&self, canvas: &mut Canvas) {
+        let mut shadow = rasterize_mask(canvas.width(), canvas.height(), &self.drawable);
+        shadow.blur(self.blur_radius);
+        canvas.fill_mask(&shadow, self.offset.0, self.offset.1, self.color);
+
+        self.drawable.draw(canvas);
+    }
+}
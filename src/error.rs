@@ -0,0 +1,75 @@
+use std::error::Error;
+use std::fmt;
+
+/// Describes why an operation on a canvas failed.
+///
+/// Returned by the `try_*` variants of canvas operations, which otherwise only report failure with `None`.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::error::CanvasError;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// let result = canvas.try_draw_pixel(-1, 100, WHITE);
+///
+/// assert_eq!(
+///     Err(CanvasError::OutOfBounds { x: -1, y: 100, width: WIDTH, height: HEIGHT }),
+///     result
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanvasError {
+    /// A position lays outside of the canvas.
+    OutOfBounds {
+        x: isize,
+        y: isize,
+        width: usize,
+        height: usize,
+    },
+    /// A canvas with a width or height of zero was requested.
+    ZeroSize,
+    /// Two canvases were expected to have the same size.
+    SizeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// A buffer provided is too small to hold the pixels of the canvas.
+    BufferTooSmall { required: usize, found: usize },
+    /// A shape can not be drawn from the geometry given.
+    InvalidGeometry(&'static str),
+}
+
+impl fmt::Display for CanvasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanvasError::OutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "position ({x}, {y}) is outside of the {width}x{height} canvas"
+            ),
+            CanvasError::ZeroSize => write!(f, "canvas has a width or height of zero"),
+            CanvasError::SizeMismatch { expected, found } => write!(
+                f,
+                "expected a {}x{} canvas but found a {}x{} canvas",
+                expected.0, expected.1, found.0, found.1
+            ),
+            CanvasError::BufferTooSmall { required, found } => {
+                write!(f, "buffer holds {found} pixels but {required} are required")
+            }
+            CanvasError::InvalidGeometry(reason) => write!(f, "invalid geometry: {reason}"),
+        }
+    }
+}
+
+impl Error for CanvasError {}
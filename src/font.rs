@@ -0,0 +1,459 @@
+//! A built-in single-stroke font whose glyphs are available as [`Path`]s.
+//!
+//! Every glyph is made of strokes through points on a small grid, so text can be stroked with any width,
+//! repeated along other paths or exported as vectors instead of only being rasterized.
+//!
+//! # Examples
+//!
+//! ```
+//! use drawing_stuff::canvas::Canvas;
+//! use drawing_stuff::color::WHITE;
+//! use drawing_stuff::font::StrokeFont;
+//!
+//! const WIDTH: usize = 1080;
+//! const HEIGHT: usize = 720;
+//!
+//! let mut canvas = Canvas::new(WIDTH, HEIGHT);
+//!
+//! for path in StrokeFont.text_paths("HELLO 42", (100.0, 100.0), 60.0) {
+//!     path.stroke(&mut canvas, 4, WHITE);
+//! }
+//!
+//! // the left stem of the H
+//! assert_eq!(255, canvas.get_at(100, 130).unwrap().r);
+//! ```
+
+use crate::canvas::{AddressMode, Canvas};
+use crate::color::{RGB, RGBA, TRANSPARANT, WHITE};
+use crate::gradient::Gradient;
+use crate::mask::Mask;
+use crate::path::Path;
+
+/// Height of capital letters in grid units, the baseline lays at this height.
+const CAP_HEIGHT: f32 = 6.0;
+/// Horizontal distance between the origins of two glyphs in grid units.
+const ADVANCE: f32 = 6.0;
+/// Vertical distance between two lines of text in grid units.
+const LINE_HEIGHT: f32 = 10.0;
+
+type Strokes = &'static [&'static [(u8, u8)]];
+
+/// The built-in single-stroke font.
+///
+/// Glyphs are drawn on a grid 4 units wide and 6 units high with the origin at the top-left corner.
+/// Digits, latin letters and common punctuation are included, lowercase letters use the uppercase glyphs
+/// and all other characters are replaced by a question mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StrokeFont;
+
+impl StrokeFont {
+    /// Returns the strokes of a glyph scaled to the specified height of capital letters in pixels.
+    ///
+    /// The origin of the glyph is its top-left corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::font::StrokeFont;
+    ///
+    /// let paths = StrokeFont.glyph_path('T', 12.0);
+    ///
+    /// assert_eq!(2, paths.len());
+    /// assert_eq!(vec![(0.0, 0.0), (8.0, 0.0)], paths[0].points);
+    /// assert!(StrokeFont.glyph_path(' ', 12.0).is_empty());
+    /// ```
+    pub fn glyph_path(&self, ch: char, size: f32) -> Vec<Path> {
+        let scale = size / CAP_HEIGHT;
+        glyph_strokes(ch)
+            .iter()
+            .map(|stroke| {
+                Path::new(
+                    stroke
+                        .iter()
+                        .map(|&(x, y)| (x as f32 * scale, y as f32 * scale))
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the horizontal distance between two glyphs with the specified height of capital letters in pixels.
+    pub fn advance(&self, size: f32) -> f32 {
+        ADVANCE * size / CAP_HEIGHT
+    }
+
+    /// Returns the strokes of a whole text with the top-left corner of its first glyph at `origin`.
+    ///
+    /// Every `'\n'` starts a new line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::font::StrokeFont;
+    ///
+    /// let paths = StrokeFont.text_paths("I\nL", (10.0, 10.0), 6.0);
+    ///
+    /// // the stem of the L starts one line below the I
+    /// assert_eq!(vec![(10.0, 20.0), (10.0, 26.0), (14.0, 26.0)], paths[3].points);
+    /// ```
+    pub fn text_paths(&self, text: &str, origin: (f32, f32), size: f32) -> Vec<Path> {
+        let scale = size / CAP_HEIGHT;
+        let mut paths = Vec::new();
+        for (line, text) in text.lines().enumerate() {
+            let y = origin.1 + line as f32 * LINE_HEIGHT * scale;
+            for (i, ch) in text.chars().enumerate() {
+                let x = origin.0 + i as f32 * ADVANCE * scale;
+                paths.extend(self.glyph_path(ch, size).into_iter().map(|mut path| {
+                    for point in path.points.iter_mut() {
+                        *point = (point.0 + x, point.1 + y);
+                    }
+                    path
+                }));
+            }
+        }
+        paths
+    }
+
+    /// Strokes a text with the specified width onto a canvas, filling the strokes with a color, gradient or image.
+    ///
+    /// The top-left corner of the first glyph lays at `origin`, see [`StrokeFont::text_paths`].
+    /// The strokes are rasterized into a [`Mask`] first, so overlapping strokes are filled once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::font::{StrokeFont, TextFill};
+    /// use drawing_stuff::gradient::Gradient;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// // fades from red at the top to blue at the baseline
+    /// let gradient = Gradient::new(&[
+    ///     (0.0, RGBA::new(255, 0, 0, 255)),
+    ///     (1.0, RGBA::new(0, 0, 255, 255)),
+    /// ]);
+    /// let fill = TextFill::Gradient {
+    ///     gradient: &gradient,
+    ///     from: (0.0, 100.0),
+    ///     to: (0.0, 160.0),
+    /// };
+    /// StrokeFont.draw_text(&mut canvas, "HELLO", (100.0, 100.0), 60.0, 6, &fill);
+    ///
+    /// // the left stem of the H
+    /// let top = canvas.get_at(100, 102).unwrap();
+    /// let bottom = canvas.get_at(100, 158).unwrap();
+    /// assert!(top.r > 200 && top.b < 50);
+    /// assert!(bottom.b > 200 && bottom.r < 50);
+    /// ```
+    pub fn draw_text(
+        &self,
+        canvas: &mut Canvas,
+        text: &str,
+        origin: (f32, f32),
+        size: f32,
+        width: u32,
+        fill: &TextFill,
+    ) {
+        let mut paths = self.text_paths(text, origin, size);
+
+        let pad = width as f32 / 2.0 + 1.0;
+        let (mut min_x, mut min_y) = (f32::INFINITY, f32::INFINITY);
+        let (mut max_x, mut max_y) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for &(x, y) in paths.iter().flat_map(|path| &path.points) {
+            if x.is_finite() && y.is_finite() {
+                (min_x, max_x) = (min_x.min(x), max_x.max(x));
+                (min_y, max_y) = (min_y.min(y), max_y.max(y));
+            }
+        }
+
+        // only the part of the text on the canvas is rasterized
+        let x1 = (min_x - pad).floor().max(0.0);
+        let y1 = (min_y - pad).floor().max(0.0);
+        let x2 = (max_x + pad).ceil().min(canvas.width() as f32);
+        let y2 = (max_y + pad).ceil().min(canvas.height() as f32);
+        if x1 >= x2 || y1 >= y2 {
+            return;
+        }
+
+        let mut mask = Mask::new((x2 - x1) as usize, (y2 - y1) as usize);
+        for path in &mut paths {
+            for point in path.points.iter_mut() {
+                *point = (point.0 - x1, point.1 - y1);
+            }
+            path.stroke(&mut mask, width, WHITE);
+        }
+        canvas.fill_mask_with(&mask, x1 as isize, y1 as isize, |x, y| fill.color_at(x, y));
+    }
+}
+
+/// How the strokes of a text drawn with [`StrokeFont::draw_text`] are filled.
+#[derive(Debug, Clone, Copy)]
+pub enum TextFill<'a> {
+    /// A single color.
+    Solid(RGBA),
+    /// A gradient running from `from` to `to`, given in pixels of the canvas.
+    ///
+    /// Every pixel samples the gradient at its position projected onto the line from `from` to `to`.
+    Gradient {
+        gradient: &'a Gradient,
+        from: (f32, f32),
+        to: (f32, f32),
+    },
+    /// An image with its top-left corner at `origin` on the canvas.
+    ///
+    /// Parts of the text outside of the image are resolved using `mode`.
+    Image {
+        image: &'a Canvas,
+        origin: (isize, isize),
+        mode: AddressMode,
+    },
+}
+
+impl TextFill<'_> {
+    /// Returns the color of the fill at the specified pixel of the canvas.
+    fn color_at(&self, x: usize, y: usize) -> RGBA {
+        match *self {
+            TextFill::Solid(color) => color,
+            TextFill::Gradient { gradient, from, to } => {
+                let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+                let length = dx * dx + dy * dy;
+                let (px, py) = (x as f32 + 0.5 - from.0, y as f32 + 0.5 - from.1);
+                match length > 0.0 {
+                    true => gradient.sample((px * dx + py * dy) / length),
+                    false => gradient.sample(0.0),
+                }
+            }
+            TextFill::Image {
+                image,
+                origin,
+                mode,
+            } => {
+                let sx = mode.resolve((x as isize).saturating_sub(origin.0), image.width());
+                let sy = mode.resolve((y as isize).saturating_sub(origin.1), image.height());
+                match sx.zip(sy) {
+                    Some((sx, sy)) => {
+                        let RGB { r, g, b } = image.buffer()[sy * image.width() + sx];
+                        RGBA { r, g, b, a: 255 }
+                    }
+                    None => TRANSPARANT,
+                }
+            }
+        }
+    }
+}
+
+/// Returns the strokes of a glyph on the grid.
+fn glyph_strokes(ch: char) -> Strokes {
+    match ch.to_ascii_uppercase() {
+        ' ' => &[],
+        'A' => &[&[(0, 6), (0, 2), (2, 0), (4, 2), (4, 6)], &[(0, 4), (4, 4)]],
+        'B' => &[
+            &[(0, 6), (0, 0), (3, 0), (4, 1), (4, 2), (3, 3), (0, 3)],
+            &[(3, 3), (4, 4), (4, 5), (3, 6), (0, 6)],
+        ],
+        'C' => &[&[
+            (4, 1),
+            (3, 0),
+            (1, 0),
+            (0, 1),
+            (0, 5),
+            (1, 6),
+            (3, 6),
+            (4, 5),
+        ]],
+        'D' => &[&[(0, 0), (0, 6), (2, 6), (4, 4), (4, 2), (2, 0), (0, 0)]],
+        'E' => &[&[(4, 0), (0, 0), (0, 6), (4, 6)], &[(0, 3), (3, 3)]],
+        'F' => &[&[(4, 0), (0, 0), (0, 6)], &[(0, 3), (3, 3)]],
+        'G' => &[&[
+            (4, 1),
+            (3, 0),
+            (1, 0),
+            (0, 1),
+            (0, 5),
+            (1, 6),
+            (3, 6),
+            (4, 5),
+            (4, 3),
+            (2, 3),
+        ]],
+        'H' => &[&[(0, 0), (0, 6)], &[(4, 0), (4, 6)], &[(0, 3), (4, 3)]],
+        'I' => &[&[(1, 0), (3, 0)], &[(2, 0), (2, 6)], &[(1, 6), (3, 6)]],
+        'J' => &[&[(4, 0), (4, 5), (3, 6), (1, 6), (0, 5)]],
+        'K' => &[&[(0, 0), (0, 6)], &[(4, 0), (0, 4)], &[(1, 3), (4, 6)]],
+        'L' => &[&[(0, 0), (0, 6), (4, 6)]],
+        'M' => &[&[(0, 6), (0, 0), (2, 3), (4, 0), (4, 6)]],
+        'N' => &[&[(0, 6), (0, 0), (4, 6), (4, 0)]],
+        'O' => &[&[
+            (1, 0),
+            (3, 0),
+            (4, 1),
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+            (0, 1),
+            (1, 0),
+        ]],
+        'P' => &[&[(0, 6), (0, 0), (3, 0), (4, 1), (4, 2), (3, 3), (0, 3)]],
+        'Q' => &[
+            &[
+                (1, 0),
+                (3, 0),
+                (4, 1),
+                (4, 5),
+                (3, 6),
+                (1, 6),
+                (0, 5),
+                (0, 1),
+                (1, 0),
+            ],
+            &[(2, 4), (4, 6)],
+        ],
+        'R' => &[
+            &[(0, 6), (0, 0), (3, 0), (4, 1), (4, 2), (3, 3), (0, 3)],
+            &[(2, 3), (4, 6)],
+        ],
+        'S' => &[&[
+            (4, 1),
+            (3, 0),
+            (1, 0),
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (3, 3),
+            (4, 4),
+            (4, 5),
+            (3, 6),
+            (1, 6),
+            (0, 5),
+        ]],
+        'T' => &[&[(0, 0), (4, 0)], &[(2, 0), (2, 6)]],
+        'U' => &[&[(0, 0), (0, 5), (1, 6), (3, 6), (4, 5), (4, 0)]],
+        'V' => &[&[(0, 0), (2, 6), (4, 0)]],
+        'W' => &[&[(0, 0), (1, 6), (2, 3), (3, 6), (4, 0)]],
+        'X' => &[&[(0, 0), (4, 6)], &[(4, 0), (0, 6)]],
+        'Y' => &[&[(0, 0), (2, 3), (4, 0)], &[(2, 3), (2, 6)]],
+        'Z' => &[&[(0, 0), (4, 0), (0, 6), (4, 6)]],
+        '0' => &[
+            &[
+                (1, 0),
+                (3, 0),
+                (4, 1),
+                (4, 5),
+                (3, 6),
+                (1, 6),
+                (0, 5),
+                (0, 1),
+                (1, 0),
+            ],
+            &[(4, 1), (0, 5)],
+        ],
+        '1' => &[&[(1, 1), (2, 0), (2, 6)], &[(1, 6), (3, 6)]],
+        '2' => &[&[(0, 1), (1, 0), (3, 0), (4, 1), (4, 2), (0, 6), (4, 6)]],
+        '3' => &[
+            &[
+                (0, 1),
+                (1, 0),
+                (3, 0),
+                (4, 1),
+                (4, 2),
+                (3, 3),
+                (4, 4),
+                (4, 5),
+                (3, 6),
+                (1, 6),
+                (0, 5),
+            ],
+            &[(1, 3), (3, 3)],
+        ],
+        '4' => &[&[(3, 6), (3, 0), (0, 4), (4, 4)]],
+        '5' => &[&[
+            (4, 0),
+            (0, 0),
+            (0, 3),
+            (3, 3),
+            (4, 4),
+            (4, 5),
+            (3, 6),
+            (0, 6),
+        ]],
+        '6' => &[&[
+            (3, 0),
+            (1, 0),
+            (0, 1),
+            (0, 5),
+            (1, 6),
+            (3, 6),
+            (4, 5),
+            (4, 4),
+            (3, 3),
+            (0, 3),
+        ]],
+        '7' => &[&[(0, 0), (4, 0), (1, 6)]],
+        '8' => &[
+            &[
+                (1, 0),
+                (3, 0),
+                (4, 1),
+                (4, 2),
+                (3, 3),
+                (1, 3),
+                (0, 2),
+                (0, 1),
+                (1, 0),
+            ],
+            &[
+                (1, 3),
+                (0, 4),
+                (0, 5),
+                (1, 6),
+                (3, 6),
+                (4, 5),
+                (4, 4),
+                (3, 3),
+            ],
+        ],
+        '9' => &[&[
+            (4, 3),
+            (1, 3),
+            (0, 2),
+            (0, 1),
+            (1, 0),
+            (3, 0),
+            (4, 1),
+            (4, 5),
+            (3, 6),
+            (1, 6),
+        ]],
+        '.' => &[&[(2, 5), (2, 6)]],
+        ',' => &[&[(2, 5), (1, 7)]],
+        ':' => &[&[(2, 1), (2, 2)], &[(2, 4), (2, 5)]],
+        '!' => &[&[(2, 0), (2, 4)], &[(2, 5), (2, 6)]],
+        '\'' => &[&[(2, 0), (2, 2)]],
+        '"' => &[&[(1, 0), (1, 2)], &[(3, 0), (3, 2)]],
+        '-' => &[&[(1, 3), (3, 3)]],
+        '+' => &[&[(0, 3), (4, 3)], &[(2, 1), (2, 5)]],
+        '=' => &[&[(0, 2), (4, 2)], &[(0, 4), (4, 4)]],
+        '*' => &[&[(2, 1), (2, 5)], &[(0, 2), (4, 4)], &[(0, 4), (4, 2)]],
+        '/' => &[&[(0, 6), (4, 0)]],
+        '_' => &[&[(0, 6), (4, 6)]],
+        '<' => &[&[(4, 1), (0, 3), (4, 5)]],
+        '>' => &[&[(0, 1), (4, 3), (0, 5)]],
+        '(' => &[&[(3, 0), (1, 2), (1, 4), (3, 6)]],
+        ')' => &[&[(1, 0), (3, 2), (3, 4), (1, 6)]],
+        '%' => &[
+            &[(0, 6), (4, 0)],
+            &[(0, 0), (1, 0), (1, 1), (0, 1), (0, 0)],
+            &[(3, 5), (4, 5), (4, 6), (3, 6), (3, 5)],
+        ],
+        _ => &[
+            &[(0, 1), (1, 0), (3, 0), (4, 1), (4, 2), (2, 3), (2, 4)],
+            &[(2, 5), (2, 6)],
+        ],
+    }
+}
@@ -0,0 +1,371 @@
+//! GIF and animated GIF encoding.
+//!
+//! GIF images hold at most 256 colors. [`IndexedCanvas`]es are stored with their palette as is,
+//! true-color frames are reduced to a palette with error diffusion dithering, see [`Canvas::dither_to_palette`](crate::canvas::Canvas::dither_to_palette).
+//! Use [`png::encode_apng`](crate::png::encode_apng) to keep the full 24-bit color of every frame instead.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::animation::Frame;
+use crate::color::RGB;
+use crate::error::CanvasError;
+use crate::indexed::IndexedCanvas;
+use crate::png::BitWriter;
+
+/// Largest number of codes of the LZW compression of GIF.
+const MAX_CODES: u16 = 4096;
+
+impl IndexedCanvas {
+    /// Encodes the canvas with its palette as a GIF image.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canvas is wider or higher than `65535` pixels, the largest size of a GIF image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::RGB;
+    /// use drawing_stuff::indexed::IndexedCanvas;
+    ///
+    /// const WIDTH: usize = 320;
+    /// const HEIGHT: usize = 200;
+    ///
+    /// let palette = vec![RGB { r: 0, g: 0, b: 0 }, RGB { r: 255, g: 170, b: 0 }];
+    /// let mut canvas = IndexedCanvas::new(WIDTH, HEIGHT, palette);
+    /// canvas.set_at(20, 10, 1);
+    ///
+    /// let bytes = canvas.to_gif_bytes();
+    /// assert!(bytes.starts_with(b"GIF89a"));
+    /// ```
+    pub fn to_gif_bytes(&self) -> Vec<u8> {
+        assert!(
+            self.width() <= u16::MAX as usize && self.height() <= u16::MAX as usize,
+            "canvas too large for a GIF image"
+        );
+
+        let mut bytes = Vec::new();
+        write_header(&mut bytes, self.width(), self.height(), self.palette());
+        let image = Region::full(self);
+        write_image(&mut bytes, &image, 0, palette_bits(self.palette().len()));
+        bytes.push(b';');
+        bytes
+    }
+
+    /// Writes the canvas to a GIF file at the specified path.
+    pub fn save_gif<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_gif_bytes())
+    }
+}
+
+/// Returns a palette of 256 colors covering all of RGB, useful for [`encode_gif`] if the colors of the frames are not known.
+///
+/// The palette holds the 216 colors of a 6x6x6 color cube followed by 40 grays in between.
+pub fn web_palette() -> Vec<RGB> {
+    let cube = (0..216u32).map(|i| {
+        let level = |v: u32| (v * 51) as u8;
+        RGB {
+            r: level(i / 36),
+            g: level(i / 6 % 6),
+            b: level(i % 6),
+        }
+    });
+    // grays avoiding the ones of the cube
+    let grays = (0..40u32).map(|i| {
+        let v = ((i + 1) * 255 / 41) as u8;
+        RGB { r: v, g: v, b: v }
+    });
+    cube.chain(grays).collect()
+}
+
+/// Encodes frames as an animated GIF, dithering every frame to the specified palette.
+///
+/// Each frame is shown until the timestamp of the next one, the last frame as long as the one before it
+/// or a tenth of a second for a single frame. GIF stores delays in hundredths of a second, which are rounded
+/// without the rounding errors adding up. Frames only store the area changed since the previous frame
+/// and unchanged frames extend the previous one. `loops` is the number of times the animation plays, `0` loops forever.
+///
+/// Returns [`CanvasError::ZeroSize`] if there are no frames or they are empty,
+/// [`CanvasError::SizeMismatch`] if the frames differ in size
+/// and [`CanvasError::InvalidGeometry`] if they are wider or higher than `65535` pixels.
+///
+/// # Panics
+///
+/// Panics if the palette holds more than 256 colors.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::animation::Frame;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{BLACK, WHITE};
+/// use drawing_stuff::gif;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let frames: Vec<Frame> = (0..10)
+///     .map(|i| {
+///         let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///         canvas.draw_circle_solid(100 + i * 80, 360, 40, WHITE);
+///         Frame { canvas, timestamp: i as f64 / 30.0 }
+///     })
+///     .collect();
+///
+/// // a palette of just the colors used, see web_palette for other frames
+/// let palette = [BLACK.to_rgb().0, WHITE.to_rgb().0];
+/// let bytes = gif::encode_gif(&frames, &palette, 0).unwrap();
+/// assert!(bytes.starts_with(b"GIF89a"));
+/// ```
+pub fn encode_gif(frames: &[Frame], palette: &[RGB], loops: u32) -> Result<Vec<u8>, CanvasError> {
+    let first = frames.first().ok_or(CanvasError::ZeroSize)?;
+    let (width, height) = (first.canvas.width(), first.canvas.height());
+    if width == 0 || height == 0 {
+        return Err(CanvasError::ZeroSize);
+    }
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        return Err(CanvasError::InvalidGeometry(
+            "frames too large for a GIF image",
+        ));
+    }
+    if let Some(frame) = frames
+        .iter()
+        .find(|f| f.canvas.width() != width || f.canvas.height() != height)
+    {
+        return Err(CanvasError::SizeMismatch {
+            expected: (width, height),
+            found: (frame.canvas.width(), frame.canvas.height()),
+        });
+    }
+
+    let indexed: Vec<IndexedCanvas> = frames
+        .iter()
+        .map(|f| f.canvas.dither_to_palette(palette))
+        .collect();
+
+    // the changed region of every frame and the time it is shown at in hundredths of a second
+    let mut regions: Vec<(Region, f64)> = Vec::new();
+    for (i, frame) in indexed.iter().enumerate() {
+        let timestamp = frames[i].timestamp;
+        let timestamp = match timestamp.is_finite() {
+            true => timestamp,
+            false => regions.last().map_or(0.0, |r| r.1),
+        };
+
+        let region = match i {
+            0 => Some(Region::full(frame)),
+            _ => Region::changed(&indexed[i - 1], frame),
+        };
+        if let Some(region) = region {
+            regions.push((region, timestamp));
+        }
+    }
+
+    let end = match frames.len() {
+        1 => frames[0].timestamp + 0.1,
+        n => 2.0 * frames[n - 1].timestamp - frames[n - 2].timestamp,
+    };
+    let centiseconds = |t: f64| (t * 100.0).round();
+
+    let mut bytes = Vec::new();
+    write_header(&mut bytes, width, height, palette);
+    if loops != 1 {
+        // the netscape extension counts the repetitions after the first play
+        let repetitions = loops.saturating_sub(1).min(u16::MAX as u32) as u16;
+        bytes.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01");
+        bytes.extend_from_slice(&repetitions.to_le_bytes());
+        bytes.push(0);
+    }
+
+    let bits = palette_bits(palette.len());
+    for (i, (region, start)) in regions.iter().enumerate() {
+        let next = regions.get(i + 1).map_or(end, |r| r.1);
+        let delay = centiseconds(next) - centiseconds(*start);
+        let delay = match delay.is_finite() {
+            true => delay.clamp(0.0, u16::MAX as f64) as u16,
+            false => 0,
+        };
+        write_image(&mut bytes, region, delay, bits);
+    }
+
+    bytes.push(b';');
+    Ok(bytes)
+}
+
+/// Writes frames to an animated GIF file at the specified path.
+///
+/// See [`encode_gif`] for more details, its errors are returned as [`io::ErrorKind::InvalidInput`].
+pub fn save_gif<P: AsRef<Path>>(
+    path: P,
+    frames: &[Frame],
+    palette: &[RGB],
+    loops: u32,
+) -> io::Result<()> {
+    let bytes = encode_gif(frames, palette, loops)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    fs::write(path, bytes)
+}
+
+/// A rectangular part of an indexed canvas.
+struct Region<'a> {
+    canvas: &'a IndexedCanvas,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Region<'a> {
+    fn full(canvas: &'a IndexedCanvas) -> Self {
+        Self {
+            canvas,
+            x: 0,
+            y: 0,
+            width: canvas.width(),
+            height: canvas.height(),
+        }
+    }
+
+    /// The bounding box of all pixels of `current` differing from `previous`, `None` if both are the same.
+    fn changed(previous: &IndexedCanvas, current: &'a IndexedCanvas) -> Option<Self> {
+        let width = current.width();
+        let rows = previous
+            .buffer()
+            .chunks(width)
+            .zip(current.buffer().chunks(width));
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        for (y, (old, new)) in rows.enumerate() {
+            if old == new {
+                continue;
+            }
+            let first = old.iter().zip(new).position(|(a, b)| a != b)?;
+            let last = width - 1 - old.iter().zip(new).rev().position(|(a, b)| a != b)?;
+            min_x = min_x.min(first);
+            max_x = max_x.max(last);
+            min_y = min_y.min(y);
+            max_y = y;
+        }
+
+        match min_y {
+            usize::MAX => None,
+            _ => Some(Self {
+                canvas: current,
+                x: min_x,
+                y: min_y,
+                width: max_x - min_x + 1,
+                height: max_y - min_y + 1,
+            }),
+        }
+    }
+
+    fn indices(&self) -> impl Iterator<Item = u8> + '_ {
+        let stride = self.canvas.width();
+        (self.y..self.y + self.height).flat_map(move |y| {
+            let start = y * stride + self.x;
+            self.canvas.buffer()[start..start + self.width]
+                .iter()
+                .copied()
+        })
+    }
+}
+
+/// Returns the number of bits per index of a palette, at least `1` as GIF requires.
+fn palette_bits(colors: usize) -> u32 {
+    colors.max(2).next_power_of_two().trailing_zeros()
+}
+
+/// Writes the signature, the screen descriptor and the palette as global color table.
+fn write_header(bytes: &mut Vec<u8>, width: usize, height: usize, palette: &[RGB]) {
+    let bits = palette_bits(palette.len());
+    bytes.extend_from_slice(b"GIF89a");
+    bytes.extend_from_slice(&(width as u16).to_le_bytes());
+    bytes.extend_from_slice(&(height as u16).to_le_bytes());
+    // global color table with 8 bits per channel, no background color or aspect ratio
+    bytes.extend_from_slice(&[0xf0 | (bits - 1) as u8, 0, 0]);
+
+    for i in 0..1 << bits {
+        let color = palette.get(i).copied().unwrap_or(RGB { r: 0, g: 0, b: 0 });
+        bytes.extend_from_slice(&[color.r, color.g, color.b]);
+    }
+}
+
+/// Writes a region as an image shown for `delay` hundredths of a second, keeping it as base for the next one.
+fn write_image(bytes: &mut Vec<u8>, region: &Region, delay: u16, bits: u32) {
+    bytes.extend_from_slice(&[0x21, 0xf9, 4, 1 << 2]);
+    bytes.extend_from_slice(&delay.to_le_bytes());
+    bytes.extend_from_slice(&[0, 0]);
+
+    bytes.push(b',');
+    for v in [region.x, region.y, region.width, region.height] {
+        bytes.extend_from_slice(&(v as u16).to_le_bytes());
+    }
+    bytes.push(0);
+
+    let min_code_size = bits.max(2);
+    bytes.push(min_code_size as u8);
+    let data = lzw_compress(region.indices(), min_code_size);
+    for block in data.chunks(255) {
+        bytes.push(block.len() as u8);
+        bytes.extend_from_slice(block);
+    }
+    bytes.push(0);
+}
+
+/// Compresses palette indices with the variable-length LZW codes of GIF, starting over once all codes are used.
+fn lzw_compress<I>(indices: I, min_code_size: u32) -> Vec<u8>
+where
+    I: Iterator<Item = u8>,
+{
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        count: 0,
+    };
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+    writer.write(clear as u32, size);
+
+    let mut prefix: Option<u16> = None;
+    for index in indices {
+        let Some(current) = prefix else {
+            prefix = Some(index as u16);
+            continue;
+        };
+        if let Some(&code) = codes.get(&(current, index)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        writer.write(current as u32, size);
+        match next < MAX_CODES - 1 {
+            true => {
+                codes.insert((current, index), next);
+                if next == 1 << size {
+                    size += 1;
+                }
+                next += 1;
+            }
+            false => {
+                writer.write(clear as u32, size);
+                codes.clear();
+                next = end + 1;
+                size = min_code_size + 1;
+            }
+        }
+        prefix = Some(index as u16);
+    }
+
+    if let Some(current) = prefix {
+        writer.write(current as u32, size);
+    }
+    writer.write(end as u32, size);
+    writer.finish()
+}
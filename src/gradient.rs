@@ -0,0 +1,141 @@
+use crate::color::{Interpolation, RGBA};
+
+/// How a [`Gradient`] is continued outside of the range `0..=1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extend {
+    /// The colors of the first and last stop are continued.
+    #[default]
+    Clamp,
+    /// The gradient starts over.
+    Repeat,
+    /// The gradient is repeated, every second time in reverse.
+    Mirror,
+}
+
+/// A gradient made of ordered color stops.
+///
+/// Each stop places a color at a position, usually in the range `0..=1`.
+/// Between two stops the colors are interpolated, before the first and after the last stop the gradient
+/// continues as specified by its [`Extend`].
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::{Interpolation, RGBA};
+/// use drawing_stuff::gradient::{Extend, Gradient};
+///
+/// let gradient = Gradient::new(&[
+///     (0.0, RGBA::new(0, 0, 0, 255)),
+///     (0.5, RGBA::new(255, 0, 0, 255)),
+///     (1.0, RGBA::new(255, 255, 255, 0)),
+/// ])
+/// .interpolation(Interpolation::SRGB)
+/// .extend(Extend::Repeat);
+///
+/// assert_eq!(RGBA::new(127, 0, 0, 255), gradient.sample(0.25));
+/// assert_eq!(RGBA::new(255, 127, 127, 128), gradient.sample(0.75));
+/// assert_eq!(RGBA::new(127, 0, 0, 255), gradient.sample(1.25));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, RGBA)>,
+
+    interpolation: Interpolation,
+    extend: Extend,
+}
+
+impl Gradient {
+    /// Creates a new gradient from color stops given as pairs of position and color.
+    ///
+    /// The stops are sorted by their position, stops at the same position keep their order to allow hard edges.
+    pub fn new(stops: &[(f32, RGBA)]) -> Self {
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self {
+            stops,
+            interpolation: Interpolation::default(),
+            extend: Extend::default(),
+        }
+    }
+
+    /// Sets the color space the colors are interpolated in.
+    pub fn interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets how the gradient is continued outside of the range `0..=1`.
+    pub fn extend(mut self, extend: Extend) -> Self {
+        self.extend = extend;
+        self
+    }
+
+    /// Returns the color stops of the gradient sorted by their position.
+    pub fn stops(&self) -> &[(f32, RGBA)] {
+        &self.stops
+    }
+
+    /// Returns the color of the gradient at the specified position.
+    ///
+    /// A gradient without stops is transparent everywhere.
+    pub fn sample(&self, t: f32) -> RGBA {
+        let (Some(&(_, first)), Some(&(_, last))) = (self.stops.first(), self.stops.last()) else {
+            return RGBA::new(0, 0, 0, 0);
+        };
+
+        let t = match t.is_nan() {
+            true => 0.0,
+            false => t,
+        };
+        let t = match self.extend {
+            Extend::Clamp => t,
+            Extend::Repeat => t.rem_euclid(1.0),
+            Extend::Mirror => 1.0 - (t.rem_euclid(2.0) - 1.0).abs(),
+        };
+
+        let next = self.stops.partition_point(|&(position, _)| position <= t);
+        if next == 0 {
+            return first;
+        }
+        if next == self.stops.len() {
+            return last;
+        }
+
+        let (start, from) = self.stops[next - 1];
+        let (end, to) = self.stops[next];
+        let local = (t - start) / (end - start);
+
+        let (from_rgb, from_alpha) = from.to_rgb();
+        let (to_rgb, to_alpha) = to.to_rgb();
+        let rgb = self.interpolation.lerp(from_rgb, to_rgb, local as f64);
+        let alpha = from_alpha as f32 + (to_alpha as f32 - from_alpha as f32) * local;
+        RGBA::new(rgb.r, rgb.g, rgb.b, alpha.round() as u8)
+    }
+
+    /// Samples the gradient at `count` evenly spaced positions from `0` to `1`, e.g. to build a palette.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::gradient::Gradient;
+    ///
+    /// let gradient = Gradient::new(&[
+    ///     (0.0, RGBA::new(0, 0, 0, 255)),
+    ///     (1.0, RGBA::new(255, 255, 255, 255)),
+    /// ]);
+    ///
+    /// let palette = gradient.colors(5);
+    /// assert_eq!(RGBA::new(0, 0, 0, 255), palette[0]);
+    /// assert_eq!(RGBA::new(127, 127, 127, 255), palette[2]);
+    /// assert_eq!(RGBA::new(255, 255, 255, 255), palette[4]);
+    /// ```
+    pub fn colors(&self, count: usize) -> Vec<RGBA> {
+        (0..count)
+            .map(|i| match count {
+                1 => self.sample(0.0),
+                _ => self.sample(i as f32 / (count - 1) as f32),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,168 @@
+//! Encoders for icon formats.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::canvas::Canvas;
+
+/// Encodes a set of canvases as a single multi-size ICO file.
+///
+/// Every canvas becomes one image of the icon and has to be between 1x1 and 256x256 pixels.
+/// Returns `None` if there are no canvases, more than `u16::MAX` or one of them has an invalid size.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::icon;
+///
+/// let sizes = [16, 32, 48, 256];
+/// let icons = sizes.map(|size| Canvas::new(size, size));
+///
+/// let bytes = icon::encode_ico(&icons).unwrap();
+/// assert_eq!(&[0, 0, 1, 0, 4, 0], &bytes[..6]);
+/// ```
+pub fn encode_ico(icons: &[Canvas]) -> Option<Vec<u8>> {
+    if icons.is_empty() || icons.len() > u16::MAX as usize {
+        return None;
+    }
+    let valid_size = |size: usize| (1..=256).contains(&size);
+    if icons
+        .iter()
+        .any(|icon| !valid_size(icon.width()) || !valid_size(icon.height()))
+    {
+        return None;
+    }
+
+    let images = icons.iter().map(encode_dib).collect::<Vec<_>>();
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(&0u16.to_le_bytes()); // reserved
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // type: icon
+    bytes.extend_from_slice(&(icons.len() as u16).to_le_bytes());
+
+    let mut offset = 6 + 16 * icons.len();
+    for (icon, image) in icons.iter().zip(&images) {
+        // a size of 256 is stored as 0
+        bytes.push(icon.width() as u8);
+        bytes.push(icon.height() as u8);
+        bytes.push(0); // no palette
+        bytes.push(0); // reserved
+        bytes.extend_from_slice(&1u16.to_le_bytes()); // color planes
+        bytes.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+        bytes.extend_from_slice(&(image.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += image.len();
+    }
+
+    for image in images {
+        bytes.extend_from_slice(&image);
+    }
+
+    Some(bytes)
+}
+
+/// Writes a set of canvases as a single multi-size ICO file to the specified path.
+///
+/// Returns an error of kind [`io::ErrorKind::InvalidInput`] if the canvases can not be encoded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::icon;
+///
+/// let icons = [16, 32, 48].map(|size| Canvas::new(size, size));
+///
+/// icon::save_ico("favicon.ico", &icons).unwrap();
+/// ```
+pub fn save_ico<P: AsRef<Path>>(path: P, icons: &[Canvas]) -> io::Result<()> {
+    let bytes = encode_ico(icons).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "icons have to be between 1x1 and 256x256 pixels",
+        )
+    })?;
+    fs::write(path, bytes)
+}
+
+/// Encodes a canvas as the 32-bit device independent bitmap stored inside of ICO files.
+fn encode_dib(canvas: &Canvas) -> Vec<u8> {
+    let width = canvas.width();
+    let height = canvas.height();
+    let mask_stride = width.div_ceil(32) * 4;
+
+    let mut bytes = Vec::with_capacity(40 + width * height * 4 + mask_stride * height);
+    bytes.extend_from_slice(&40u32.to_le_bytes()); // header size
+    bytes.extend_from_slice(&(width as i32).to_le_bytes());
+    bytes.extend_from_slice(&(2 * height as i32).to_le_bytes()); // color and mask
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // color planes
+    bytes.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+    bytes.extend_from_slice(&0u32.to_le_bytes()); // no compression
+    bytes.extend_from_slice(&((width * height * 4) as u32).to_le_bytes());
+    bytes.extend_from_slice(&[0; 16]); // resolution and palette
+
+    // rows are stored bottom-up in BGRA
+    for row in canvas.buffer().chunks(width).rev() {
+        for pixel in row {
+            bytes.extend_from_slice(&[pixel.b, pixel.g, pixel.r, 255]);
+        }
+    }
+
+    // fully opaque AND mask
+    bytes.resize(bytes.len() + mask_stride * height, 0);
+
+    bytes
+}
+
+impl Canvas {
+    /// Encodes the canvas as a monochrome XBM bitmap, a C source snippet usable e.g. for X11 cursors.
+    ///
+    /// Pixels with a brightness below `threshold` become set bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// let canvas = Canvas::new(8, 1);
+    ///
+    /// let xbm = canvas.to_xbm("cursor", 128);
+    /// assert!(xbm.starts_with("#define cursor_width 8\n"));
+    /// ```
+    pub fn to_xbm(&self, name: &str, threshold: u8) -> String {
+        let mut bytes = Vec::new();
+        for row in self.buffer().chunks(self.width().max(1)) {
+            for chunk in row.chunks(8) {
+                let mut byte = 0u8;
+                for (bit, pixel) in chunk.iter().enumerate() {
+                    let brightness = (pixel.r as u32 + pixel.g as u32 + pixel.b as u32) / 3;
+                    if brightness < threshold as u32 {
+                        // XBM stores the leftmost pixel in the least significant bit
+                        byte |= 1 << bit;
+                    }
+                }
+                bytes.push(format!("0x{:02x}", byte));
+            }
+        }
+
+        let mut xbm = format!(
+            "#define {name}_width {}\n#define {name}_height {}\nstatic unsigned char {name}_bits[] = {{\n",
+            self.width(),
+            self.height()
+        );
+        for line in bytes.chunks(12) {
+            xbm.push_str("   ");
+            xbm.push_str(&line.join(", "));
+            xbm.push_str(",\n");
+        }
+        if xbm.ends_with(",\n") {
+            xbm.truncate(xbm.len() - 2);
+            xbm.push('\n');
+        }
+        xbm.push_str("};\n");
+
+        xbm
+    }
+}
@@ -0,0 +1,115 @@
+use crate::canvas::Canvas;
+use crate::color::{RGB, RGBA};
+
+/// A small fixed size RGBA image which can be created at compile time, e.g. using the [`pixels!`] macro.
+///
+/// [`pixels!`]: crate::pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Image<const W: usize, const H: usize> {
+    pub pixels: [[RGBA; W]; H],
+}
+
+impl<const W: usize, const H: usize> Image<W, H> {
+    /// Creates an image from rows of ASCII characters, mapping each character to a color using a palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a row doesn't have a length of `W` or contains a character missing from the palette.
+    /// When used in a constant this turns into a compile error.
+    pub const fn from_ascii(rows: [&str; H], palette: &[(u8, RGBA)]) -> Self {
+        let mut pixels = [[RGBA {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        }; W]; H];
+
+        let mut y = 0;
+        while y < H {
+            let row = rows[y].as_bytes();
+            if row.len() != W {
+                panic!("all rows of an image need to have the same length");
+            }
+
+            let mut x = 0;
+            while x < W {
+                let mut i = 0;
+                loop {
+                    if i == palette.len() {
+                        panic!("image contains a character missing from the palette");
+                    }
+                    if palette[i].0 == row[x] {
+                        pixels[y][x] = palette[i].1;
+                        break;
+                    }
+                    i += 1;
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+
+        Self { pixels }
+    }
+
+    /// Returns the width of the image.
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// Returns the height of the image.
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Returns the pixels of the image stored row by row, e.g. for use with [`Canvas::blit_rgba`].
+    pub fn as_flattened(&self) -> &[RGBA] {
+        self.pixels.as_flattened()
+    }
+
+    /// Creates a canvas of the image composited onto a background color.
+    pub fn to_canvas(&self, background: RGB) -> Canvas {
+        let mut canvas = Canvas::new(W, H);
+        canvas.fill(background);
+        canvas.blit_rgba(self.as_flattened(), W, 0, 0);
+        canvas
+    }
+}
+
+/// Creates an [`Image`] from inline ASCII art at compile time.
+///
+/// A palette mapping characters to colors is followed by the rows of the image.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{BLACK, TRANSPARANT, WHITE};
+/// use drawing_stuff::image::Image;
+/// use drawing_stuff::pixels;
+///
+/// const CURSOR: Image<4, 4> = pixels! {
+///     '.' => TRANSPARANT,
+///     '#' => BLACK,
+///     'o' => WHITE;
+///     "#...",
+///     "#o#.",
+///     "#oo#",
+///     "###.",
+/// };
+///
+/// let mut canvas = Canvas::new(1080, 720);
+/// canvas.blit_rgba(CURSOR.as_flattened(), CURSOR.width(), 200, 100);
+///
+/// assert_eq!(255, canvas.get_at(201, 101).unwrap().r);
+/// ```
+#[macro_export]
+macro_rules! pixels {
+    ($($c:literal => $color:expr),+ $(,)?; $($row:literal),+ $(,)?) => {{
+        const ROWS: &[&str] = &[$($row),+];
+        $crate::image::Image::<{ ROWS[0].len() }, { ROWS.len() }>::from_ascii(
+            [$($row),+],
+            &[$(($c as u8, $color)),+],
+        )
+    }};
+}
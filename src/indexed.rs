@@ -0,0 +1,184 @@
+use crate::canvas::Canvas;
+use crate::color::RGB;
+
+/// A canvas storing a palette index per pixel instead of a color.
+///
+/// Every pixel takes up a single byte, so the palette holds at most 256 colors.
+/// Retro-style renderers can draw with indices directly and swap the palette for effects like color cycling.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::RGB;
+/// use drawing_stuff::indexed::IndexedCanvas;
+///
+/// const WIDTH: usize = 320;
+/// const HEIGHT: usize = 200;
+///
+/// let palette = vec![RGB { r: 0, g: 0, b: 0 }, RGB { r: 255, g: 170, b: 0 }];
+/// let mut canvas = IndexedCanvas::new(WIDTH, HEIGHT, palette);
+///
+/// canvas.set_at(20, 10, 1);
+///
+/// let output = canvas.to_canvas();
+/// assert_eq!(&RGB { r: 255, g: 170, b: 0 }, output.get_at(20, 10).unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedCanvas {
+    width: usize,
+    height: usize,
+    palette: Vec<RGB>,
+
+    buffer: Vec<u8>,
+}
+
+impl IndexedCanvas {
+    /// Creates a new canvas with all pixels set to the first color of the palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette holds more than 256 colors.
+    pub fn new(width: usize, height: usize, palette: Vec<RGB>) -> Self {
+        assert!(palette.len() <= 256, "a palette holds at most 256 colors");
+        Self {
+            width,
+            height,
+            palette,
+            buffer: vec![0; width * height],
+        }
+    }
+
+    /// Creates an indexed copy of a [`Canvas`], replacing every color by the closest color of the palette.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette holds more than 256 colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    /// use drawing_stuff::indexed::IndexedCanvas;
+    ///
+    /// let mut canvas = Canvas::new(64, 64);
+    /// canvas.fill(RGB { r: 200, g: 30, b: 40 });
+    ///
+    /// let palette = vec![RGB { r: 0, g: 0, b: 0 }, RGB { r: 255, g: 0, b: 0 }];
+    /// let indexed = IndexedCanvas::from_canvas(&canvas, palette);
+    ///
+    /// assert_eq!(Some(1), indexed.get_at(10, 10));
+    /// ```
+    pub fn from_canvas(canvas: &Canvas, palette: Vec<RGB>) -> Self {
+        let mut indexed = Self::new(canvas.width(), canvas.height(), palette);
+        for (index, &color) in indexed.buffer.iter_mut().zip(canvas.buffer().iter()) {
+            *index = nearest_index(&indexed.palette, color);
+        }
+        indexed
+    }
+
+    /// Converts the canvas into a new true-color [`Canvas`].
+    ///
+    /// Pixels with an index missing from the palette turn black.
+    pub fn to_canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        let black = RGB { r: 0, g: 0, b: 0 };
+        for (pixel, &index) in canvas.buffer_mut().iter_mut().zip(&self.buffer) {
+            *pixel = *self.palette.get(index as usize).unwrap_or(&black);
+        }
+        canvas
+    }
+
+    /// Returns the width of the canvas.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the palette of the canvas.
+    pub fn palette(&self) -> &[RGB] {
+        &self.palette
+    }
+
+    /// Replaces the palette of the canvas keeping the indices of all pixels.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the palette holds more than 256 colors.
+    pub fn set_palette(&mut self, palette: Vec<RGB>) {
+        assert!(palette.len() <= 256, "a palette holds at most 256 colors");
+        self.palette = palette;
+    }
+
+    /// Returns a reference to the index buffer of the canvas.
+    pub fn buffer(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+
+    /// Returns a mutable reference to the index buffer of the canvas.
+    pub fn buffer_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+
+    /// Checks if the pixel specified lays inside of the canvas.
+    pub fn pixel_inside(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    }
+
+    /// Returns the palette index of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    pub fn get_at(&self, x: isize, y: isize) -> Option<u8> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        Some(self.buffer[y as usize * self.width + x as usize])
+    }
+
+    /// Sets the palette index of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    pub fn set_at(&mut self, x: isize, y: isize, index: u8) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.buffer[y as usize * self.width + x as usize] = index;
+        Some(())
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas or its index is missing from the palette.
+    pub fn color_at(&self, x: isize, y: isize) -> Option<RGB> {
+        let index = self.get_at(x, y)?;
+        self.palette.get(index as usize).copied()
+    }
+
+    /// Sets all pixels of the canvas to a palette index.
+    pub fn fill(&mut self, index: u8) {
+        self.buffer.fill(index);
+    }
+}
+
+/// Returns the index of the palette color closest to a color.
+///
+/// An empty palette always results in `0`.
+pub(crate) fn nearest_index(palette: &[RGB], color: RGB) -> u8 {
+    let distance = |c: &RGB| {
+        let dr = c.r as i32 - color.r as i32;
+        let dg = c.g as i32 - color.g as i32;
+        let db = c.b as i32 - color.b as i32;
+        dr * dr + dg * dg + db * db
+    };
+
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, c)| distance(c))
+        .map(|(i, _)| i as u8)
+        .unwrap_or(0)
+}
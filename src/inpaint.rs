@@ -0,0 +1,152 @@
+use crate::canvas::Canvas;
+use crate::color::RGBA;
+use crate::mask::Mask;
+
+/// Number of smoothing passes run after the hole has been filled from its border.
+const DIFFUSION_ITERATIONS: usize = 64;
+
+impl Canvas {
+    /// Fills the area covered by a mask with colors spreading in from its surroundings,
+    /// e.g. to erase stamps or moved regions without leaving holes.
+    ///
+    /// The mask is placed at the top-left corner of the canvas. The hole is first filled layer by layer from its border
+    /// and then smoothed by diffusion, which works best for backgrounds without fine detail.
+    /// Partially covered pixels are blended with the fill by their coverage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::{RGB, WHITE};
+    /// use drawing_stuff::mask::Mask;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 40, g: 120, b: 200 });
+    /// canvas.draw_circle_solid(200, 100, 20, WHITE);
+    ///
+    /// // erase the circle
+    /// let mut region = Mask::new(WIDTH, HEIGHT);
+    /// region.fill_circle(200, 100, 22);
+    /// canvas.inpaint(&region);
+    ///
+    /// assert_eq!(Some(&RGB { r: 40, g: 120, b: 200 }), canvas.get_at(200, 100));
+    ///
+    /// // an empty mask leaves the canvas untouched
+    /// canvas.inpaint(&Mask::new(0, HEIGHT));
+    /// ```
+    pub fn inpaint(&mut self, region: &Mask) {
+        let width = self.width().min(region.width());
+        let height = self.height().min(region.height());
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // bounds of the hole grown by one pixel of known surroundings
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (y, row) in region
+            .data()
+            .chunks(region.width())
+            .take(height)
+            .enumerate()
+        {
+            for (x, _) in row[..width].iter().enumerate().filter(|(_, &c)| c > 0) {
+                let b = bounds.get_or_insert((x, y, x, y));
+                *b = (b.0.min(x), b.1.min(y), b.2.max(x), b.3.max(y));
+            }
+        }
+        let Some((x0, y0, x1, y1)) = bounds else {
+            return;
+        };
+        let (x0, y0) = (x0.saturating_sub(1), y0.saturating_sub(1));
+        let (x1, y1) = ((x1 + 2).min(self.width()), (y1 + 2).min(self.height()));
+        let (w, h) = (x1 - x0, y1 - y0);
+
+        let coverage = |x: usize, y: usize| match x < width && y < height {
+            true => region.data()[y * region.width() + x],
+            false => 0,
+        };
+        let hole: Vec<bool> = (0..w * h)
+            .map(|i| coverage(x0 + i % w, y0 + i / w) > 0)
+            .collect();
+        let mut colors: Vec<[f32; 3]> = (0..w * h)
+            .map(|i| {
+                let c = self.buffer()[(y0 + i / w) * self.width() + x0 + i % w];
+                [c.r as f32, c.g as f32, c.b as f32]
+            })
+            .collect();
+
+        let neighbors = |i: usize| {
+            let (x, y) = (i % w, i / w);
+            [
+                (x > 0).then(|| i - 1),
+                (x + 1 < w).then(|| i + 1),
+                (y > 0).then(|| i - w),
+                (y + 1 < h).then(|| i + w),
+            ]
+            .into_iter()
+            .flatten()
+        };
+
+        // fill the hole layer by layer from its border
+        let mut known: Vec<bool> = hole.iter().map(|h| !h).collect();
+        loop {
+            let layer: Vec<usize> = (0..w * h)
+                .filter(|&i| !known[i] && neighbors(i).any(|n| known[n]))
+                .collect();
+            if layer.is_empty() {
+                break;
+            }
+
+            for &i in &layer {
+                let mut sum = [0.0; 3];
+                let mut count = 0.0;
+                for n in neighbors(i).filter(|&n| known[n]) {
+                    sum = [
+                        sum[0] + colors[n][0],
+                        sum[1] + colors[n][1],
+                        sum[2] + colors[n][2],
+                    ];
+                    count += 1.0;
+                }
+                colors[i] = sum.map(|v| v / count);
+            }
+            for i in layer {
+                known[i] = true;
+            }
+        }
+        // nothing around the hole to fill it from
+        if known.iter().any(|k| !k) {
+            return;
+        }
+
+        // smooth the fill by repeatedly averaging the neighbors of hole pixels
+        for _ in 0..DIFFUSION_ITERATIONS {
+            let previous = colors.clone();
+            for i in (0..w * h).filter(|&i| hole[i]) {
+                let mut sum = [0.0; 3];
+                let mut count = 0.0;
+                for n in neighbors(i) {
+                    sum = [
+                        sum[0] + previous[n][0],
+                        sum[1] + previous[n][1],
+                        sum[2] + previous[n][2],
+                    ];
+                    count += 1.0;
+                }
+                colors[i] = sum.map(|v| v / count);
+            }
+        }
+
+        let stride = self.width();
+        let buffer = self.buffer_mut();
+        for i in (0..w * h).filter(|&i| hole[i]) {
+            let (x, y) = (x0 + i % w, y0 + i / w);
+            let [r, g, b] = colors[i].map(|v| v.round().clamp(0.0, 255.0) as u8);
+            let pixel = &mut buffer[y * stride + x];
+            *pixel = pixel.add_rgba(RGBA::new(r, g, b, coverage(x, y)));
+        }
+    }
+}
@@ -15,10 +15,10 @@
 //!     let mut canvas = Canvas::new(WIDTH, HEIGHT);
 //!
 //!     // get color of pixel
-//!     canvas.get(200, 100);
+//!     canvas.get_at(200, 100);
 //!
 //!     // set color of pixel
-//!     canvas.set(200, 100, RGB { r: 255, g: 255, b: 255 });
+//!     canvas.set_at(200, 100, RGB { r: 255, g: 255, b: 255 });
 //!
 //!     // draw single pixel
 //!     canvas.draw_pixel(200, 100, WHITE);
@@ -79,6 +79,51 @@
 //! }
 //! ```
 
+pub mod animation;
+pub mod atlas;
+pub mod backgrounds;
+pub mod blend;
 pub mod canvas;
+#[cfg(feature = "canvas16")]
+pub mod canvas16;
+pub mod captcha;
+pub mod charts;
 pub mod color;
+pub mod commands;
+pub mod compare;
+pub mod delta;
+pub mod dither;
+pub mod draw_list;
 pub mod drawables;
+pub mod error;
+pub mod font;
+pub mod gif;
+pub mod gradient;
+pub mod icon;
+pub mod image;
+pub mod indexed;
+pub mod lut;
+pub mod mask;
+pub mod noise;
+pub mod ora;
+pub mod packed;
+pub mod path;
+pub mod png;
+pub mod polar;
+pub mod present;
+pub mod raster;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod smoothing;
+pub mod target;
+pub mod testgen;
+pub mod transform;
+
+mod adjust;
+mod blur;
+mod color_space;
+mod inpaint;
+mod patterns;
+mod ppm;
+mod random;
+mod stats;
@@ -0,0 +1,454 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::canvas::Canvas;
+use crate::color::RGB;
+
+/// A color lookup table as stored in `.cube` files.
+///
+/// Both 1D tables, mapping each channel independently, and 3D tables, mapping whole colors, are supported.
+/// 3D tables are sampled using trilinear interpolation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubeLut {
+    dimension: LutDimension,
+    size: usize,
+    table: Vec<[f32; 3]>,
+
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+}
+
+/// Dimension of a [`CubeLut`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LutDimension {
+    OneD,
+    ThreeD,
+}
+
+impl CubeLut {
+    /// Parses a lookup table from the contents of a `.cube` file.
+    ///
+    /// Returns `None` if the contents are not a valid `.cube` file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::lut::CubeLut;
+    ///
+    /// let cube = "LUT_1D_SIZE 2\n1.0 1.0 1.0\n0.0 0.0 0.0\n";
+    /// let lut = CubeLut::from_cube_str(cube).unwrap();
+    ///
+    /// assert_eq!([1.0, 1.0, 1.0], lut.lookup([0.0, 0.0, 0.0]));
+    /// ```
+    pub fn from_cube_str(contents: &str) -> Option<Self> {
+        let mut dimension = None;
+        let mut size: usize = 0;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut table = Vec::new();
+
+        let parse_triple = |parts: &[&str]| -> Option<[f32; 3]> {
+            match parts {
+                [r, g, b] => Some([r.parse().ok()?, g.parse().ok()?, b.parse().ok()?]),
+                _ => None,
+            }
+        };
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            match parts[0] {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => {
+                    dimension = Some(LutDimension::OneD);
+                    size = parts.get(1)?.parse().ok()?;
+                }
+                "LUT_3D_SIZE" => {
+                    dimension = Some(LutDimension::ThreeD);
+                    size = parts.get(1)?.parse().ok()?;
+                }
+                "DOMAIN_MIN" => domain_min = parse_triple(&parts[1..])?,
+                "DOMAIN_MAX" => domain_max = parse_triple(&parts[1..])?,
+                // unknown keywords are skipped as suggested by the specification
+                keyword if keyword.starts_with(|c: char| c.is_ascii_alphabetic()) => {}
+                _ => table.push(parse_triple(&parts)?),
+            }
+        }
+
+        let dimension = dimension?;
+        let expected = match dimension {
+            LutDimension::OneD => size,
+            LutDimension::ThreeD => size.checked_mul(size)?.checked_mul(size)?,
+        };
+        if size < 2 || table.len() != expected {
+            return None;
+        }
+        if (0..3).any(|i| domain_max[i] <= domain_min[i]) {
+            return None;
+        }
+
+        Some(Self {
+            dimension,
+            size,
+            table,
+            domain_min,
+            domain_max,
+        })
+    }
+
+    /// Reads a lookup table from a `.cube` file at the specified path.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the file is not a valid `.cube` file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use drawing_stuff::lut::CubeLut;
+    ///
+    /// let lut = CubeLut::load_cube("look.cube").unwrap();
+    /// ```
+    pub fn load_cube<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_cube_str(&contents)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid .cube file"))
+    }
+
+    /// Returns the dimension of the lookup table.
+    pub fn dimension(&self) -> LutDimension {
+        self.dimension
+    }
+
+    /// Returns the number of entries along each axis of the lookup table.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Maps a color with channels given in the input domain (usually `0.0..=1.0`) through the lookup table.
+    pub fn lookup(&self, color: [f32; 3]) -> [f32; 3] {
+        let last = (self.size - 1) as f32;
+        let mut pos = [0.0; 3];
+        for i in 0..3 {
+            let t = (color[i] - self.domain_min[i]) / (self.domain_max[i] - self.domain_min[i]);
+            pos[i] = t.clamp(0.0, 1.0) * last;
+        }
+
+        match self.dimension {
+            LutDimension::OneD => {
+                let mut out = [0.0; 3];
+                for i in 0..3 {
+                    let i0 = (pos[i].floor() as usize).min(self.size - 2);
+                    let t = pos[i] - i0 as f32;
+                    out[i] = self.table[i0][i] + (self.table[i0 + 1][i] - self.table[i0][i]) * t;
+                }
+                out
+            }
+            LutDimension::ThreeD => {
+                let r0 = (pos[0].floor() as usize).min(self.size - 2);
+                let g0 = (pos[1].floor() as usize).min(self.size - 2);
+                let b0 = (pos[2].floor() as usize).min(self.size - 2);
+                let (tr, tg, tb) = (pos[0] - r0 as f32, pos[1] - g0 as f32, pos[2] - b0 as f32);
+
+                // red changes fastest in .cube files
+                let entry =
+                    |r: usize, g: usize, b: usize| self.table[(b * self.size + g) * self.size + r];
+                let lerp = |a: [f32; 3], b: [f32; 3], t: f32| {
+                    [
+                        a[0] + (b[0] - a[0]) * t,
+                        a[1] + (b[1] - a[1]) * t,
+                        a[2] + (b[2] - a[2]) * t,
+                    ]
+                };
+
+                let c00 = lerp(entry(r0, g0, b0), entry(r0 + 1, g0, b0), tr);
+                let c10 = lerp(entry(r0, g0 + 1, b0), entry(r0 + 1, g0 + 1, b0), tr);
+                let c01 = lerp(entry(r0, g0, b0 + 1), entry(r0 + 1, g0, b0 + 1), tr);
+                let c11 = lerp(entry(r0, g0 + 1, b0 + 1), entry(r0 + 1, g0 + 1, b0 + 1), tr);
+
+                lerp(lerp(c00, c10, tg), lerp(c01, c11, tg), tb)
+            }
+        }
+    }
+}
+
+/// A tone curve through a set of control points, as known from photo editors.
+///
+/// Both the input and output of the curve are in the range `0.0..=1.0`.
+/// The points are connected using monotone cubic interpolation, so the curve never overshoots between them.
+/// Inputs outside of the control points keep the value of the closest one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    points: Vec<(f32, f32)>,
+    tangents: Vec<f32>,
+}
+
+impl Curve {
+    /// Creates a curve through the specified control points.
+    ///
+    /// Returns `None` if less than two points are given or if two points share the same input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::lut::Curve;
+    ///
+    /// // a gentle S-curve raising the contrast
+    /// let curve = Curve::new(&[(0.0, 0.0), (0.25, 0.2), (0.75, 0.8), (1.0, 1.0)]).unwrap();
+    ///
+    /// assert_eq!(0.2, curve.evaluate(0.25));
+    /// ```
+    pub fn new(points: &[(f32, f32)]) -> Option<Self> {
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if points.len() < 2 || points.windows(2).any(|w| w[0].0 == w[1].0) {
+            return None;
+        }
+
+        let slopes = points
+            .windows(2)
+            .map(|w| (w[1].1 - w[0].1) / (w[1].0 - w[0].0))
+            .collect::<Vec<_>>();
+
+        // Fritsch-Carlson tangents
+        let n = points.len();
+        let mut tangents = vec![0.0; n];
+        tangents[0] = slopes[0];
+        tangents[n - 1] = slopes[n - 2];
+        for i in 1..n - 1 {
+            tangents[i] = match slopes[i - 1] * slopes[i] <= 0.0 {
+                true => 0.0,
+                false => (slopes[i - 1] + slopes[i]) / 2.0,
+            };
+        }
+        for (i, &slope) in slopes.iter().enumerate() {
+            if slope == 0.0 {
+                tangents[i] = 0.0;
+                tangents[i + 1] = 0.0;
+                continue;
+            }
+
+            let a = tangents[i] / slope;
+            let b = tangents[i + 1] / slope;
+            let h = a * a + b * b;
+            if h > 9.0 {
+                let t = 3.0 / h.sqrt();
+                tangents[i] = t * a * slope;
+                tangents[i + 1] = t * b * slope;
+            }
+        }
+
+        Some(Self { points, tangents })
+    }
+
+    /// Returns the control points of the curve sorted by their input.
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.points
+    }
+
+    /// Evaluates the curve at the specified input.
+    ///
+    /// Inputs outside of the control points return the value of the nearest end, `NaN` returns `NaN`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::lut::Curve;
+    ///
+    /// let curve = Curve::new(&[(0.0, 0.0), (0.5, 0.75), (1.0, 1.0)]).unwrap();
+    ///
+    /// assert_eq!(0.75, curve.evaluate(0.5));
+    /// assert_eq!(1.0, curve.evaluate(2.0));
+    /// assert!(curve.evaluate(f32::NAN).is_nan());
+    /// ```
+    pub fn evaluate(&self, x: f32) -> f32 {
+        if x.is_nan() {
+            return f32::NAN;
+        }
+
+        let first = self.points[0];
+        let last = self.points[self.points.len() - 1];
+        if x <= first.0 {
+            return first.1;
+        }
+        if x >= last.0 {
+            return last.1;
+        }
+
+        let i = self.points.partition_point(|p| p.0 <= x) - 1;
+        let (x0, y0) = self.points[i];
+        let (x1, y1) = self.points[i + 1];
+        let h = x1 - x0;
+        let t = (x - x0) / h;
+
+        let t2 = t * t;
+        let t3 = t2 * t;
+        (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+            + (t3 - 2.0 * t2 + t) * h * self.tangents[i]
+            + (-2.0 * t3 + 3.0 * t2) * y1
+            + (t3 - t2) * h * self.tangents[i + 1]
+    }
+
+    /// Samples the curve into a lookup table usable with [`Canvas::apply_lut`].
+    pub fn to_table(&self) -> [u8; 256] {
+        std::array::from_fn(|i| {
+            (self.evaluate(i as f32 / 255.0).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+    }
+}
+
+impl Canvas {
+    /// Maps every pixel of the canvas through a lookup table per channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let mut invert = [0u8; 256];
+    /// for (i, v) in invert.iter_mut().enumerate() {
+    ///     *v = 255 - i as u8;
+    /// }
+    /// canvas.apply_lut(&invert, &invert, &invert);
+    ///
+    /// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+    /// ```
+    pub fn apply_lut(&mut self, r: &[u8; 256], g: &[u8; 256], b: &[u8; 256]) {
+        self.for_each_row_mut(0..self.height(), |_, row| {
+            for pixel in row {
+                pixel.r = r[pixel.r as usize];
+                pixel.g = g[pixel.g as usize];
+                pixel.b = b[pixel.b as usize];
+            }
+        });
+    }
+
+    /// Maps every pixel of the canvas through a [`CubeLut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::lut::CubeLut;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let cube = "LUT_1D_SIZE 2\n1.0 1.0 1.0\n0.0 0.0 0.0\n";
+    /// let lut = CubeLut::from_cube_str(cube).unwrap();
+    /// canvas.apply_cube_lut(&lut);
+    ///
+    /// assert_eq!(255, canvas.get_at(200, 100).unwrap().r);
+    /// ```
+    pub fn apply_cube_lut(&mut self, lut: &CubeLut) {
+        let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        if lut.dimension() == LutDimension::OneD {
+            let mut tables = [[0u8; 256]; 3];
+            for i in 0..256 {
+                let v = i as f32 / 255.0;
+                let mapped = lut.lookup([v, v, v]);
+                for (table, v) in tables.iter_mut().zip(mapped) {
+                    table[i] = to_u8(v);
+                }
+            }
+            self.apply_lut(&tables[0], &tables[1], &tables[2]);
+            return;
+        }
+
+        self.for_each_row_mut(0..self.height(), |_, row| {
+            for pixel in row {
+                let mapped = lut.lookup([
+                    pixel.r as f32 / 255.0,
+                    pixel.g as f32 / 255.0,
+                    pixel.b as f32 / 255.0,
+                ]);
+                pixel.r = to_u8(mapped[0]);
+                pixel.g = to_u8(mapped[1]);
+                pixel.b = to_u8(mapped[2]);
+            }
+        });
+    }
+
+    /// Maps every channel of the canvas through a tone curve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    /// use drawing_stuff::lut::Curve;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 51, g: 128, b: 255 });
+    ///
+    /// let brighten = Curve::new(&[(0.0, 0.0), (0.5, 0.75), (1.0, 1.0)]).unwrap();
+    /// canvas.apply_curve(&brighten);
+    ///
+    /// assert_eq!(192, canvas.get_at(200, 100).unwrap().g);
+    /// ```
+    pub fn apply_curve(&mut self, curve: &Curve) {
+        let table = curve.to_table();
+        self.apply_lut(&table, &table, &table);
+    }
+
+    /// Maps each channel of the canvas through its own tone curve.
+    pub fn apply_curves(&mut self, r: &Curve, g: &Curve, b: &Curve) {
+        self.apply_lut(&r.to_table(), &g.to_table(), &b.to_table());
+    }
+
+    /// Maps the luminance of the canvas through a tone curve, scaling the channels of each pixel equally.
+    ///
+    /// Unlike [`Canvas::apply_curve`] this keeps the hue of the pixels, but channels may still clip.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    /// use drawing_stuff::lut::Curve;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 100, g: 100, b: 100 });
+    ///
+    /// let invert = Curve::new(&[(0.0, 1.0), (1.0, 0.0)]).unwrap();
+    /// canvas.apply_curve_luminance(&invert);
+    ///
+    /// assert_eq!(Some(&RGB { r: 155, g: 155, b: 155 }), canvas.get_at(200, 100));
+    /// ```
+    pub fn apply_curve_luminance(&mut self, curve: &Curve) {
+        let table = curve.to_table();
+        for pixel in self.buffer_mut().iter_mut() {
+            let luma = 0.2126 * pixel.r as f32 + 0.7152 * pixel.g as f32 + 0.0722 * pixel.b as f32;
+            let luma_u8 = luma.round() as u8;
+
+            if luma_u8 == 0 {
+                let v = table[0];
+                *pixel = RGB { r: v, g: v, b: v };
+                continue;
+            }
+
+            let gain = table[luma_u8 as usize] as f32 / luma;
+            let scale = |v: u8| (v as f32 * gain).round().clamp(0.0, 255.0) as u8;
+            pixel.r = scale(pixel.r);
+            pixel.g = scale(pixel.g);
+            pixel.b = scale(pixel.b);
+        }
+    }
+}
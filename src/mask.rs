@@ -0,0 +1,360 @@
+use crate::blur::blur_buffer;
+use crate::canvas::{Canvas, Draw, Rect};
+use crate::color::{RGB, RGBA};
+use crate::raster::SpanRasterizer;
+use crate::target::PixelTarget;
+
+/// A single channel coverage buffer, where `0` means not covered and `255` fully covered.
+///
+/// Masks store one byte per pixel and are used to describe the area of shapes independent of any color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    width: usize,
+    height: usize,
+
+    data: Vec<u8>,
+}
+
+impl Mask {
+    /// Creates a new mask covering nothing.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; width * height],
+        }
+    }
+
+    /// Returns the width of the mask.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the mask.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns a reference to the coverage values of the mask stored row by row.
+    pub fn data(&self) -> &Vec<u8> {
+        &self.data
+    }
+
+    /// Returns a mutable reference to the coverage values of the mask stored row by row.
+    pub fn data_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.data
+    }
+
+    /// Checks if the pixel specified lays inside of the mask.
+    pub fn pixel_inside(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    }
+
+    /// Returns the coverage at the specified position.
+    ///
+    /// Returns `None` if position is not inside the mask.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::mask::Mask;
+    ///
+    /// let mut mask = Mask::new(64, 64);
+    /// mask.set_at(10, 20, 128);
+    ///
+    /// assert_eq!(Some(128), mask.get_at(10, 20));
+    /// assert_eq!(None, mask.get_at(-1, 20));
+    /// assert_eq!(None, mask.set_at(64, 20, 255));
+    /// ```
+    pub fn get_at(&self, x: isize, y: isize) -> Option<u8> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        Some(self.data[y as usize * self.width + x as usize])
+    }
+
+    /// Returns the coverage at the specified position.
+    ///
+    /// Returns `None` if position is not inside the mask.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `get_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        match x < self.width && y < self.height {
+            true => Some(self.data[y * self.width + x]),
+            false => None,
+        }
+    }
+
+    /// Sets the coverage at the specified position.
+    ///
+    /// Returns `None` if position is not inside the mask.
+    pub fn set_at(&mut self, x: isize, y: isize, coverage: u8) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.data[y as usize * self.width + x as usize] = coverage;
+        Some(())
+    }
+
+    /// Sets the coverage at the specified position.
+    ///
+    /// Returns `None` if position is not inside the mask.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `set_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn set(&mut self, x: usize, y: usize, coverage: u8) -> Option<()> {
+        match x < self.width && y < self.height {
+            true => {
+                self.data[y * self.width + x] = coverage;
+                Some(())
+            }
+            false => None,
+        }
+    }
+
+    /// Fully covers a rectangle. Parts outside of the mask are clipped.
+    pub fn fill_rect(&mut self, rect: Rect) {
+        let Some((x, y, w, h)) = rect.clip(self.width, self.height) else {
+            return;
+        };
+
+        for row in self.data.chunks_mut(self.width).skip(y).take(h) {
+            row[x..x + w].fill(255);
+        }
+    }
+
+    /// Fully covers a solid circle. Parts outside of the mask are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::mask::Mask;
+    ///
+    /// let mut mask = Mask::new(64, 64);
+    /// mask.fill_circle(32, 32, 10);
+    ///
+    /// assert_eq!(Some(255), mask.get_at(32, 32));
+    /// assert_eq!(Some(0), mask.get_at(0, 0));
+    /// ```
+    pub fn fill_circle(&mut self, x: isize, y: isize, r: u32) {
+        let mut spans = SpanRasterizer::new();
+        spans.add_circle(x, y, r);
+        spans.fill_mask(self);
+    }
+
+    /// Fully covers a solid polygon with vertices in the specified order (clockwise / anti-clockwise).
+    /// Parts outside of the mask are clipped.
+    pub fn fill_polygon(&mut self, vertices: &[(isize, isize)], clockwise: bool) {
+        let mut spans = SpanRasterizer::new();
+        spans.add_polygon(vertices, clockwise);
+        spans.fill_mask(self);
+    }
+
+    /// Adds the coverage of another mask of the same size, like layering translucent colors.
+    pub(crate) fn cover(&mut self, other: &Mask) {
+        for (v, &c) in self.data.iter_mut().zip(&other.data) {
+            *v = union(*v, c);
+        }
+    }
+
+    /// Inverts the coverage of the mask.
+    pub fn invert(&mut self) {
+        for v in self.data.iter_mut() {
+            *v = 255 - *v;
+        }
+    }
+
+    /// Softens the edges of the mask by blurring the coverage with the specified radius.
+    ///
+    /// Three box blur passes are used to approximate a gaussian blur, pixels outside of the mask repeat the closest edge pixel
+    /// just like with [`Canvas::gaussian_blur`](crate::canvas::Canvas::gaussian_blur).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::mask::Mask;
+    ///
+    /// let mut mask = Mask::new(64, 64);
+    /// mask.fill_circle(32, 32, 10);
+    /// mask.blur(2);
+    ///
+    /// assert_eq!(Some(255), mask.get_at(32, 32));
+    /// assert!(mask.get_at(32, 42).unwrap() > 0 && mask.get_at(32, 42).unwrap() < 255);
+    /// ```
+    pub fn blur(&mut self, radius: u32) {
+        blur_buffer(&mut self.data, self.width, self.height, radius as usize);
+    }
+}
+
+impl PixelTarget for Mask {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Adds the alpha of the color to the coverage of the span, like layering translucent colors.
+    fn blend_span(&mut self, y: usize, x1: usize, x2: usize, color: RGBA) {
+        let start = y * self.width;
+        for v in &mut self.data[start + x1..start + x2] {
+            *v = union(*v, color.a);
+        }
+    }
+}
+
+/// Combines two coverage values as if the areas were drawn on top of each other.
+fn union(a: u8, b: u8) -> u8 {
+    (a as u32 + (b as u32 * (255 - a as u32) + 127) / 255) as u8
+}
+
+/// Rasterizes anything implementing the [`Draw`] trait into a [`Mask`] of its coverage.
+///
+/// The coverage is independent of the colors used by the drawable, translucent colors result in partial coverage.
+/// Basic shapes like the ones in [`drawables`](crate::drawables) are drawn into the mask directly,
+/// other drawables are drawn onto temporary canvases, see [`Draw::draw_mask`].
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::{BLACK, RGBA};
+/// use drawing_stuff::drawables::Circle;
+/// use drawing_stuff::mask::rasterize_mask;
+///
+/// let circle = Circle { center: (32, 32), radius: 10, solid: true, color: BLACK };
+/// let mask = rasterize_mask(64, 64, &circle);
+///
+/// assert_eq!(Some(255), mask.get_at(32, 32));
+/// assert_eq!(Some(0), mask.get_at(0, 0));
+///
+/// let translucent = Circle { color: RGBA::new(0, 0, 0, 128), ..circle };
+/// assert_eq!(Some(128), rasterize_mask(64, 64, &translucent).get_at(32, 32));
+/// ```
+pub fn rasterize_mask<T>(width: usize, height: usize, drawable: &T) -> Mask
+where
+    T: Draw,
+{
+    let mut mask = Mask::new(width, height);
+    drawable.draw_mask(&mut mask);
+    mask
+}
+
+/// Rasterizes a drawable into its premultiplied colors, which is the drawable drawn onto black, and its coverage.
+pub(crate) fn rasterize_layer<T>(width: usize, height: usize, drawable: &T) -> (Canvas, Mask)
+where
+    T: Draw + ?Sized,
+{
+    let mut black = Canvas::new(width, height);
+    let mut white = Canvas::new(width, height);
+    white.fill(RGB {
+        r: 255,
+        g: 255,
+        b: 255,
+    });
+
+    drawable.draw(&mut black);
+    drawable.draw(&mut white);
+
+    // drawing with alpha `a` moves black and white `255 * (1 - a)` apart
+    let data = black
+        .buffer()
+        .iter()
+        .zip(white.buffer())
+        .map(|(b, w)| {
+            let spread = (w.r.saturating_sub(b.r))
+                .max(w.g.saturating_sub(b.g))
+                .max(w.b.saturating_sub(b.b));
+            255 - spread
+        })
+        .collect();
+
+    let mask = Mask {
+        width,
+        height,
+        data,
+    };
+    (black, mask)
+}
+
+impl Canvas {
+    /// Blends a color onto the canvas weighted by the coverage of a mask placed at the specified position.
+    ///
+    /// Parts of the mask laying outside of the canvas are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    /// use drawing_stuff::mask::Mask;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let mut mask = Mask::new(64, 64);
+    /// mask.fill_circle(32, 32, 20);
+    /// canvas.fill_mask(&mask, 200, 100, WHITE);
+    ///
+    /// assert_eq!(255, canvas.get_at(232, 132).unwrap().r);
+    /// ```
+    pub fn fill_mask(&mut self, mask: &Mask, x: isize, y: isize, color: RGBA) {
+        self.fill_mask_with(mask, x, y, |_, _| color);
+    }
+
+    /// Blends colors onto the canvas weighted by the coverage of a mask placed at the specified position,
+    /// e.g. to fill a shape with a gradient or an image.
+    ///
+    /// The color of every covered pixel is returned by `paint` given the position of the pixel on the canvas.
+    /// Parts of the mask laying outside of the canvas are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::mask::Mask;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let mut mask = Mask::new(64, 64);
+    /// mask.fill_circle(32, 32, 20);
+    /// // fades from black to red from left to right
+    /// canvas.fill_mask_with(&mask, 200, 100, |x, _| RGBA::new((x - 200) as u8 * 4, 0, 0, 255));
+    ///
+    /// assert_eq!(128, canvas.get_at(232, 132).unwrap().r);
+    /// ```
+    pub fn fill_mask_with<F>(&mut self, mask: &Mask, x: isize, y: isize, paint: F)
+    where
+        F: Fn(usize, usize) -> RGBA + Send + Sync,
+    {
+        let Some((dst_x, dst_y, w, h)) =
+            Rect::new(x, y, mask.width, mask.height).clip(self.width(), self.height())
+        else {
+            return;
+        };
+        let src_x = (dst_x as isize - x) as usize;
+        let src_y = (dst_y as isize - y) as usize;
+
+        self.for_each_row_mut(dst_y..dst_y + h, |row, dst| {
+            let start = (src_y + row - dst_y) * mask.width + src_x;
+            let coverage = &mask.data[start..start + w];
+            for (i, (pixel, &c)) in dst[dst_x..dst_x + w].iter_mut().zip(coverage).enumerate() {
+                if c == 0 {
+                    continue;
+                }
+                let color = paint(dst_x + i, row);
+                let alpha = (color.a as u32 * c as u32 / 255) as u8;
+                *pixel = pixel.add_rgba(RGBA { a: alpha, ..color });
+            }
+        });
+    }
+}
@@ -0,0 +1,276 @@
+//! Seeded coherent noise for procedural backgrounds, textures and terrain previews.
+
+use std::f32::consts::TAU;
+
+use crate::canvas::{Canvas, Rect};
+use crate::color::RGB;
+use crate::gradient::Gradient;
+use crate::random::hash2;
+
+/// Algorithm used to generate [`Noise`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseKind {
+    /// Smoothly interpolated random values on a grid, cheap but blocky.
+    Value,
+    /// Interpolated random gradients on a grid.
+    #[default]
+    Perlin,
+    /// Random gradients on a triangular grid, showing less grid artifacts than Perlin noise.
+    Simplex,
+}
+
+/// Seeded coherent noise made of one or more octaves of increasing frequency.
+///
+/// The same seed always produces the same noise.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::noise::{Noise, NoiseKind};
+///
+/// let noise = Noise::new(NoiseKind::Perlin, 42).scale(64.0).octaves(4);
+///
+/// let value = noise.sample(200.0, 100.0);
+/// assert!((0.0..=1.0).contains(&value));
+/// assert_eq!(value, noise.sample(200.0, 100.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Noise {
+    kind: NoiseKind,
+    seed: u64,
+
+    scale: f32,
+    octaves: u32,
+    persistence: f32,
+    lacunarity: f32,
+}
+
+impl Noise {
+    /// Creates a new single octave noise with features roughly 32 pixels in size.
+    pub fn new(kind: NoiseKind, seed: u64) -> Self {
+        Self {
+            kind,
+            seed,
+            scale: 32.0,
+            octaves: 1,
+            persistence: 0.5,
+            lacunarity: 2.0,
+        }
+    }
+
+    /// Sets the size of the largest features in pixels.
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Sets the number of octaves summed up, each adding finer detail.
+    pub fn octaves(mut self, octaves: u32) -> Self {
+        self.octaves = octaves.max(1);
+        self
+    }
+
+    /// Sets the factor the amplitude changes by from one octave to the next.
+    pub fn persistence(mut self, persistence: f32) -> Self {
+        self.persistence = persistence;
+        self
+    }
+
+    /// Sets the factor the frequency changes by from one octave to the next.
+    pub fn lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Returns the value of the noise in `0.0..=1.0` at a position in pixels.
+    pub fn sample(&self, x: f32, y: f32) -> f32 {
+        let mut frequency = 1.0 / self.scale;
+        let mut amplitude = 1.0;
+        let mut sum = 0.0;
+        let mut total = 0.0;
+
+        for octave in 0..self.octaves {
+            let seed = self.seed.wrapping_add(octave as u64);
+            let (x, y) = (x * frequency, y * frequency);
+            let value = match self.kind {
+                NoiseKind::Value => value_noise(x, y, seed),
+                NoiseKind::Perlin => perlin_noise(x, y, seed),
+                NoiseKind::Simplex => simplex_noise(x, y, seed),
+            };
+
+            sum += value * amplitude;
+            total += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.persistence;
+        }
+
+        match total {
+            0.0 => 0.5,
+            _ => (sum / total).clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl Canvas {
+    /// Fills a region of the canvas with grayscale noise.
+    ///
+    /// Parts of the region laying outside of the canvas are ignored.
+    /// The noise is sampled at the pixel centers in canvas coordinates, so neighbouring regions filled with the same noise line up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::noise::{Noise, NoiseKind};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let noise = Noise::new(NoiseKind::Simplex, 42).scale(128.0).octaves(5);
+    /// canvas.fill_noise(Rect::new(0, 0, WIDTH, HEIGHT), &noise);
+    /// ```
+    pub fn fill_noise(&mut self, rect: Rect, noise: &Noise) {
+        self.map_noise(rect, noise, |pixel, value| {
+            let v = (value * 255.0).round() as u8;
+            *pixel = RGB { r: v, g: v, b: v };
+        });
+    }
+
+    /// Fills a region of the canvas with noise mapped through a gradient.
+    ///
+    /// Translucent colors of the gradient are blended onto the canvas.
+    /// See [`Canvas::fill_noise`] for more details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::gradient::Gradient;
+    /// use drawing_stuff::noise::{Noise, NoiseKind};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let terrain = Gradient::new(&[
+    ///     (0.4, RGBA::new(20, 60, 160, 255)),
+    ///     (0.45, RGBA::new(230, 210, 140, 255)),
+    ///     (0.55, RGBA::new(50, 140, 50, 255)),
+    ///     (0.75, RGBA::new(120, 110, 100, 255)),
+    ///     (0.8, RGBA::new(255, 255, 255, 255)),
+    /// ]);
+    /// let noise = Noise::new(NoiseKind::Perlin, 42).scale(256.0).octaves(6);
+    /// canvas.fill_noise_gradient(Rect::new(0, 0, WIDTH, HEIGHT), &noise, &terrain);
+    /// ```
+    pub fn fill_noise_gradient(&mut self, rect: Rect, noise: &Noise, gradient: &Gradient) {
+        self.map_noise(rect, noise, |pixel, value| {
+            *pixel = pixel.add_rgba(gradient.sample(value));
+        });
+    }
+
+    /// Calls a function with every pixel inside of a region and the value of the noise at its center.
+    fn map_noise<F>(&mut self, rect: Rect, noise: &Noise, f: F)
+    where
+        F: Fn(&mut RGB, f32) + Send + Sync,
+    {
+        let Some((x, y, w, h)) = rect.clip(self.width(), self.height()) else {
+            return;
+        };
+
+        self.for_each_row_mut(y..y + h, |row, pixels| {
+            for (i, pixel) in pixels[x..x + w].iter_mut().enumerate() {
+                f(pixel, noise.sample((x + i) as f32 + 0.5, row as f32 + 0.5));
+            }
+        });
+    }
+}
+
+/// Smoothly interpolated value noise in `0.0..1.0`.
+pub(crate) fn value_noise(x: f32, y: f32, seed: u64) -> f32 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let tx = smoothstep(x - x0);
+    let ty = smoothstep(y - y0);
+    let (x0, y0) = (x0 as i64, y0 as i64);
+
+    let top = hash2(x0, y0, seed) + (hash2(x0 + 1, y0, seed) - hash2(x0, y0, seed)) * tx;
+    let bottom =
+        hash2(x0, y0 + 1, seed) + (hash2(x0 + 1, y0 + 1, seed) - hash2(x0, y0 + 1, seed)) * tx;
+    top + (bottom - top) * ty
+}
+
+/// Perlin gradient noise mapped to `0.0..=1.0`.
+fn perlin_noise(x: f32, y: f32, seed: u64) -> f32 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i64, y0 as i64);
+
+    let corner = |cx: i64, cy: i64| {
+        let (gx, gy) = gradient(cx, cy, seed);
+        gx * (x - cx as f32) + gy * (y - cy as f32)
+    };
+
+    // quintic fade avoids visible creases at the cell borders
+    let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let (tx, ty) = (fade(fx), fade(fy));
+
+    let top = corner(x0, y0) + (corner(x0 + 1, y0) - corner(x0, y0)) * tx;
+    let bottom = corner(x0, y0 + 1) + (corner(x0 + 1, y0 + 1) - corner(x0, y0 + 1)) * tx;
+    let value = top + (bottom - top) * ty;
+
+    // the value of 2D Perlin noise lays within `-sqrt(0.5)..=sqrt(0.5)`
+    (value * std::f32::consts::SQRT_2 * 0.5 + 0.5).clamp(0.0, 1.0)
+}
+
+/// 2D simplex noise mapped to `0.0..=1.0`.
+fn simplex_noise(x: f32, y: f32, seed: u64) -> f32 {
+    let skew = 0.5 * (3.0f32.sqrt() - 1.0);
+    let unskew = (3.0 - 3.0f32.sqrt()) / 6.0;
+
+    let s = (x + y) * skew;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * unskew;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+
+    // the simplex (triangle) containing the point
+    let (i1, j1) = match x0 > y0 {
+        true => (1, 0),
+        false => (0, 1),
+    };
+    let corners = [
+        (0, 0, x0, y0),
+        (i1, j1, x0 - i1 as f32 + unskew, y0 - j1 as f32 + unskew),
+        (1, 1, x0 - 1.0 + 2.0 * unskew, y0 - 1.0 + 2.0 * unskew),
+    ];
+
+    let (i, j) = (i as i64, j as i64);
+    let value: f32 = corners
+        .iter()
+        .map(|&(di, dj, dx, dy)| {
+            let falloff = 0.5 - dx * dx - dy * dy;
+            if falloff <= 0.0 {
+                return 0.0;
+            }
+            let (gx, gy) = gradient(i + di, j + dj, seed);
+            falloff.powi(4) * (gx * dx + gy * dy)
+        })
+        .sum();
+
+    // the value of simplex noise with unit gradients lays within roughly `-0.01..=0.01`
+    (value * 48.0 + 0.5).clamp(0.0, 1.0)
+}
+
+/// Random unit gradient at a grid point.
+fn gradient(x: i64, y: i64, seed: u64) -> (f32, f32) {
+    let angle = hash2(x, y, seed) * TAU;
+    (angle.cos(), angle.sin())
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
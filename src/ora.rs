@@ -0,0 +1,300 @@
+//! OpenRaster (`.ora`) export of layered images.
+//!
+//! OpenRaster files keep every layer with its name, opacity and visibility, so compositions started with this crate
+//! can be finished in image editors like GIMP or Krita. Layers are stored as 32-bit PNG images in an uncompressed zip archive.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::canvas::Canvas;
+use crate::color::{RGB, RGBA};
+use crate::error::CanvasError;
+use crate::mask::Mask;
+use crate::png::{crc32, encode_rgba};
+
+/// Largest width and height of the thumbnail stored alongside the layers.
+const THUMBNAIL_SIZE: usize = 256;
+
+/// A named layer of a layered image.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub name: String,
+    pub canvas: Canvas,
+    /// Coverage of every pixel of the canvas, `None` makes the whole layer opaque.
+    pub alpha: Option<Mask>,
+    /// Opacity of the whole layer from `0.0` to `1.0`.
+    pub opacity: f32,
+    pub visible: bool,
+}
+
+impl Layer {
+    /// Creates a new visible and fully opaque layer.
+    pub fn new(name: &str, canvas: Canvas) -> Self {
+        Self {
+            name: name.to_string(),
+            canvas,
+            alpha: None,
+            opacity: 1.0,
+            visible: true,
+        }
+    }
+
+    /// Sets the coverage of every pixel of the layer.
+    pub fn alpha(mut self, alpha: Mask) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    /// Sets the opacity of the whole layer from `0.0` to `1.0`.
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Sets if the layer is shown.
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Returns the pixels of the layer with their coverage, not including the opacity of the layer.
+    fn pixels(&self) -> Vec<RGBA> {
+        let with_alpha = |c: &RGB, a: u8| RGBA::new(c.r, c.g, c.b, a);
+        let canvas = self.canvas.buffer().iter();
+        match &self.alpha {
+            Some(alpha) => canvas
+                .zip(alpha.data())
+                .map(|(c, &a)| with_alpha(c, a))
+                .collect(),
+            None => canvas.map(|c| with_alpha(c, 255)).collect(),
+        }
+    }
+}
+
+/// Encodes layers, given from bottom to top, as an OpenRaster image.
+///
+/// The image has the size of the layers. Besides the layers the file contains the visible layers merged into one image
+/// and a thumbnail of it, which viewers not supporting layers show instead.
+///
+/// Returns [`CanvasError::ZeroSize`] if there are no layers or they are empty
+/// and [`CanvasError::SizeMismatch`] if the layers or their alpha masks differ in size.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::{RED, WHITE};
+/// use drawing_stuff::mask::Mask;
+/// use drawing_stuff::ora::{self, Layer};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut background = Canvas::new(WIDTH, HEIGHT);
+/// background.fill(WHITE.to_rgb().0);
+///
+/// let mut sun = Canvas::new(WIDTH, HEIGHT);
+/// sun.draw_circle_solid(540, 360, 100, RED);
+/// let mut shape = Mask::new(WIDTH, HEIGHT);
+/// shape.fill_circle(540, 360, 100);
+///
+/// let layers = [
+///     Layer::new("background", background),
+///     Layer::new("sun", sun).alpha(shape).opacity(0.8),
+/// ];
+///
+/// let bytes = ora::encode_ora(&layers).unwrap();
+/// // the uncompressed mimetype comes first
+/// assert_eq!(b"mimetypeimage/openraster", &bytes[30..54]);
+/// ```
+pub fn encode_ora(layers: &[Layer]) -> Result<Vec<u8>, CanvasError> {
+    let first = layers.first().ok_or(CanvasError::ZeroSize)?;
+    let (width, height) = (first.canvas.width(), first.canvas.height());
+    if width == 0 || height == 0 {
+        return Err(CanvasError::ZeroSize);
+    }
+    for layer in layers {
+        let sizes = [
+            Some((layer.canvas.width(), layer.canvas.height())),
+            layer.alpha.as_ref().map(|a| (a.width(), a.height())),
+        ];
+        if let Some(found) = sizes.into_iter().flatten().find(|&s| s != (width, height)) {
+            return Err(CanvasError::SizeMismatch {
+                expected: (width, height),
+                found,
+            });
+        }
+    }
+
+    let mut archive = ZipWriter::new();
+    archive.add("mimetype", b"image/openraster");
+
+    let mut entries = Vec::with_capacity(layers.len());
+    let mut merged = vec![RGBA::new(0, 0, 0, 0); width * height];
+    let mut files = Vec::with_capacity(layers.len());
+    for (i, layer) in layers.iter().enumerate() {
+        let pixels = layer.pixels();
+        let opacity = match layer.opacity.is_finite() {
+            true => layer.opacity.clamp(0.0, 1.0),
+            false => 1.0,
+        };
+        if layer.visible {
+            for (dst, src) in merged.iter_mut().zip(&pixels) {
+                let alpha = (src.a as f32 * opacity).round() as u8;
+                *dst = RGBA { a: alpha, ..*src }.over(*dst);
+            }
+        }
+        files.push((
+            format!("data/layer{i}.png"),
+            encode_rgba(width, height, &pixels),
+        ));
+
+        entries.push(format!(
+            "<layer name=\"{}\" src=\"data/layer{i}.png\" x=\"0\" y=\"0\" opacity=\"{opacity}\" visibility=\"{}\"/>\n",
+            escape_xml(&layer.name),
+            match layer.visible {
+                true => "visible",
+                false => "hidden",
+            },
+        ));
+    }
+
+    // the stack lists the topmost layer first
+    let mut stack = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<image version=\"0.0.5\" w=\"{width}\" h=\"{height}\">\n<stack>\n"
+    );
+    for entry in entries.iter().rev() {
+        stack.push_str(entry);
+    }
+    stack.push_str("</stack>\n</image>\n");
+
+    archive.add("stack.xml", stack.as_bytes());
+    for (name, png) in &files {
+        archive.add(name, png);
+    }
+    archive.add("mergedimage.png", &encode_rgba(width, height, &merged));
+
+    let scale = (THUMBNAIL_SIZE as f64 / width.max(height) as f64).min(1.0);
+    let thumbnail_width = ((width as f64 * scale).round() as usize).max(1);
+    let thumbnail_height = ((height as f64 * scale).round() as usize).max(1);
+    let thumbnail: Vec<RGBA> = (0..thumbnail_height)
+        .flat_map(|y| (0..thumbnail_width).map(move |x| (x, y)))
+        .map(|(x, y)| {
+            let sx = x * width / thumbnail_width;
+            let sy = y * height / thumbnail_height;
+            merged[sy * width + sx]
+        })
+        .collect();
+    archive.add(
+        "Thumbnails/thumbnail.png",
+        &encode_rgba(thumbnail_width, thumbnail_height, &thumbnail),
+    );
+
+    archive.finish()
+}
+
+/// Writes layers, given from bottom to top, to an OpenRaster file at the specified path.
+///
+/// See [`encode_ora`] for more details, its errors are returned as [`io::ErrorKind::InvalidInput`].
+pub fn save_ora<P: AsRef<Path>>(path: P, layers: &[Layer]) -> io::Result<()> {
+    let bytes = encode_ora(layers).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    fs::write(path, bytes)
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Writes a zip archive storing files without compression.
+struct ZipWriter {
+    bytes: Vec<u8>,
+    /// Central directory entries of the files written so far.
+    directory: Vec<u8>,
+    files: usize,
+    /// Set if the archive grew too large for the offsets and sizes of a zip archive.
+    overflow: bool,
+}
+
+impl ZipWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            directory: Vec::new(),
+            files: 0,
+            overflow: false,
+        }
+    }
+
+    fn add(&mut self, name: &str, data: &[u8]) {
+        let (Ok(offset), Ok(size)) = (u32::try_from(self.bytes.len()), u32::try_from(data.len()))
+        else {
+            self.overflow = true;
+            return;
+        };
+        let crc = crc32(data);
+        let name = name.as_bytes();
+
+        // version 1.0, no flags, stored, 1980-01-01 00:00
+        let common = |bytes: &mut Vec<u8>| {
+            bytes.extend_from_slice(&10u16.to_le_bytes());
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+            bytes.extend_from_slice(&0x21u16.to_le_bytes());
+            bytes.extend_from_slice(&crc.to_le_bytes());
+            bytes.extend_from_slice(&size.to_le_bytes());
+            bytes.extend_from_slice(&size.to_le_bytes());
+            bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+        };
+
+        self.bytes.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        common(&mut self.bytes);
+        self.bytes.extend_from_slice(name);
+        self.bytes.extend_from_slice(data);
+
+        self.directory
+            .extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        self.directory.extend_from_slice(&10u16.to_le_bytes());
+        common(&mut self.directory);
+        // no comment, disk 0, no attributes
+        self.directory.extend_from_slice(&[0; 10]);
+        self.directory.extend_from_slice(&offset.to_le_bytes());
+        self.directory.extend_from_slice(name);
+        self.files += 1;
+    }
+
+    fn finish(mut self) -> Result<Vec<u8>, CanvasError> {
+        let offset = u32::try_from(self.bytes.len());
+        let size = u32::try_from(self.directory.len());
+        let (Ok(offset), Ok(size), Ok(files), false) =
+            (offset, size, u16::try_from(self.files), self.overflow)
+        else {
+            return Err(CanvasError::InvalidGeometry(
+                "image too large for a zip archive",
+            ));
+        };
+
+        self.bytes.extend_from_slice(&self.directory);
+        self.bytes.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        self.bytes.extend_from_slice(&[0; 4]);
+        self.bytes.extend_from_slice(&files.to_le_bytes());
+        self.bytes.extend_from_slice(&files.to_le_bytes());
+        self.bytes.extend_from_slice(&size.to_le_bytes());
+        self.bytes.extend_from_slice(&offset.to_le_bytes());
+        self.bytes.extend_from_slice(&0u16.to_le_bytes());
+        Ok(self.bytes)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::canvas::{Canvas, Draw, Rect};
+use crate::color::{PixelOrder, RGB, RGBA};
+use crate::target::PixelTarget;
+
+/// A canvas variant storing its pixels already packed into 32-bit values.
+///
+/// The buffer can be handed to e.g. `minifb::Window::update_with_buffer` directly without converting it every frame.
+/// In return every write packs and every read unpacks the color.
+/// All drawing methods of [`PixelTarget`] are available, [`Draw`] implementations can be drawn using [`PackedCanvas::draw`].
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::color::{PixelOrder, WHITE};
+/// use drawing_stuff::packed::PackedCanvas;
+/// use drawing_stuff::target::PixelTarget;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = PackedCanvas::new(WIDTH, HEIGHT, PixelOrder::XRGB);
+///
+/// canvas.draw_pixel(200, 100, WHITE);
+/// canvas.draw_line(0, 0, 500, 300, WHITE);
+/// canvas.draw_circle_solid(800, 400, 50, WHITE);
+///
+/// // window.update_with_buffer(canvas.buffer(), WIDTH, HEIGHT);
+/// assert_eq!(0x00ffffff, canvas.buffer()[100 * WIDTH + 200]);
+/// assert_eq!(Some(WHITE.to_rgb().0), canvas.get_at(200, 100));
+/// assert_eq!(None, canvas.get_at(-1, 100));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PackedCanvas {
+    width: usize,
+    height: usize,
+    order: PixelOrder,
+
+    buffer: Vec<u32>,
+}
+
+impl PackedCanvas {
+    /// Creates a new black canvas packed in the specified byte order.
+    pub fn new(width: usize, height: usize, order: PixelOrder) -> Self {
+        Self {
+            width,
+            height,
+            order,
+            buffer: vec![order.pack(RGBA::new(0, 0, 0, 255)); width * height],
+        }
+    }
+
+    /// Creates a packed copy of a [`Canvas`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::PixelOrder;
+    /// use drawing_stuff::packed::PackedCanvas;
+    ///
+    /// let canvas = Canvas::new(64, 64);
+    ///
+    /// let packed = PackedCanvas::from_canvas(&canvas, PixelOrder::BGRA);
+    /// assert_eq!(0x000000ff, packed.buffer()[0]);
+    /// ```
+    pub fn from_canvas(canvas: &Canvas, order: PixelOrder) -> Self {
+        Self {
+            width: canvas.width(),
+            height: canvas.height(),
+            order,
+            buffer: canvas.buffer_u32_ordered(order),
+        }
+    }
+
+    /// Returns the width of the canvas.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the height of the canvas.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the byte order the pixels are packed in.
+    pub fn order(&self) -> PixelOrder {
+        self.order
+    }
+
+    /// Returns a reference to the packed pixel buffer of the canvas.
+    pub fn buffer(&self) -> &Vec<u32> {
+        &self.buffer
+    }
+
+    /// Returns a mutable reference to the packed pixel buffer of the canvas.
+    pub fn buffer_mut(&mut self) -> &mut Vec<u32> {
+        &mut self.buffer
+    }
+
+    /// Checks if the pixel specified lays inside of the canvas.
+    pub fn pixel_inside(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width as isize && y >= 0 && y < self.height as isize
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    pub fn get_at(&self, x: isize, y: isize) -> Option<RGB> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        let value = self.buffer[y as usize * self.width + x as usize];
+        Some(self.order.unpack(value).to_rgb().0)
+    }
+
+    /// Returns the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `get_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn get(&self, x: usize, y: usize) -> Option<RGB> {
+        if x >= self.width {
+            return None;
+        }
+        let value = self.buffer.get(y * self.width + x)?;
+        Some(self.order.unpack(*value).to_rgb().0)
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    pub fn set_at(&mut self, x: isize, y: isize, color: RGB) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+        self.buffer[y as usize * self.width + x as usize] =
+            self.order.pack(RGBA::new(color.r, color.g, color.b, 255));
+        Some(())
+    }
+
+    /// Sets the color of the pixel at the specified position.
+    ///
+    /// Returns `None` if position is not inside the canvas.
+    #[deprecated(
+        since = "0.2.2",
+        note = "use `set_at`, which takes signed coordinates like the drawing methods"
+    )]
+    pub fn set(&mut self, x: usize, y: usize, color: RGB) -> Option<()> {
+        if x >= self.width {
+            return None;
+        }
+        *self.buffer.get_mut(y * self.width + x)? =
+            self.order.pack(RGBA::new(color.r, color.g, color.b, 255));
+        Some(())
+    }
+
+    /// Fills the whole canvas with a given color.
+    pub fn fill(&mut self, color: RGB) {
+        let value = self.order.pack(RGBA::new(color.r, color.g, color.b, 255));
+        self.buffer.fill(value);
+    }
+
+    /// Draws anything arbitrary implementing the [`Draw`] trait onto the canvas.
+    ///
+    /// As [`Draw`] works on a [`Canvas`], the canvas is unpacked into a temporary canvas and packed again afterwards,
+    /// which costs about as much as presenting a [`Canvas`] would. The alpha channel ends up opaque.
+    /// Prefer the methods of [`PixelTarget`] when drawing every frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::{PixelOrder, WHITE};
+    /// use drawing_stuff::drawables::Circle;
+    /// use drawing_stuff::packed::PackedCanvas;
+    ///
+    /// let mut canvas = PackedCanvas::new(64, 64, PixelOrder::XRGB);
+    ///
+    /// canvas.draw(&Circle { center: (32, 32), radius: 10, solid: true, color: WHITE });
+    /// assert_eq!(0x00ffffff, canvas.buffer()[32 * 64 + 32]);
+    /// ```
+    pub fn draw<T>(&mut self, drawable: &T)
+    where
+        T: Draw,
+    {
+        let mut canvas = self.to_canvas();
+        drawable.draw(&mut canvas);
+        self.buffer = canvas.buffer_u32_ordered(self.order);
+    }
+
+    /// Copies a [`Canvas`] onto the canvas at the specified position packing its pixels on the way.
+    ///
+    /// Parts of the other canvas laying outside of the canvas are clipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::PixelOrder;
+    /// use drawing_stuff::packed::PackedCanvas;
+    ///
+    /// let mut window = PackedCanvas::new(1080, 720, PixelOrder::XRGB);
+    /// let hud = Canvas::new(200, 50);
+    ///
+    /// window.blit(&hud, 10, 10);
+    /// ```
+    pub fn blit(&mut self, other: &Canvas, x: isize, y: isize) {
+        let Some((dst_x, dst_y, w, h)) =
+            Rect::new(x, y, other.width(), other.height()).clip(self.width, self.height)
+        else {
+            return;
+        };
+        let src_x = (dst_x as isize - x) as usize;
+        let src_y = (dst_y as isize - y) as usize;
+
+        for row in 0..h {
+            let src_start = (src_y + row) * other.width() + src_x;
+            let dst_start = (dst_y + row) * self.width + dst_x;
+            let src = &other.buffer()[src_start..src_start + w];
+            let dst = &mut self.buffer[dst_start..dst_start + w];
+            for (d, c) in dst.iter_mut().zip(src) {
+                *d = self.order.pack(RGBA::new(c.r, c.g, c.b, 255));
+            }
+        }
+    }
+
+    /// Unpacks the canvas into a new [`Canvas`].
+    pub fn to_canvas(&self) -> Canvas {
+        let mut canvas = Canvas::new(self.width, self.height);
+        for (pixel, value) in canvas.buffer_mut().iter_mut().zip(&self.buffer) {
+            *pixel = self.order.unpack(*value).to_rgb().0;
+        }
+        canvas
+    }
+}
+
+impl PixelTarget for PackedCanvas {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Blends a color onto a span of a row.
+    ///
+    /// If the byte order has an alpha channel the color is composited using [`RGBA::over`],
+    /// so translucent pixels of the canvas stay translucent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::color::{PixelOrder, RGBA};
+    /// use drawing_stuff::packed::PackedCanvas;
+    /// use drawing_stuff::target::PixelTarget;
+    ///
+    /// let mut canvas = PackedCanvas::new(64, 64, PixelOrder::RGBA);
+    /// canvas.buffer_mut().fill(0);
+    ///
+    /// canvas.draw_pixel(10, 10, RGBA::new(255, 0, 0, 128));
+    /// assert_eq!(0xff000080, canvas.buffer()[10 * 64 + 10]);
+    /// ```
+    fn blend_span(&mut self, y: usize, x1: usize, x2: usize, color: RGBA) {
+        let start = y * self.width;
+        let span = &mut self.buffer[start + x1..start + x2];
+        match color.a {
+            255 => span.fill(self.order.pack(color)),
+            _ => {
+                for value in span {
+                    *value = self.order.pack(color.over(self.order.unpack(*value)));
+                }
+            }
+        }
+    }
+}
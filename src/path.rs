@@ -0,0 +1,258 @@
+use crate::canvas::{Canvas, Draw};
+use crate::charts::clip_segment;
+use crate::color::RGBA;
+use crate::mask::rasterize_layer;
+use crate::target::PixelTarget;
+
+/// A curve given by a list of points connected by straight segments.
+///
+/// Smooth curves are approximated by sampling enough points along them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Path {
+    pub points: Vec<(f32, f32)>,
+}
+
+impl Path {
+    /// Creates a new path through the specified points.
+    pub fn new(points: Vec<(f32, f32)>) -> Self {
+        Self { points }
+    }
+
+    /// Returns the total length of the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::path::Path;
+    ///
+    /// let path = Path::new(vec![(0.0, 0.0), (30.0, 0.0), (30.0, 40.0)]);
+    ///
+    /// assert_eq!(70.0, path.length());
+    /// ```
+    pub fn length(&self) -> f32 {
+        self.segments().map(|(a, b)| distance(a, b)).sum()
+    }
+
+    /// Returns the point at the specified distance along the path together with the unit tangent at that point.
+    ///
+    /// Returns `None` if the distance is negative, longer than the path or the path has no length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::path::Path;
+    ///
+    /// let path = Path::new(vec![(0.0, 0.0), (30.0, 0.0), (30.0, 40.0)]);
+    ///
+    /// assert_eq!(Some(((30.0, 10.0), (0.0, 1.0))), path.point_at(40.0));
+    /// ```
+    pub fn point_at(&self, distance_along: f32) -> Option<((f32, f32), (f32, f32))> {
+        if distance_along < 0.0 {
+            return None;
+        }
+
+        let mut remaining = distance_along;
+        for (a, b) in self.segments() {
+            let length = distance(a, b);
+            if length == 0.0 {
+                continue;
+            }
+
+            if remaining <= length {
+                let t = remaining / length;
+                let tangent = ((b.0 - a.0) / length, (b.1 - a.1) / length);
+                return Some(((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t), tangent));
+            }
+            remaining -= length;
+        }
+        None
+    }
+
+    /// Draws an anti-aliased stroke with the specified width along the path onto a target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    /// use drawing_stuff::path::Path;
+    ///
+    /// let mut canvas = Canvas::new(64, 64);
+    ///
+    /// let path = Path::new(vec![(10.0, 10.0), (50.0, 10.0), (50.0, 50.0)]);
+    /// path.stroke(&mut canvas, 3, WHITE);
+    ///
+    /// assert_eq!(255, canvas.get_at(30, 10).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(50, 30).unwrap().r);
+    ///
+    /// // points far outside of the canvas are fine
+    /// Path::new(vec![(10.0, 40.0), (1e30, 40.0)]).stroke(&mut canvas, 3, WHITE);
+    /// assert_eq!(255, canvas.get_at(60, 40).unwrap().r);
+    /// ```
+    pub fn stroke<T>(&self, target: &mut T, width: u32, color: RGBA)
+    where
+        T: PixelTarget + ?Sized,
+    {
+        let points: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|&(x, y)| (x as f64, y as f64))
+            .collect();
+        let segments: Vec<_> = match points.len() {
+            1 => vec![(points[0], points[0])],
+            _ => points.windows(2).map(|w| (w[0], w[1])).collect(),
+        };
+
+        // segments are clipped to a margin around the target, pieces of the stroke only meet outside of it
+        let margin = width as f64 / 2.0 + 2.0;
+        let bounds = (
+            -margin,
+            -margin,
+            target.width() as f64 + margin,
+            target.height() as f64 + margin,
+        );
+        let to_pixel = |(x, y): (f64, f64)| (x.round() as isize, y.round() as isize);
+
+        let mut piece: Vec<(isize, isize)> = Vec::new();
+        for (a, b) in segments {
+            let Some((a, b)) = clip_segment(a, b, bounds) else {
+                continue;
+            };
+            let (a, b) = (to_pixel(a), to_pixel(b));
+            if piece.last() != Some(&a) {
+                target.draw_stroke_aa(&piece, width, color);
+                piece = vec![a];
+            }
+            piece.push(b);
+        }
+        target.draw_stroke_aa(&piece, width, color);
+    }
+
+    fn segments(&self) -> impl Iterator<Item = ((f32, f32), (f32, f32))> + '_ {
+        self.points.windows(2).map(|w| (w[0], w[1]))
+    }
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt()
+}
+
+impl Canvas {
+    /// Stamps a drawable along a path every `spacing` pixels, e.g. for ticks, hatching or decorative borders.
+    ///
+    /// The drawable is drawn once into a temporary layer and centered on each position using the bounds of what it draws.
+    /// If `align_to_tangent` is set, each stamp is rotated to follow the direction of the path,
+    /// with the x axis of the drawable pointing along the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::WHITE;
+    /// use drawing_stuff::drawables::{AnkerType, Rectangle};
+    /// use drawing_stuff::path::Path;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// // railway sleepers
+    /// let sleeper = Rectangle {
+    ///     anker: (0, 0),
+    ///     width: 4,
+    ///     height: 20,
+    ///     anker_type: AnkerType::CORNER,
+    ///     solid: true,
+    ///     color: WHITE,
+    /// };
+    /// let track = Path::new(vec![(100.0, 100.0), (500.0, 100.0), (500.0, 400.0)]);
+    /// canvas.repeat_along_path(&track, &sleeper, 20.0, true);
+    ///
+    /// assert_eq!(255, canvas.get_at(100, 100).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(500, 300).unwrap().r);
+    ///
+    /// // stamps far outside of the canvas are skipped
+    /// let far_away = Path::new(vec![(1e30, 100.0), (1e30, 200.0)]);
+    /// canvas.repeat_along_path(&far_away, &sleeper, 20.0, false);
+    /// ```
+    pub fn repeat_along_path<T>(
+        &mut self,
+        path: &Path,
+        drawable: &T,
+        spacing: f32,
+        align_to_tangent: bool,
+    ) where
+        T: Draw,
+    {
+        if spacing <= 0.0 {
+            return;
+        }
+
+        let (colors, coverage) = rasterize_layer(self.width(), self.height(), drawable);
+        let (width, data) = (coverage.width(), coverage.data());
+
+        // bounds of the covered pixels
+        let covered = || {
+            data.iter()
+                .enumerate()
+                .filter(|(_, &c)| c > 0)
+                .map(|(i, _)| i)
+        };
+        let Some(min_x) = covered().map(|i| i % width).min() else {
+            return;
+        };
+        let max_x = covered().map(|i| i % width).max().unwrap();
+        let min_y = covered().next().unwrap() / width;
+        let max_y = covered().next_back().unwrap() / width;
+
+        let center = (
+            (min_x + max_x + 1) as f32 / 2.0,
+            (min_y + max_y + 1) as f32 / 2.0,
+        );
+        let radius = distance((min_x as f32, min_y as f32), center).ceil() as isize + 1;
+
+        let mut position = 0.0;
+        while let Some((point, tangent)) = path.point_at(position) {
+            position += spacing;
+
+            let (cos, sin) = match align_to_tangent {
+                true => tangent,
+                false => (1.0, 0.0),
+            };
+
+            let r = radius as f32;
+            let visible = point.0 + r >= 0.0
+                && point.0 - r <= self.width() as f32
+                && point.1 + r >= 0.0
+                && point.1 - r <= self.height() as f32;
+            if !visible {
+                continue;
+            }
+
+            let (px, py) = (point.0.round() as isize, point.1.round() as isize);
+            let (canvas_width, canvas_height) = (self.width() as isize, self.height() as isize);
+            let buffer = self.buffer_mut();
+            for y in (py - radius).max(0)..=(py + radius).min(canvas_height - 1) {
+                for x in (px - radius).max(0)..=(px + radius).min(canvas_width - 1) {
+                    // rotate the pixel center back into the layer
+                    let (dx, dy) = (x as f32 + 0.5 - point.0, y as f32 + 0.5 - point.1);
+                    let sx = (dx * cos + dy * sin + center.0).floor();
+                    let sy = (-dx * sin + dy * cos + center.1).floor();
+                    if sx < min_x as f32
+                        || sx > max_x as f32
+                        || sy < min_y as f32
+                        || sy > max_y as f32
+                    {
+                        continue;
+                    }
+
+                    let i = sy as usize * width + sx as usize;
+                    let pixel = &mut buffer[y as usize * width + x as usize];
+                    *pixel = pixel.add_premultiplied(colors.buffer()[i], data[i]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::canvas::{Canvas, Rect};
+use crate::color::RGB;
+
+impl Canvas {
+    /// Fills a region of the canvas with a checkerboard of square cells of alternating colors.
+    ///
+    /// The pattern starts with `color_a` at the top-left corner of the region. Parts of the region laying outside of the canvas are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let light = RGB { r: 200, g: 200, b: 200 };
+    /// let dark = RGB { r: 100, g: 100, b: 100 };
+    /// canvas.fill_checkerboard(Rect::new(0, 0, WIDTH, HEIGHT), 16, light, dark);
+    ///
+    /// assert_eq!(Some(&light), canvas.get_at(0, 0));
+    /// assert_eq!(Some(&dark), canvas.get_at(16, 0));
+    /// assert_eq!(Some(&light), canvas.get_at(16, 16));
+    /// ```
+    pub fn fill_checkerboard(&mut self, rect: Rect, cell: usize, color_a: RGB, color_b: RGB) {
+        let Some((x, y, w, h)) = rect.clip(self.width(), self.height()) else {
+            return;
+        };
+        let cell = cell.max(1);
+        let (origin_x, origin_y) = (rect.x, rect.y);
+
+        self.for_each_row_mut(y..y + h, |row, pixels| {
+            let cell_y = (row as isize - origin_y) as usize / cell;
+            for (i, pixel) in pixels[x..x + w].iter_mut().enumerate() {
+                let cell_x = ((x + i) as isize - origin_x) as usize / cell;
+                *pixel = match (cell_x + cell_y) % 2 {
+                    0 => color_a,
+                    _ => color_b,
+                };
+            }
+        });
+    }
+
+    /// Fills a region of the canvas with straight stripes cycling through the specified colors.
+    ///
+    /// `spacing` is the width of a single stripe in pixels, `angle` rotates the stripes clockwise in radians,
+    /// starting with horizontal stripes at `0.0`. The pattern starts at the top-left corner of the region.
+    /// Parts of the region laying outside of the canvas are ignored. Does nothing if no colors are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let yellow = RGB { r: 255, g: 210, b: 0 };
+    /// let black = RGB { r: 0, g: 0, b: 0 };
+    /// let angle = std::f32::consts::FRAC_PI_4;
+    /// canvas.fill_stripes(Rect::new(0, 0, WIDTH, HEIGHT), 20.0, angle, &[yellow, black]);
+    ///
+    /// // stripes running diagonally down to the right
+    /// assert_eq!(canvas.get_at(10, 0), canvas.get_at(110, 100));
+    /// ```
+    pub fn fill_stripes(&mut self, rect: Rect, spacing: f32, angle: f32, colors: &[RGB]) {
+        let Some((x, y, w, h)) = rect.clip(self.width(), self.height()) else {
+            return;
+        };
+        if colors.is_empty() || spacing.is_nan() || spacing <= 0.0 {
+            return;
+        }
+
+        // distance along the normal of the stripes
+        let (sin, cos) = angle.sin_cos();
+        let (normal_x, normal_y) = (-sin, cos);
+        let (origin_x, origin_y) = (rect.x as f32, rect.y as f32);
+
+        self.for_each_row_mut(y..y + h, |row, pixels| {
+            let dy = row as f32 + 0.5 - origin_y;
+            for (i, pixel) in pixels[x..x + w].iter_mut().enumerate() {
+                let dx = (x + i) as f32 + 0.5 - origin_x;
+                let stripe = ((dx * normal_x + dy * normal_y) / spacing).floor() as i64;
+                *pixel = colors[stripe.rem_euclid(colors.len() as i64) as usize];
+            }
+        });
+    }
+}
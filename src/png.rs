@@ -0,0 +1,559 @@
+//! Lossless PNG and animated PNG (APNG) encoding with full 24-bit color.
+//!
+//! Images are compressed with a small built-in deflate encoder, trading some file size for not needing any dependencies.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::animation::Frame;
+use crate::canvas::Canvas;
+use crate::color::{RGB, RGBA};
+use crate::error::CanvasError;
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+impl Canvas {
+    /// Encodes the canvas as a 24-bit PNG image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let bytes = canvas.to_png_bytes();
+    /// assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+    /// ```
+    pub fn to_png_bytes(&self) -> Vec<u8> {
+        let mut bytes = SIGNATURE.to_vec();
+        write_header(&mut bytes, self.width(), self.height(), TRUECOLOR);
+        let image = Region::full(self);
+        write_chunk(&mut bytes, b"IDAT", &zlib_compress(&filter_region(&image)));
+        write_chunk(&mut bytes, b"IEND", &[]);
+        bytes
+    }
+
+    /// Writes the canvas to a PNG file at the specified path.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.save_png("image.png").unwrap();
+    /// ```
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_png_bytes())
+    }
+}
+
+/// Encodes frames as an animated PNG, which unlike GIF keeps the full 24-bit color of every frame.
+///
+/// Each frame is shown until the timestamp of the next one, the last frame as long as the one before it
+/// or a tenth of a second for a single frame. Frames only store the area changed since the previous frame
+/// and unchanged frames extend the previous one. `loops` is the number of times the animation plays, `0` loops forever.
+///
+/// Returns [`CanvasError::ZeroSize`] if there are no frames or they are empty
+/// and [`CanvasError::SizeMismatch`] if the frames differ in size.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::animation::Frame;
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::png;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let frames: Vec<Frame> = (0..10)
+///     .map(|i| {
+///         let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///         canvas.draw_circle_solid(100 + i * 80, 360, 40, WHITE);
+///         Frame { canvas, timestamp: i as f64 / 30.0 }
+///     })
+///     .collect();
+///
+/// let bytes = png::encode_apng(&frames, 0).unwrap();
+/// assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+/// ```
+pub fn encode_apng(frames: &[Frame], loops: u32) -> Result<Vec<u8>, CanvasError> {
+    let first = frames.first().ok_or(CanvasError::ZeroSize)?;
+    let (width, height) = (first.canvas.width(), first.canvas.height());
+    if width == 0 || height == 0 {
+        return Err(CanvasError::ZeroSize);
+    }
+    if let Some(frame) = frames
+        .iter()
+        .find(|f| f.canvas.width() != width || f.canvas.height() != height)
+    {
+        return Err(CanvasError::SizeMismatch {
+            expected: (width, height),
+            found: (frame.canvas.width(), frame.canvas.height()),
+        });
+    }
+
+    // the changed region of every frame and how long it is shown in seconds
+    let mut regions: Vec<(Region, f64)> = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        let delay = match (frames.get(i + 1), i) {
+            (Some(next), _) => next.timestamp - frame.timestamp,
+            (None, 0) => 0.1,
+            (None, _) => frame.timestamp - frames[i - 1].timestamp,
+        };
+        let delay = match delay.is_finite() {
+            true => delay.max(0.0),
+            false => 0.0,
+        };
+
+        let region = match i {
+            0 => Some(Region::full(&frame.canvas)),
+            _ => Region::changed(&frames[i - 1].canvas, &frame.canvas),
+        };
+        match (region, regions.last_mut()) {
+            (Some(region), _) => regions.push((region, delay)),
+            (None, Some(previous)) => previous.1 += delay,
+            (None, None) => unreachable!("the first frame is always stored"),
+        }
+    }
+
+    let mut bytes = SIGNATURE.to_vec();
+    write_header(&mut bytes, width, height, TRUECOLOR);
+
+    let mut control = Vec::with_capacity(8);
+    control.extend_from_slice(&(regions.len() as u32).to_be_bytes());
+    control.extend_from_slice(&loops.to_be_bytes());
+    write_chunk(&mut bytes, b"acTL", &control);
+
+    let mut sequence = 0u32;
+    for (i, (region, delay)) in regions.iter().enumerate() {
+        let milliseconds = (delay * 1000.0).round().min(u16::MAX as f64) as u16;
+
+        let mut control = Vec::with_capacity(26);
+        control.extend_from_slice(&sequence.to_be_bytes());
+        control.extend_from_slice(&(region.width as u32).to_be_bytes());
+        control.extend_from_slice(&(region.height as u32).to_be_bytes());
+        control.extend_from_slice(&(region.x as u32).to_be_bytes());
+        control.extend_from_slice(&(region.y as u32).to_be_bytes());
+        control.extend_from_slice(&milliseconds.to_be_bytes());
+        control.extend_from_slice(&1000u16.to_be_bytes());
+        // keep the frame as base for the next one and overwrite instead of blending
+        control.extend_from_slice(&[0, 0]);
+        write_chunk(&mut bytes, b"fcTL", &control);
+        sequence += 1;
+
+        let data = zlib_compress(&filter_region(region));
+        match i {
+            0 => write_chunk(&mut bytes, b"IDAT", &data),
+            _ => {
+                let mut frame_data = Vec::with_capacity(data.len() + 4);
+                frame_data.extend_from_slice(&sequence.to_be_bytes());
+                frame_data.extend_from_slice(&data);
+                write_chunk(&mut bytes, b"fdAT", &frame_data);
+                sequence += 1;
+            }
+        }
+    }
+
+    write_chunk(&mut bytes, b"IEND", &[]);
+    Ok(bytes)
+}
+
+/// Writes frames to an animated PNG file at the specified path.
+///
+/// See [`encode_apng`] for more details, its errors are returned as [`io::ErrorKind::InvalidInput`].
+pub fn save_apng<P: AsRef<Path>>(path: P, frames: &[Frame], loops: u32) -> io::Result<()> {
+    let bytes =
+        encode_apng(frames, loops).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    fs::write(path, bytes)
+}
+
+/// Encodes pixels with transparency given row by row as a 32-bit PNG image.
+pub(crate) fn encode_rgba(width: usize, height: usize, pixels: &[RGBA]) -> Vec<u8> {
+    let mut bytes = SIGNATURE.to_vec();
+    write_header(&mut bytes, width, height, TRUECOLOR_ALPHA);
+    let data = filter_rows(height, 4, |y, row| {
+        let pixels = &pixels[y * width..(y + 1) * width];
+        row.extend(pixels.iter().flat_map(|c| [c.r, c.g, c.b, c.a]));
+    });
+    write_chunk(&mut bytes, b"IDAT", &zlib_compress(&data));
+    write_chunk(&mut bytes, b"IEND", &[]);
+    bytes
+}
+
+/// A rectangular part of a canvas.
+struct Region<'a> {
+    canvas: &'a Canvas,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Region<'a> {
+    fn full(canvas: &'a Canvas) -> Self {
+        Self {
+            canvas,
+            x: 0,
+            y: 0,
+            width: canvas.width(),
+            height: canvas.height(),
+        }
+    }
+
+    /// The bounding box of all pixels of `current` differing from `previous`, `None` if both are the same.
+    fn changed(previous: &Canvas, current: &'a Canvas) -> Option<Self> {
+        let width = current.width();
+        let rows = previous
+            .buffer()
+            .chunks(width)
+            .zip(current.buffer().chunks(width));
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        for (y, (old, new)) in rows.enumerate() {
+            if old == new {
+                continue;
+            }
+            let first = old.iter().zip(new).position(|(a, b)| a != b)?;
+            let last = width - 1 - old.iter().zip(new).rev().position(|(a, b)| a != b)?;
+            min_x = min_x.min(first);
+            max_x = max_x.max(last);
+            min_y = min_y.min(y);
+            max_y = y;
+        }
+
+        match min_y {
+            usize::MAX => None,
+            _ => Some(Self {
+                canvas: current,
+                x: min_x,
+                y: min_y,
+                width: max_x - min_x + 1,
+                height: max_y - min_y + 1,
+            }),
+        }
+    }
+
+    fn row(&self, y: usize) -> &[RGB] {
+        let start = (self.y + y) * self.canvas.width() + self.x;
+        &self.canvas.buffer()[start..start + self.width]
+    }
+}
+
+const TRUECOLOR: u8 = 2;
+const TRUECOLOR_ALPHA: u8 = 6;
+
+fn write_header(bytes: &mut Vec<u8>, width: usize, height: usize, color_type: u8) {
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits per channel, deflate, adaptive filtering, no interlacing
+    header.extend_from_slice(&[8, color_type, 0, 0, 0]);
+    write_chunk(bytes, b"IHDR", &header);
+}
+
+fn write_chunk(bytes: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend_from_slice(kind);
+    bytes.extend_from_slice(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Serializes the rows of a region as 24-bit pixels, see [`filter_rows`].
+fn filter_region(region: &Region) -> Vec<u8> {
+    filter_rows(region.height, 3, |y, row| {
+        row.extend(region.row(y).iter().flat_map(|c| [c.r, c.g, c.b]));
+    })
+}
+
+/// Serializes rows of pixels with `bpp` bytes each, every row prefixed with the filter producing the smallest residuals.
+///
+/// `row` appends the bytes of row `y` to the empty buffer it is given, all rows need to be equally long.
+fn filter_rows<F>(height: usize, bpp: usize, row: F) -> Vec<u8>
+where
+    F: Fn(usize, &mut Vec<u8>),
+{
+    let mut output = Vec::new();
+    let mut previous = Vec::new();
+    let mut current = Vec::new();
+    let mut candidates = vec![Vec::new(); 5];
+
+    for y in 0..height {
+        current.clear();
+        row(y, &mut current);
+        let stride = current.len();
+        previous.resize(stride, 0);
+        for candidate in candidates.iter_mut() {
+            candidate.resize(stride, 0);
+        }
+
+        for i in 0..stride {
+            let a = match i >= bpp {
+                true => current[i - bpp],
+                false => 0,
+            };
+            let b = previous[i];
+            let c = match i >= bpp {
+                true => previous[i - bpp],
+                false => 0,
+            };
+            let x = current[i];
+            candidates[0][i] = x;
+            candidates[1][i] = x.wrapping_sub(a);
+            candidates[2][i] = x.wrapping_sub(b);
+            candidates[3][i] = x.wrapping_sub(((a as u16 + b as u16) / 2) as u8);
+            candidates[4][i] = x.wrapping_sub(paeth(a, b, c));
+        }
+
+        // residuals closest to zero usually compress best
+        let cost =
+            |row: &[u8]| -> u64 { row.iter().map(|&v| (v as i8).unsigned_abs() as u64).sum() };
+        let best = (0..5).min_by_key(|&f| cost(&candidates[f])).unwrap_or(0);
+
+        output.push(best as u8);
+        output.extend_from_slice(&candidates[best]);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    output
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    match (pa <= pb && pa <= pc, pb <= pc) {
+        (true, _) => a,
+        (false, true) => b,
+        (false, false) => c,
+    }
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = match c & 1 {
+                1 => 0xedb8_8320 ^ (c >> 1),
+                _ => c >> 1,
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &b| {
+        CRC_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    // the sums can not overflow within blocks of this size
+    for block in bytes.chunks(5552) {
+        for &byte in block {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+/// Packs bits into bytes starting at the least significant bit, as deflate and the LZW codes of GIF expect.
+pub(crate) struct BitWriter {
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) buffer: u64,
+    pub(crate) count: u32,
+}
+
+impl BitWriter {
+    pub(crate) fn write(&mut self, bits: u32, count: u32) {
+        self.buffer |= (bits as u64) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Writes a huffman code, which deflate stores starting at the most significant bit.
+    fn write_code(&mut self, code: u32, length: u32) {
+        self.write(code.reverse_bits() >> (32 - length), length);
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+const WINDOW: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const MAX_CHAIN: usize = 32;
+
+/// Compresses data into a zlib stream made of a single deflate block with the fixed huffman codes.
+///
+/// Matches are found greedily through hash chains of the last three bytes.
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter {
+        // deflate without a preset dictionary and a 32K window
+        bytes: vec![0x78, 0x01],
+        buffer: 0,
+        count: 0,
+    };
+    // final block compressed with the fixed codes
+    writer.write(1, 1);
+    writer.write(1, 2);
+
+    let mut head = vec![usize::MAX; 1 << 15];
+    let mut chain = vec![usize::MAX; WINDOW];
+
+    let mut i = 0;
+    while i < data.len() {
+        let (length, distance) = longest_match(data, i, &head, &chain);
+        insert(data, i, &mut head, &mut chain);
+
+        match length >= MIN_MATCH {
+            true => {
+                write_length(&mut writer, length);
+                write_distance(&mut writer, distance);
+                for j in i + 1..i + length {
+                    insert(data, j, &mut head, &mut chain);
+                }
+                i += length;
+            }
+            false => {
+                write_literal(&mut writer, data[i] as u32);
+                i += 1;
+            }
+        }
+    }
+    write_literal(&mut writer, 256);
+
+    let mut bytes = writer.finish();
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    bytes
+}
+
+/// Finds the longest earlier repetition of the bytes at `i` within the window as `(length, distance)`.
+fn longest_match(data: &[u8], i: usize, head: &[usize], chain: &[usize]) -> (usize, usize) {
+    if i + MIN_MATCH > data.len() {
+        return (0, 0);
+    }
+    let max = (data.len() - i).min(MAX_MATCH);
+
+    let mut best = (0, 0);
+    let mut candidate = head[hash(data, i)];
+    for _ in 0..MAX_CHAIN {
+        if candidate == usize::MAX || i - candidate > WINDOW - 1 {
+            break;
+        }
+        let length = data[candidate..]
+            .iter()
+            .zip(&data[i..i + max])
+            .take_while(|(a, b)| a == b)
+            .count();
+        if length > best.0 {
+            best = (length, i - candidate);
+            if length == max {
+                break;
+            }
+        }
+
+        let next = chain[candidate % WINDOW];
+        // entries older than the window were overwritten by newer positions
+        if next == usize::MAX || next >= candidate {
+            break;
+        }
+        candidate = next;
+    }
+    best
+}
+
+/// Remembers position `i` in the hash chains if at least three bytes follow.
+fn insert(data: &[u8], i: usize, head: &mut [usize], chain: &mut [usize]) {
+    if i + MIN_MATCH <= data.len() {
+        let h = hash(data, i);
+        chain[i % WINDOW] = head[h];
+        head[h] = i;
+    }
+}
+
+/// Hashes the three bytes at position `i` to 15 bits.
+fn hash(data: &[u8], i: usize) -> usize {
+    let v = (data[i] as u32) << 16 | (data[i + 1] as u32) << 8 | data[i + 2] as u32;
+    (v.wrapping_mul(0x9e37_79b1) >> 17) as usize
+}
+
+fn write_literal(writer: &mut BitWriter, value: u32) {
+    match value {
+        0..=143 => writer.write_code(0x30 + value, 8),
+        144..=255 => writer.write_code(0x190 + value - 144, 9),
+        256..=279 => writer.write_code(value - 256, 7),
+        _ => writer.write_code(0xc0 + value - 280, 8),
+    }
+}
+
+fn write_length(writer: &mut BitWriter, length: usize) {
+    let code = LENGTH_BASE.partition_point(|&base| base as usize <= length) - 1;
+    write_literal(writer, 257 + code as u32);
+    writer.write(
+        (length - LENGTH_BASE[code] as usize) as u32,
+        LENGTH_EXTRA[code],
+    );
+}
+
+fn write_distance(writer: &mut BitWriter, distance: usize) {
+    let code = DISTANCE_BASE.partition_point(|&base| base as usize <= distance) - 1;
+    writer.write_code(code as u32, 5);
+    writer.write(
+        (distance - DISTANCE_BASE[code] as usize) as u32,
+        DISTANCE_EXTRA[code],
+    );
+}
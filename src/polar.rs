@@ -0,0 +1,432 @@
+//! Polar coordinates for radar charts, polar plots and anything else arranged around a center.
+//!
+//! # Examples
+//!
+//! ```
+//! use drawing_stuff::canvas::Canvas;
+//! use drawing_stuff::color::RGBA;
+//! use drawing_stuff::polar::{AngleDirection, PolarContext};
+//!
+//! const WIDTH: usize = 1080;
+//! const HEIGHT: usize = 720;
+//!
+//! let mut canvas = Canvas::new(WIDTH, HEIGHT);
+//!
+//! // a radar chart starting at the top and going clockwise
+//! let polar = PolarContext::new((540.0, 360.0), 300.0)
+//!     .direction(AngleDirection::Clockwise)
+//!     .start_angle(-std::f64::consts::FRAC_PI_2);
+//!
+//! let values = [0.8, 0.5, 0.9, 0.3, 0.6];
+//! let color = RGBA::new(255, 255, 255, 255);
+//! let step = std::f64::consts::TAU / values.len() as f64;
+//! for (i, &value) in values.iter().enumerate() {
+//!     let next = values[(i + 1) % values.len()];
+//!     polar.draw_polar_line(&mut canvas, (value, i as f64 * step), (next, (i + 1) as f64 * step), 2, color);
+//! }
+//!
+//! // the first value points straight up
+//! assert_eq!((540.0, 120.0), polar.project(0.8, 0.0));
+//! ```
+
+use crate::canvas::{Canvas, Draw, Rect};
+use crate::charts::{sample_adaptive, stroke_aa, Axis, ChartStyle, Plot};
+use crate::color::RGBA;
+use crate::raster::SpanRasterizer;
+use crate::target::PixelTarget;
+
+/// Direction in which angles increase on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AngleDirection {
+    /// Counter-clockwise like in mathematics, even though the y axis of a canvas points down.
+    #[default]
+    CounterClockwise,
+    /// Clockwise like on a clock face or a compass.
+    Clockwise,
+}
+
+/// A polar coordinate system placed onto a canvas.
+///
+/// Points are given as a radius `r` and an angle `theta` in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolarContext {
+    /// Position of the pole on the canvas in pixels.
+    pub origin: (f64, f64),
+    /// Length of a radius of `1.0` in pixels.
+    pub scale: f64,
+    /// Direction in which angles increase.
+    pub direction: AngleDirection,
+    /// Direction of `theta = 0.0` in radians, measured from the positive x axis in the context's direction.
+    pub start_angle: f64,
+}
+
+impl PolarContext {
+    /// Creates a new polar context with counter-clockwise angles starting at the positive x axis.
+    pub fn new(origin: (f64, f64), scale: f64) -> Self {
+        Self {
+            origin,
+            scale,
+            direction: AngleDirection::CounterClockwise,
+            start_angle: 0.0,
+        }
+    }
+
+    /// Sets the direction in which angles increase.
+    pub fn direction(mut self, direction: AngleDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Sets the direction of `theta = 0.0` in radians, measured from the positive x axis in the context's direction.
+    pub fn start_angle(mut self, start_angle: f64) -> Self {
+        self.start_angle = start_angle;
+        self
+    }
+
+    /// Returns the position on the canvas in pixels of a point in polar coordinates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::polar::PolarContext;
+    ///
+    /// let polar = PolarContext::new((100.0, 100.0), 50.0);
+    ///
+    /// let (x, y) = polar.project(1.0, std::f64::consts::FRAC_PI_2);
+    /// assert!((x - 100.0).abs() < 1e-9);
+    /// assert!((y - 50.0).abs() < 1e-9);
+    /// ```
+    pub fn project(&self, r: f64, theta: f64) -> (f64, f64) {
+        let (sin, cos) = (self.start_angle + theta).sin_cos();
+        let length = r * self.scale;
+        let sin = match self.direction {
+            // the y axis of the canvas points down
+            AngleDirection::CounterClockwise => -sin,
+            AngleDirection::Clockwise => sin,
+        };
+        (self.origin.0 + length * cos, self.origin.1 + length * sin)
+    }
+
+    /// Draws an anti-aliased straight line between two points in polar coordinates given as `(r, theta)`.
+    pub fn draw_polar_line(
+        &self,
+        canvas: &mut Canvas,
+        from: (f64, f64),
+        to: (f64, f64),
+        width: u32,
+        color: RGBA,
+    ) {
+        let points = [self.project(from.0, from.1), self.project(to.0, to.1)];
+        stroke_aa(canvas, &points, width, color);
+    }
+
+    /// Draws a solid circle with the specified radius in pixels at a point in polar coordinates.
+    ///
+    /// Points whose circle lies completely outside of the canvas are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::polar::PolarContext;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let polar = PolarContext::new((540.0, 360.0), 300.0);
+    /// let color = RGBA::new(255, 255, 255, 255);
+    ///
+    /// polar.draw_polar_point(&mut canvas, 1.0, 0.0, 5, color);
+    /// polar.draw_polar_point(&mut canvas, 1e300, 0.0, 5, color);
+    ///
+    /// assert_eq!(Some(&color.to_rgb().0), canvas.get_at(840, 360));
+    /// ```
+    pub fn draw_polar_point(
+        &self,
+        canvas: &mut Canvas,
+        r: f64,
+        theta: f64,
+        size: u32,
+        color: RGBA,
+    ) {
+        let (x, y) = self.project(r, theta);
+        let margin = size as f64;
+        let visible = x >= -margin
+            && x <= canvas.width() as f64 + margin
+            && y >= -margin
+            && y <= canvas.height() as f64 + margin;
+        if !visible {
+            return;
+        }
+        canvas.draw_circle_solid(x.round() as isize, y.round() as isize, size, color);
+    }
+
+    /// Strokes the anti-aliased curve `r = f(theta)` over a range of angles.
+    ///
+    /// The curve is sampled adaptively, non-finite radii leave a gap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::polar::PolarContext;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let polar = PolarContext::new((540.0, 360.0), 300.0);
+    /// let color = RGBA::new(255, 255, 255, 255);
+    ///
+    /// // a four-petaled rose
+    /// let rose = |theta: f64| (2.0 * theta).cos();
+    /// polar.plot_r_of_theta(&mut canvas, rose, (0.0, std::f64::consts::TAU), 3, color);
+    ///
+    /// assert_eq!(Some(&color.to_rgb().0), canvas.get_at(840, 360));
+    /// ```
+    pub fn plot_r_of_theta<F>(
+        &self,
+        canvas: &mut Canvas,
+        f: F,
+        theta_range: (f64, f64),
+        width: u32,
+        color: RGBA,
+    ) where
+        F: Fn(f64) -> f64,
+    {
+        let (start, end) = theta_range;
+        // roughly one segment per 4 pixels along the circumference of the unit circle
+        let segments = ((end - start).abs() * self.scale / 4.0).clamp(16.0, 4096.0) as usize;
+
+        let curve = |theta: f64| self.project(f(theta), theta);
+        let points = sample_adaptive(curve, start, end, segments, 0.5);
+        stroke_aa(canvas, &points, width, color);
+    }
+}
+
+/// A series of points in polar coordinates given as `(r, theta)` connected by straight lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolarSeries {
+    /// Points of the series as `(r, theta)` with `theta` in radians. Points with a non-finite coordinate leave a gap.
+    pub points: Vec<(f64, f64)>,
+    /// Connects the last point back to the first one, e.g. for radar charts.
+    pub closed: bool,
+
+    pub width: u32,
+    pub color: RGBA,
+    /// Color of the area between the pole and the series, `None` leaves it empty.
+    ///
+    /// The area is filled as seen from the pole, which suits series going around it once like the ones of radar charts.
+    pub fill: Option<RGBA>,
+}
+
+/// A chart of [`PolarSeries`] around a pole in the center of a rectangle, e.g. a radar chart or directional data.
+///
+/// Gridlines are drawn as circles at the ticks of the radial axis and as spokes evenly dividing the full circle,
+/// the outer circle is drawn in the axis color. Parts of the series beyond the radial axis are clipped at the rectangle of the plot.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::charts::{Axis, ChartStyle, SERIES_COLORS};
+/// use drawing_stuff::color::RGBA;
+/// use drawing_stuff::polar::{AngleDirection, PolarPlot, PolarSeries};
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// // a radar chart of five values starting at the top
+/// let step = std::f64::consts::TAU / 5.0;
+/// let values = [0.8, 0.5, 0.9, 0.3, 0.6];
+/// let plot = PolarPlot {
+///     rect: Rect::new(340, 160, 401, 401),
+///     r_axis: Axis::new(0.0, 1.0),
+///     spokes: 5,
+///     direction: AngleDirection::Clockwise,
+///     start_angle: -std::f64::consts::FRAC_PI_2,
+///     series: vec![PolarSeries {
+///         points: values.iter().enumerate().map(|(i, &v)| (v, i as f64 * step)).collect(),
+///         closed: true,
+///         width: 2,
+///         color: SERIES_COLORS[0],
+///         fill: Some(RGBA { a: 80, ..SERIES_COLORS[0] }),
+///     }],
+///     style: ChartStyle::default(),
+/// };
+/// canvas.draw(&plot);
+///
+/// // the first value points straight up from the center at (540, 360)
+/// assert_eq!(Some(&SERIES_COLORS[0].to_rgb().0), canvas.get_at(540, 200));
+/// // the filled area around the center
+/// assert!(canvas.get_at(540, 340).unwrap().b > 0);
+///
+/// // a value far beyond the radial axis only fills the rectangle of the plot
+/// let mut spike = plot.clone();
+/// spike.series[0].points[1].0 = 1e300;
+/// canvas.draw(&spike);
+/// assert!(canvas.get_at(700, 300).unwrap().b > 0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolarPlot {
+    /// Rectangle containing the plot, the outer circle fits into it.
+    pub rect: Rect,
+    /// Range of radii shown, [`Axis::min`] at the pole and [`Axis::max`] at the outer circle.
+    pub r_axis: Axis,
+    /// Number of spokes evenly dividing the full circle, starting at `theta = 0.0`.
+    pub spokes: usize,
+    /// Direction in which angles increase.
+    pub direction: AngleDirection,
+    /// Direction of `theta = 0.0` in radians, see [`PolarContext::start_angle`].
+    pub start_angle: f64,
+
+    pub series: Vec<PolarSeries>,
+    pub style: ChartStyle,
+}
+
+impl Draw for PolarPlot {
+    fn draw(&self, canvas: &mut Canvas) {
+        self.draw_with(canvas, self.rect, self.r_axis, self.r_axis);
+    }
+}
+
+impl Plot for PolarPlot {
+    /// Returns the radial axis as both axes.
+    fn axes(&self) -> (Axis, Axis) {
+        (self.r_axis, self.r_axis)
+    }
+
+    /// Draws the chart into a rectangle using the x axis as radial axis, the y axis is ignored.
+    fn draw_with(&self, canvas: &mut Canvas, rect: Rect, r_axis: Axis, _: Axis) {
+        let Some((x, y, _, _)) = rect.clip(canvas.width(), canvas.height()) else {
+            return;
+        };
+
+        let radius = (rect.width.min(rect.height).saturating_sub(1) / 2) as f64;
+        let center = (
+            (rect.x - x as isize) as f64 + radius,
+            (rect.y - y as isize) as f64 + radius,
+        );
+        let polar = PolarContext::new(center, 1.0)
+            .direction(self.direction)
+            .start_angle(self.start_angle);
+        let project = |r: f64, theta: f64| polar.project(r_axis.position(r, radius), theta);
+
+        let mut view = canvas.sub_canvas_mut(rect);
+        let (cx, cy) = (center.0.round() as isize, center.1.round() as isize);
+
+        if let Some(background) = self.style.background {
+            let (width, height) = (view.width(), view.height());
+            view.fill_rect(0, 0, width, height, background);
+        }
+
+        if let Some(grid) = self.style.grid_color {
+            for tick in r_axis.ticks(self.style.ticks) {
+                let r = r_axis.position(tick, radius).round();
+                if r > 0.0 && r < radius {
+                    view.draw_circle(cx, cy, r as u32, grid);
+                }
+            }
+            for i in 0..self.spokes {
+                let theta = i as f64 * std::f64::consts::TAU / self.spokes as f64;
+                let (ex, ey) = polar.project(radius, theta);
+                view.draw_line(cx, cy, ex.round() as isize, ey.round() as isize, grid);
+            }
+        }
+
+        for series in &self.series {
+            let mut points: Vec<(f64, f64)> = series
+                .points
+                .iter()
+                .map(|&(r, theta)| project(r, theta))
+                .collect();
+            if series.closed && points.len() > 2 {
+                points.push(points[0]);
+            }
+
+            if let Some(fill) = series.fill {
+                // a fan of triangles around the pole, merged to blend every pixel once
+                let bounds = (
+                    -1.0,
+                    -1.0,
+                    view.width() as f64 + 1.0,
+                    view.height() as f64 + 1.0,
+                );
+                let mut spans = SpanRasterizer::new();
+                for pair in points.windows(2) {
+                    if ![pair[0].0, pair[0].1, pair[1].0, pair[1].1]
+                        .iter()
+                        .all(|v| v.is_finite())
+                    {
+                        continue;
+                    }
+
+                    let (a, b) = (pair[0], pair[1]);
+                    let cross =
+                        (a.0 - center.0) * (b.1 - center.1) - (a.1 - center.1) * (b.0 - center.0);
+                    if cross == 0.0 {
+                        continue;
+                    }
+
+                    let clipped = clip_polygon(&[center, a, b], bounds);
+                    if clipped.len() > 2 {
+                        let vertices: Vec<_> = clipped
+                            .iter()
+                            .map(|p| (p.0.round() as isize, p.1.round() as isize))
+                            .collect();
+                        spans.add_polygon(&vertices, cross > 0.0);
+                    }
+                }
+                spans.fill(&mut view, fill);
+            }
+
+            stroke_aa(&mut view, &points, series.width, series.color);
+        }
+
+        view.draw_circle(cx, cy, radius as u32, self.style.axis_color);
+    }
+}
+
+/// Clips a convex polygon to the rectangle `(min_x, min_y, max_x, max_y)` keeping the order of its vertices.
+fn clip_polygon(
+    vertices: &[(f64, f64)],
+    (min_x, min_y, max_x, max_y): (f64, f64, f64, f64),
+) -> Vec<(f64, f64)> {
+    let mut polygon = vertices.to_vec();
+    // each edge of the rectangle as a signed distance, positive inside
+    let edges: [&dyn Fn((f64, f64)) -> f64; 4] =
+        [&|p| p.0 - min_x, &|p| max_x - p.0, &|p| p.1 - min_y, &|p| {
+            max_y - p.1
+        }];
+
+    for distance in edges {
+        let input = std::mem::take(&mut polygon);
+        for (i, &current) in input.iter().enumerate() {
+            let previous = input[(i + input.len() - 1) % input.len()];
+            let (d_previous, d_current) = (distance(previous), distance(current));
+            if (d_previous >= 0.0) != (d_current >= 0.0) {
+                // starting at the vertex inside keeps far away vertices from cancelling out the intersection
+                let (inside, outside, d_inside, d_outside) = match d_previous >= 0.0 {
+                    true => (previous, current, d_previous, d_current),
+                    false => (current, previous, d_current, d_previous),
+                };
+                let t = d_inside / (d_inside - d_outside);
+                polygon.push((
+                    inside.0 + (outside.0 - inside.0) * t,
+                    inside.1 + (outside.1 - inside.1) * t,
+                ));
+            }
+            if d_current >= 0.0 {
+                polygon.push(current);
+            }
+        }
+    }
+    polygon
+}
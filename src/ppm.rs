@@ -0,0 +1,295 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::canvas::Canvas;
+use crate::color::RGB;
+
+impl Canvas {
+    /// Encodes the canvas as a PPM image.
+    ///
+    /// If `plain` is set the ASCII variant (`P3`) is written, otherwise the binary variant (`P6`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let bytes = canvas.to_ppm_bytes(false);
+    /// assert!(bytes.starts_with(b"P6\n1080 720\n255\n"));
+    /// ```
+    pub fn to_ppm_bytes(&self, plain: bool) -> Vec<u8> {
+        let magic = if plain { "P3" } else { "P6" };
+        let mut bytes =
+            format!("{}\n{} {}\n255\n", magic, self.width(), self.height()).into_bytes();
+
+        if plain {
+            for row in self.buffer().chunks(self.width().max(1)) {
+                let line = row
+                    .iter()
+                    .map(|c| format!("{} {} {}", c.r, c.g, c.b))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                bytes.extend_from_slice(line.as_bytes());
+                bytes.push(b'\n');
+            }
+        } else {
+            bytes.reserve(self.buffer().len() * 3);
+            for c in self.buffer() {
+                bytes.extend_from_slice(&[c.r, c.g, c.b]);
+            }
+        }
+
+        bytes
+    }
+
+    /// Writes the canvas to a PPM file at the specified path.
+    ///
+    /// If `plain` is set the ASCII variant (`P3`) is written, otherwise the binary variant (`P6`).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// canvas.save_ppm("image.ppm", false).unwrap();
+    /// ```
+    pub fn save_ppm<P: AsRef<Path>>(&self, path: P, plain: bool) -> io::Result<()> {
+        fs::write(path, self.to_ppm_bytes(plain))
+    }
+
+    /// Decodes a canvas from the bytes of a PNM image.
+    ///
+    /// All netpbm formats `P1` to `P6` are supported. Grayscale and bitmap images are expanded to RGB
+    /// and sample values are rescaled to the range `0..=255`.
+    ///
+    /// Returns `None` if the bytes are not a valid PNM image.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// let bytes = b"P3\n2 1\n255\n255 0 0  0 0 255\n";
+    /// let canvas = Canvas::from_ppm_bytes(bytes).unwrap();
+    ///
+    /// assert_eq!(2, canvas.width());
+    /// assert_eq!(Some(&RGB { r: 0, g: 0, b: 255 }), canvas.get_at(1, 0));
+    ///
+    /// // the header claims more pixels than there is data
+    /// assert!(Canvas::from_ppm_bytes(b"P6\n100000 100000\n255\n").is_none());
+    /// ```
+    pub fn from_ppm_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = PnmReader { bytes, pos: 0 };
+
+        let magic = reader.token()?;
+        let (binary, channels, bitmap) = match magic {
+            b"P1" => (false, 1, true),
+            b"P2" => (false, 1, false),
+            b"P3" => (false, 3, false),
+            b"P4" => (true, 1, true),
+            b"P5" => (true, 1, false),
+            b"P6" => (true, 3, false),
+            _ => return None,
+        };
+
+        let width = reader.number()? as usize;
+        let height = reader.number()? as usize;
+        let max = if bitmap { 1 } else { reader.number()? };
+        if max == 0 || max > u16::MAX as u32 {
+            return None;
+        }
+
+        let scale = |v: u32| -> Option<u8> {
+            if v > max {
+                return None;
+            }
+            Some(((v * 255 + max / 2) / max) as u8)
+        };
+
+        // every sample takes up at least one byte, so the header can't claim more pixels than the input holds
+        let pixels = width.checked_mul(height)?;
+        let data_len = match (binary, bitmap) {
+            (true, true) => width.div_ceil(8).checked_mul(height)?,
+            (true, false) if max >= 256 => pixels.checked_mul(channels * 2)?,
+            _ => pixels.checked_mul(channels)?,
+        };
+        if data_len > reader.remaining() {
+            return None;
+        }
+
+        let mut canvas = Canvas::new(width, height);
+
+        if bitmap {
+            // in bitmaps 1 is black and 0 is white
+            let bit = |v: u32| if v == 0 { 255 } else { 0 };
+
+            if binary {
+                reader.skip_single_whitespace()?;
+                let row_bytes = width.div_ceil(8);
+                for y in 0..height {
+                    let row = reader.take(row_bytes)?;
+                    for x in 0..width {
+                        let v = bit(((row[x / 8] >> (7 - x % 8)) & 1) as u32);
+                        canvas[(x, y)] = RGB { r: v, g: v, b: v };
+                    }
+                }
+            } else {
+                for y in 0..height {
+                    for x in 0..width {
+                        let v = bit(reader.bit()?);
+                        canvas[(x, y)] = RGB { r: v, g: v, b: v };
+                    }
+                }
+            }
+
+            return Some(canvas);
+        }
+
+        if binary {
+            reader.skip_single_whitespace()?;
+        }
+
+        let sample = |reader: &mut PnmReader| -> Option<u8> {
+            let v = match binary {
+                true if max < 256 => reader.take(1)?[0] as u32,
+                true => {
+                    let b = reader.take(2)?;
+                    (b[0] as u32) << 8 | b[1] as u32
+                }
+                false => reader.number()?,
+            };
+            scale(v)
+        };
+
+        for y in 0..height {
+            for x in 0..width {
+                let color = match channels {
+                    1 => {
+                        let v = sample(&mut reader)?;
+                        RGB { r: v, g: v, b: v }
+                    }
+                    _ => RGB {
+                        r: sample(&mut reader)?,
+                        g: sample(&mut reader)?,
+                        b: sample(&mut reader)?,
+                    },
+                };
+                canvas[(x, y)] = color;
+            }
+        }
+
+        Some(canvas)
+    }
+
+    /// Reads a canvas from a PNM file at the specified path.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the file is not a valid PNM image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// let canvas = Canvas::load_ppm("image.ppm").unwrap();
+    /// ```
+    pub fn load_ppm<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_ppm_bytes(&bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid PNM image"))
+    }
+}
+
+/// Minimal cursor over the bytes of a PNM image.
+struct PnmReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PnmReader<'a> {
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if b == b'#' {
+                while let Some(&b) = self.bytes.get(self.pos) {
+                    if b == b'\n' {
+                        break;
+                    }
+                    self.pos += 1;
+                }
+            } else if b.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Skips the single whitespace separating the header from binary data.
+    fn skip_single_whitespace(&mut self) -> Option<()> {
+        match self.bytes.get(self.pos)?.is_ascii_whitespace() {
+            true => {
+                self.pos += 1;
+                Some(())
+            }
+            false => None,
+        }
+    }
+
+    /// Returns the next whitespace separated token.
+    fn token(&mut self) -> Option<&'a [u8]> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if b.is_ascii_whitespace() || b == b'#' {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        match start == self.pos {
+            true => None,
+            false => Some(&self.bytes[start..self.pos]),
+        }
+    }
+
+    /// Returns the next ASCII decimal number.
+    fn number(&mut self) -> Option<u32> {
+        std::str::from_utf8(self.token()?).ok()?.parse().ok()
+    }
+
+    /// Returns the next ASCII bit of a plain bitmap, which need not be separated by whitespace.
+    fn bit(&mut self) -> Option<u32> {
+        self.skip_whitespace();
+        let v = match self.bytes.get(self.pos)? {
+            b'0' => 0,
+            b'1' => 1,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(v)
+    }
+
+    /// Returns the number of bytes left.
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.pos
+    }
+
+    /// Returns the next `n` raw bytes.
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let slice = self.bytes.get(self.pos..self.pos + n)?;
+        self.pos += n;
+        Some(slice)
+    }
+}
@@ -0,0 +1,196 @@
+use crate::animation::Recorder;
+use crate::canvas::{Canvas, Filter};
+use crate::color::{PixelOrder, RGB};
+
+/// How a [`Canvas`] gets fitted into a presentation target of a different size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scaling {
+    /// Draws the canvas unscaled in the center of the target.
+    None,
+    /// Stretches the canvas over the whole target ignoring its aspect ratio.
+    Stretch,
+    /// Scales the canvas as large as possible while preserving its aspect ratio (letterboxing).
+    Fit,
+    /// Scales the canvas by the largest integer factor that fits into the target.
+    Integer,
+}
+
+/// Options used when presenting a [`Canvas`] into a target buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresentOptions {
+    pub scaling: Scaling,
+    pub filter: Filter,
+
+    /// Color of the area not covered by the canvas.
+    pub background: RGB,
+}
+
+impl Default for PresentOptions {
+    fn default() -> Self {
+        Self {
+            scaling: Scaling::Integer,
+            filter: Filter::Nearest,
+            background: RGB { r: 0, g: 0, b: 0 },
+        }
+    }
+}
+
+impl Canvas {
+    /// Computes where the canvas ends up when presented into a target of the specified size.
+    ///
+    /// Returns the position and size `(x, y, width, height)` of the canvas inside of the target.
+    /// The result may exceed the target if it is smaller than the canvas and `Scaling::None` is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::present::Scaling;
+    ///
+    /// let canvas = Canvas::new(320, 180);
+    ///
+    /// let rect = canvas.present_rect(1080, 720, Scaling::Integer);
+    /// assert_eq!((60, 90, 960, 540), rect);
+    /// ```
+    pub fn present_rect(
+        &self,
+        width: usize,
+        height: usize,
+        scaling: Scaling,
+    ) -> (isize, isize, usize, usize) {
+        let (w, h) = match scaling {
+            Scaling::None => (self.width(), self.height()),
+            Scaling::Stretch => (width, height),
+            Scaling::Fit => {
+                let scale = f64::min(
+                    width as f64 / self.width().max(1) as f64,
+                    height as f64 / self.height().max(1) as f64,
+                );
+                (
+                    (self.width() as f64 * scale).round() as usize,
+                    (self.height() as f64 * scale).round() as usize,
+                )
+            }
+            Scaling::Integer => {
+                let scale =
+                    usize::min(width / self.width().max(1), height / self.height().max(1)).max(1);
+                (self.width() * scale, self.height() * scale)
+            }
+        };
+
+        let x = (width as isize - w as isize) / 2;
+        let y = (height as isize - h as isize) / 2;
+
+        (x, y, w, h)
+    }
+
+    /// Presents the canvas into a 32-bit target buffer in the format `0RGB`.
+    ///
+    /// This is the scaled counterpart of [`Canvas::buffer_u32`] for displaying a canvas in a window of a different size.
+    /// Does nothing if the target buffer is smaller than `width * height`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Filter};
+    /// use drawing_stuff::present::{PresentOptions, Scaling};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(320, 180);
+    /// let mut window_buffer = vec![0u32; WIDTH * HEIGHT];
+    ///
+    /// let options = PresentOptions {
+    ///     scaling: Scaling::Fit,
+    ///     filter: Filter::Bilinear,
+    ///     ..Default::default()
+    /// };
+    /// canvas.present_into(&mut window_buffer, WIDTH, HEIGHT, &options);
+    /// ```
+    pub fn present_into(
+        &self,
+        target: &mut [u32],
+        width: usize,
+        height: usize,
+        options: &PresentOptions,
+    ) {
+        if target.len() < width * height {
+            return;
+        }
+
+        let pack = |c: RGB| c.to_u32(PixelOrder::XRGB);
+        let background = pack(options.background);
+
+        let (x, y, w, h) = self.present_rect(width, height, options.scaling);
+        if w == 0 || h == 0 || self.width() == 0 || self.height() == 0 {
+            target[..width * height].fill(background);
+            return;
+        }
+
+        let scale_x = self.width() as f32 / w as f32;
+        let scale_y = self.height() as f32 / h as f32;
+
+        for ty in 0..height {
+            let row = &mut target[ty * width..(ty + 1) * width];
+
+            let dy = ty as isize - y;
+            if dy < 0 || dy >= h as isize {
+                row.fill(background);
+                continue;
+            }
+            let sy = (dy as f32 + 0.5) * scale_y;
+
+            for (tx, pixel) in row.iter_mut().enumerate() {
+                let dx = tx as isize - x;
+                if dx < 0 || dx >= w as isize {
+                    *pixel = background;
+                    continue;
+                }
+                let sx = (dx as f32 + 0.5) * scale_x;
+
+                *pixel = pack(self.sample(sx, sy, options.filter));
+            }
+        }
+    }
+
+    /// Presents the canvas into a 32-bit target buffer like [`Canvas::present_into`] and offers it to a [`Recorder`].
+    ///
+    /// Calling this instead of [`Canvas::present_into`] in a window loop records the interactive session,
+    /// throttled by the settings of the recorder, see [`Recorder::record`].
+    /// The canvas is recorded at its own size, independent of the scaling used for presenting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::animation::{Recorder, Timing};
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::present::PresentOptions;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(320, 180);
+    /// let mut window_buffer = vec![0u32; WIDTH * HEIGHT];
+    /// // record every other presented frame
+    /// let mut recorder = Recorder::new(Timing::FixedFps(30.0)).every_nth(2);
+    ///
+    /// for _ in 0..10 {
+    ///     canvas.present_recorded(&mut window_buffer, WIDTH, HEIGHT, &PresentOptions::default(), &mut recorder);
+    /// }
+    ///
+    /// assert_eq!(5, recorder.len());
+    /// let apng = recorder.encode_apng(0).unwrap();
+    /// ```
+    pub fn present_recorded(
+        &self,
+        target: &mut [u32],
+        width: usize,
+        height: usize,
+        options: &PresentOptions,
+        recorder: &mut Recorder,
+    ) {
+        self.present_into(target, width, height, options);
+        recorder.record(self);
+    }
+}
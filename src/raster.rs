@@ -0,0 +1,502 @@
+use crate::canvas::Rect;
+use crate::color::RGBA;
+use crate::mask::Mask;
+use crate::target::PixelTarget;
+
+/// Iterator over the pixels of a line, see [`line_points`].
+#[derive(Debug, Clone)]
+pub struct LinePoints {
+    x: isize,
+    y: isize,
+    steep: bool,
+    step: isize,
+
+    a: isize,
+    b: isize,
+    p: isize,
+
+    remaining: usize,
+}
+
+impl Iterator for LinePoints {
+    type Item = (isize, isize);
+
+    fn next(&mut self) -> Option<(isize, isize)> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let point = (self.x, self.y);
+
+        let offset = match self.p < 0 {
+            true => {
+                self.p += self.a;
+                0
+            }
+            false => {
+                self.p += self.b;
+                self.step
+            }
+        };
+        match self.steep {
+            true => {
+                self.y += 1;
+                self.x += offset;
+            }
+            false => {
+                self.x += 1;
+                self.y += offset;
+            }
+        }
+
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for LinePoints {}
+
+/// Returns an iterator over the pixels of the line between two points using Bresenham's algorithm.
+///
+/// Both end points are included. The points are ordered from left to right,
+/// or from top to bottom for lines steeper than 45 degrees.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::raster::line_points;
+///
+/// let points = line_points(0, 0, 4, 2).collect::<Vec<_>>();
+/// assert_eq!(vec![(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)], points);
+/// ```
+pub fn line_points(x1: isize, y1: isize, x2: isize, y2: isize) -> LinePoints {
+    let dx = (x2 - x1).abs();
+    let dy = (y2 - y1).abs();
+    let steep = dy > dx;
+
+    let ((start_x, start_y), (end_x, end_y)) = match (steep, x1 < x2, y1 < y2) {
+        (false, true, _) | (true, _, true) => ((x1, y1), (x2, y2)),
+        _ => ((x2, y2), (x1, y1)),
+    };
+
+    let (major, minor, step) = match steep {
+        true => (dy, dx, if start_x < end_x { 1 } else { -1 }),
+        false => (dx, dy, if start_y < end_y { 1 } else { -1 }),
+    };
+
+    let a = 2 * minor;
+    let b = a - 2 * major;
+
+    LinePoints {
+        x: start_x,
+        y: start_y,
+        steep,
+        step,
+        a,
+        b,
+        p: a - major,
+        remaining: major as usize + 1,
+    }
+}
+
+/// Iterator over the pixels of a circle outline, see [`circle_points`].
+#[derive(Debug, Clone)]
+pub struct CirclePoints {
+    x: isize,
+    y: isize,
+
+    e: isize,
+    x_offset: isize,
+    y_offset: isize,
+    octant: usize,
+}
+
+impl Iterator for CirclePoints {
+    type Item = (isize, isize);
+
+    fn next(&mut self) -> Option<(isize, isize)> {
+        if self.y_offset > self.x_offset {
+            return None;
+        }
+
+        let (x, y, xo, yo) = (self.x, self.y, self.x_offset, self.y_offset);
+        let point = match self.octant {
+            0 => (x + xo, y + yo),
+            1 => (x + xo, y - yo),
+            2 => (x - xo, y + yo),
+            3 => (x - xo, y - yo),
+            4 => (x + yo, y + xo),
+            5 => (x + yo, y - xo),
+            6 => (x - yo, y - xo),
+            _ => (x - yo, y + xo),
+        };
+
+        self.octant += 1;
+        if self.octant == 8 {
+            self.octant = 0;
+
+            self.e += 2 * self.y_offset + 1;
+            self.y_offset += 1;
+            if self.e >= 0 {
+                self.e -= 2 * self.x_offset - 1;
+                self.x_offset -= 1;
+            }
+        }
+
+        Some(point)
+    }
+}
+
+/// Returns an iterator over the pixels of a circle outline using the midpoint circle algorithm.
+///
+/// Points on the borders between the octants of the circle may be returned more than once.
+/// A radius of `0` yields no points.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::raster::circle_points;
+///
+/// let points = circle_points(100, 100, 10).collect::<Vec<_>>();
+/// assert_eq!(true, points.contains(&(110, 100)));
+/// assert_eq!(true, points.contains(&(100, 90)));
+/// ```
+pub fn circle_points(x: isize, y: isize, r: u32) -> CirclePoints {
+    CirclePoints {
+        x,
+        y,
+        e: -(r as isize),
+        x_offset: r as isize,
+        y_offset: if r == 0 { 1 } else { 0 },
+        octant: 0,
+    }
+}
+
+/// Collects the horizontal spans covered by shapes and fills them in a single pass.
+///
+/// Overlapping spans are merged before filling, so every pixel is blended at most once
+/// even if it is covered by multiple shapes, e.g. the caps and the body of a thick line.
+/// New shapes only need to describe which part of each row they cover.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::color::RGBA;
+/// use drawing_stuff::raster::SpanRasterizer;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// // a pill shape
+/// let mut shape = SpanRasterizer::new();
+/// shape.add_circle(200, 100, 20);
+/// shape.add_rect(Rect::new(200, 80, 100, 40));
+/// shape.add_circle(300, 100, 20);
+///
+/// shape.fill(&mut canvas, RGBA { r: 255, g: 255, b: 255, a: 128 });
+/// assert_eq!(128, canvas.get_at(200, 100).unwrap().r);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SpanRasterizer {
+    spans: Vec<(isize, isize, isize)>,
+}
+
+impl SpanRasterizer {
+    /// Creates a new rasterizer without any spans.
+    pub fn new() -> Self {
+        Self { spans: Vec::new() }
+    }
+
+    /// Checks if no spans have been added.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Removes all spans.
+    pub fn clear(&mut self) {
+        self.spans.clear();
+    }
+
+    /// Adds the span `x1..x2` on row `y`. Empty spans are ignored.
+    pub fn add_span(&mut self, y: isize, x1: isize, x2: isize) {
+        if x1 < x2 {
+            self.spans.push((y, x1, x2));
+        }
+    }
+
+    /// Adds an axis aligned rectangle.
+    pub fn add_rect(&mut self, rect: Rect) {
+        let x2 = rect.x + rect.width as isize;
+        for y in rect.y..rect.y + rect.height as isize {
+            self.add_span(y, rect.x, x2);
+        }
+    }
+
+    /// Adds a solid circle.
+    pub fn add_circle(&mut self, x: isize, y: isize, r: u32) {
+        self.add_spans(circle_spans(x, y, r));
+    }
+
+    /// Adds a solid polygon with vertices in the specified order (clockwise / anti-clockwise).
+    pub fn add_polygon(&mut self, vertices: &[(isize, isize)], clockwise: bool) {
+        self.add_spans(polygon_spans(vertices, clockwise));
+    }
+
+    /// Blends a color onto all pixels covered by the spans.
+    ///
+    /// Parts of the spans laying outside of the target are clipped.
+    pub fn fill<T>(&self, target: &mut T, color: RGBA)
+    where
+        T: PixelTarget + ?Sized,
+    {
+        target.fill_spans(&self.merged(), color);
+    }
+
+    /// Fully covers all pixels of a mask covered by the spans.
+    ///
+    /// Parts of the spans laying outside of the mask are clipped.
+    pub fn fill_mask(&self, mask: &mut Mask) {
+        let (width, height) = (mask.width() as isize, mask.height() as isize);
+        let data = mask.data_mut();
+        for (y, x1, x2) in self.merged() {
+            if y < 0 || y >= height {
+                continue;
+            }
+
+            let x1 = x1.clamp(0, width) as usize;
+            let x2 = x2.clamp(0, width) as usize;
+            let start = y as usize * width as usize;
+            data[start + x1..start + x2].fill(255);
+        }
+    }
+
+    fn add_spans(&mut self, spans: Spans) {
+        for (i, (x1, x2)) in spans.left.into_iter().zip(spans.right).enumerate() {
+            self.add_span(spans.start_y + i as isize, x1, x2);
+        }
+    }
+
+    /// Returns the spans sorted by row and position with overlapping spans merged.
+    pub(crate) fn merged(&self) -> Vec<(isize, isize, isize)> {
+        let mut spans = self.spans.clone();
+        spans.sort_unstable();
+
+        let mut merged: Vec<(isize, isize, isize)> = Vec::with_capacity(spans.len());
+        for (y, x1, x2) in spans {
+            match merged.last_mut() {
+                Some(last) if last.0 == y && x1 <= last.2 => last.2 = last.2.max(x2),
+                _ => merged.push((y, x1, x2)),
+            }
+        }
+        merged
+    }
+}
+
+/// Spans `left[i]..right[i]` of a shape on the rows starting at `start_y`.
+#[derive(Debug, Clone, Default)]
+struct Spans {
+    start_y: isize,
+    left: Vec<isize>,
+    right: Vec<isize>,
+}
+
+/// Computes the spans of a solid circle.
+fn circle_spans(x: isize, y: isize, r: u32) -> Spans {
+    if r == 0 {
+        return Spans::default();
+    }
+
+    let mut e = -(r as isize);
+    let mut x_offset = r as isize;
+    let mut y_offset = 0isize;
+
+    let dy = 2 * r;
+
+    let mut left_buff = vec![0isize; dy as usize + 1];
+    let mut right_buff = vec![0isize; dy as usize + 1];
+
+    while y_offset <= x_offset {
+        right_buff[(y + y_offset - (y - r as isize)) as usize] = x + x_offset;
+        right_buff[(y - y_offset - (y - r as isize)) as usize] = x + x_offset;
+        left_buff[(y + y_offset - (y - r as isize)) as usize] = x - x_offset;
+        left_buff[(y - y_offset - (y - r as isize)) as usize] = x - x_offset;
+
+        right_buff[(y + x_offset - (y - r as isize)) as usize] = x + y_offset;
+        right_buff[(y - x_offset - (y - r as isize)) as usize] = x + y_offset;
+        left_buff[(y + x_offset - (y - r as isize)) as usize] = x - y_offset;
+        left_buff[(y - x_offset - (y - r as isize)) as usize] = x - y_offset;
+
+        e += 2 * y_offset + 1;
+        y_offset += 1;
+        if e >= 0 {
+            e -= 2 * x_offset - 1;
+            x_offset -= 1;
+        }
+    }
+
+    left_buff.truncate(dy as usize);
+    right_buff.truncate(dy as usize);
+    Spans {
+        start_y: y - r as isize,
+        left: left_buff,
+        right: right_buff,
+    }
+}
+
+/// Computes the spans of a solid polygon with vertices in the specified order (clockwise / anti-clockwise).
+fn polygon_spans(vertices: &[(isize, isize)], clockwise: bool) -> Spans {
+    if vertices.is_empty() {
+        return Spans::default();
+    }
+
+    let mut min_vert = 0;
+    let mut max_vert = 0;
+    for i in 0..vertices.len() {
+        if vertices[i].1 < vertices[min_vert].1 {
+            min_vert = i;
+        }
+        if vertices[i].1 > vertices[max_vert].1 {
+            max_vert = i;
+        }
+    }
+
+    let (start_x, start_y) = vertices[min_vert];
+
+    let vertices = vertices
+        .iter()
+        .map(|(x, y)| (x - start_x, y - start_y))
+        .collect::<Vec<_>>();
+
+    let dy = (vertices[max_vert].1 + 1) as usize;
+
+    let mut left_buff = vec![0isize; dy];
+    let mut right_buff = vec![0isize; dy];
+
+    let start_vert = if clockwise { min_vert } else { max_vert };
+    let end_vert = if clockwise { max_vert } else { min_vert };
+
+    let mut vert_index = start_vert;
+    loop {
+        let (x1, y1) = vertices[vert_index % vertices.len()];
+        let (x2, y2) = vertices[(vert_index + 1) % vertices.len()];
+
+        polygon_buffer_line(&mut right_buff, true, x1, y1, x2, y2);
+
+        vert_index += 1;
+        if vert_index % vertices.len() == end_vert {
+            break;
+        }
+    }
+
+    let mut vert_index = end_vert;
+    loop {
+        let (x1, y1) = vertices[vert_index % vertices.len()];
+        let (x2, y2) = vertices[(vert_index + 1) % vertices.len()];
+
+        polygon_buffer_line(&mut left_buff, false, x1, y1, x2, y2);
+
+        vert_index += 1;
+        if vert_index % vertices.len() == start_vert {
+            break;
+        }
+    }
+
+    for x in left_buff.iter_mut().chain(right_buff.iter_mut()) {
+        *x += start_x;
+    }
+
+    Spans {
+        start_y,
+        left: left_buff,
+        right: right_buff,
+    }
+}
+
+/// Computes a line for use of drawing solid polygons.
+fn polygon_buffer_line(
+    buff: &mut [isize],
+    right: bool,
+    x1: isize,
+    y1: isize,
+    x2: isize,
+    y2: isize,
+) {
+    let dx = (x2 - x1).abs();
+    let dy = (y2 - y1).abs();
+
+    let abs_m = dy as f32 / dx as f32;
+    match abs_m <= 1.0 {
+        true => {
+            let (start_x, start_y, end_x, end_y) = if x1 < x2 {
+                (x1, y1, x2, y2)
+            } else {
+                (x2, y2, x1, y1)
+            };
+
+            let step = if start_y < end_y { 1 } else { -1 };
+
+            let a = 2 * dy;
+            let b = a - 2 * dx;
+            let mut p = a - dx;
+
+            buff[start_y as usize] = start_x;
+            let mut new_line = false;
+
+            let mut offset = 0isize;
+            for i in 1..=(end_x - start_x) {
+                match p < 0 {
+                    true => {
+                        p += a;
+                    }
+                    false => {
+                        offset += step;
+                        new_line = true;
+                        p += b;
+                    }
+                }
+
+                if right || new_line {
+                    buff[(start_y + offset) as usize] = start_x + i;
+                    new_line = false;
+                }
+            }
+        }
+        false => {
+            let (start_x, start_y, end_x, end_y) = if y1 < y2 {
+                (x1, y1, x2, y2)
+            } else {
+                (x2, y2, x1, y1)
+            };
+
+            let step = if start_x < end_x { 1 } else { -1 };
+
+            let a = 2 * dx;
+            let b = a - 2 * dy;
+            let mut p = a - dy;
+
+            buff[start_y as usize] = start_x;
+
+            let mut offset = 0isize;
+            for i in 1..=(end_y - start_y) {
+                match p < 0 {
+                    true => {
+                        p += a;
+                    }
+                    false => {
+                        offset += step;
+                        p += b;
+                    }
+                }
+
+                buff[(start_y + i) as usize] = start_x + offset;
+            }
+        }
+    }
+}
@@ -0,0 +1,434 @@
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+
+use crate::canvas::Canvas;
+
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Maximum size of the HTTP request of a new connection in bytes.
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+/// Time a new connection has to send its HTTP request before it gets dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+const VIEWER_HTML: &str = r#"<!DOCTYPE html>
+<html>
+<head><meta charset="utf-8"><title>drawing-stuff viewer</title></head>
+<body style="margin:0;background:#222;display:flex;justify-content:center;align-items:center;height:100vh">
+<canvas id="c" style="image-rendering:pixelated;max-width:100%;max-height:100%"></canvas>
+<script>
+const canvas = document.getElementById("c");
+const ctx = canvas.getContext("2d");
+function connect() {
+  const ws = new WebSocket("ws://" + location.host + "/");
+  ws.binaryType = "arraybuffer";
+  ws.onmessage = (e) => {
+    const v = new DataView(e.data);
+    let p = 4;
+    const u32 = () => { const x = v.getUint32(p, true); p += 4; return x; };
+    const w = u32(), h = u32(), count = u32();
+    if (canvas.width !== w || canvas.height !== h) { canvas.width = w; canvas.height = h; }
+    for (let t = 0; t < count; t++) {
+      const x = u32(), y = u32(), tw = u32(), th = u32(), len = u32();
+      const img = ctx.createImageData(tw, th);
+      let o = 0;
+      for (let end = p + len; p < end; p += 4) {
+        for (let n = v.getUint8(p); n >= 0; n--) {
+          img.data[o++] = v.getUint8(p + 1);
+          img.data[o++] = v.getUint8(p + 2);
+          img.data[o++] = v.getUint8(p + 3);
+          img.data[o++] = 255;
+        }
+      }
+      ctx.putImageData(img, x, y);
+    }
+  };
+  ws.onclose = () => setTimeout(connect, 1000);
+}
+connect();
+</script>
+</body>
+</html>
+"#;
+
+/// Serves a canvas to browsers over WebSocket, e.g. to monitor long-running headless renders live.
+///
+/// Opening the address of the server in a browser shows a tiny viewer. Every call to [`RemoteViewer::present`]
+/// sends the changes since the last presented frame as a [`DeltaFrame`](crate::delta::DeltaFrame) to all connected viewers.
+/// The server never blocks on viewers, it only handles connections when presenting.
+/// Viewers which can't keep up skip frames and receive the whole canvas once they caught up.
+///
+/// # Examples
+///
+/// ```no_run
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::color::WHITE;
+/// use drawing_stuff::remote::RemoteViewer;
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+/// let mut viewer = RemoteViewer::bind("127.0.0.1:8080").unwrap();
+///
+/// // open http://127.0.0.1:8080 in a browser
+/// for frame in 0..1000 {
+///     canvas.draw_pixel(frame % WIDTH as isize, 100, WHITE);
+///     viewer.present(&canvas).unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RemoteViewer {
+    listener: TcpListener,
+    requests: Vec<Request>,
+    pages: Vec<Outgoing>,
+    clients: Vec<Client>,
+
+    previous: Option<Canvas>,
+}
+
+impl RemoteViewer {
+    /// Creates a new server listening on the specified address.
+    pub fn bind<A>(addr: A) -> io::Result<Self>
+    where
+        A: ToSocketAddrs,
+    {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            requests: Vec::new(),
+            pages: Vec::new(),
+            clients: Vec::new(),
+            previous: None,
+        })
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Returns the number of connected viewers.
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Accepts pending connections and sends the changes of the canvas to all viewers.
+    ///
+    /// Newly connected viewers receive the whole canvas. Viewers which disconnected are dropped silently.
+    /// Viewers still busy receiving an earlier frame skip this one and get the whole canvas with a later call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
+    ///
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::remote::RemoteViewer;
+    ///
+    /// let mut viewer = RemoteViewer::bind("127.0.0.1:0").unwrap();
+    ///
+    /// let mut client = TcpStream::connect(viewer.local_addr().unwrap()).unwrap();
+    /// client.write_all(b"GET / HTTP/1.1\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+    ///     Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n").unwrap();
+    ///
+    /// std::thread::sleep(std::time::Duration::from_millis(100));
+    /// viewer.present(&Canvas::new(64, 64)).unwrap();
+    /// assert_eq!(1, viewer.client_count());
+    ///
+    /// let mut response = [0; 129];
+    /// client.read_exact(&mut response).unwrap();
+    /// let response = String::from_utf8_lossy(&response);
+    /// assert!(response.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+    ///
+    /// // a connection which never finishes its request doesn't hold up presenting
+    /// let _idle = TcpStream::connect(viewer.local_addr().unwrap()).unwrap();
+    /// let start = std::time::Instant::now();
+    /// viewer.present(&Canvas::new(64, 64)).unwrap();
+    /// assert!(start.elapsed() < std::time::Duration::from_millis(500));
+    /// ```
+    pub fn present(&mut self, canvas: &Canvas) -> io::Result<()> {
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    if stream.set_nonblocking(true).is_ok() {
+                        self.requests.push(Request::new(stream));
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+
+        for request in std::mem::take(&mut self.requests) {
+            match request.poll() {
+                Ok(Handshake::Pending(request)) => self.requests.push(request),
+                Ok(Handshake::Page(page)) => self.pages.push(page),
+                Ok(Handshake::Viewer(client)) => self.clients.push(client),
+                Err(_) => {}
+            }
+        }
+
+        self.pages
+            .retain_mut(|page| matches!(page.flush(), Ok(false)));
+
+        let delta = match (&self.previous, self.clients.is_empty()) {
+            (Some(previous), false) => Some(canvas.delta_since(previous)),
+            _ => None,
+        };
+        let delta = delta
+            .filter(|delta| !delta.is_empty())
+            .map(|delta| binary_message(&delta.to_bytes()));
+        let mut full = None;
+
+        self.clients.retain_mut(|client| {
+            match client.outgoing.flush() {
+                Ok(true) => {}
+                // still busy with an earlier frame, this one gets skipped
+                Ok(false) => {
+                    client.outdated = true;
+                    return true;
+                }
+                Err(_) => return false,
+            }
+
+            let message = match client.outdated {
+                true => full.get_or_insert_with(|| {
+                    binary_message(&canvas.delta_since(&Canvas::new(0, 0)).to_bytes())
+                }),
+                false => match &delta {
+                    Some(delta) => delta,
+                    None => return true,
+                },
+            };
+            client.outdated = false;
+            client.outgoing.buffer.extend_from_slice(message);
+            client.outgoing.flush().is_ok()
+        });
+
+        self.previous = Some(canvas.clone());
+        Ok(())
+    }
+}
+
+/// Bytes waiting to be written to a non-blocking connection.
+#[derive(Debug)]
+struct Outgoing {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    written: usize,
+}
+
+impl Outgoing {
+    fn new(stream: TcpStream, buffer: Vec<u8>) -> Self {
+        Self {
+            stream,
+            buffer,
+            written: 0,
+        }
+    }
+
+    /// Writes as many of the waiting bytes as possible without blocking.
+    ///
+    /// Returns `true` if all of them have been written.
+    fn flush(&mut self) -> io::Result<bool> {
+        while self.written < self.buffer.len() {
+            match self.stream.write(&self.buffer[self.written..]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => self.written += n,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(false),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.buffer.clear();
+        self.written = 0;
+        Ok(true)
+    }
+}
+
+/// A connected viewer.
+#[derive(Debug)]
+struct Client {
+    outgoing: Outgoing,
+    /// Whether the viewer missed a frame and needs the whole canvas.
+    outdated: bool,
+}
+
+/// A new connection whose HTTP request is still being received.
+#[derive(Debug)]
+struct Request {
+    stream: TcpStream,
+    received: Vec<u8>,
+    since: Instant,
+}
+
+/// State of a new connection after receiving what it sent so far.
+enum Handshake {
+    Pending(Request),
+    /// The connection asked for the viewer page and gets closed after receiving it.
+    Page(Outgoing),
+    /// The connection got upgraded to a WebSocket.
+    Viewer(Client),
+}
+
+impl Request {
+    fn new(stream: TcpStream) -> Self {
+        Self {
+            stream,
+            received: Vec::new(),
+            since: Instant::now(),
+        }
+    }
+
+    /// Reads what arrived without blocking and answers the request once it is complete.
+    fn poll(mut self) -> io::Result<Handshake> {
+        let mut chunk = [0; 1024];
+        let header_len = loop {
+            if let Some(end) = self.received.windows(4).position(|w| w == b"\r\n\r\n") {
+                break end;
+            }
+            if self.received.len() > MAX_REQUEST_SIZE {
+                return Err(io::ErrorKind::InvalidData.into());
+            }
+
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => self.received.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    return match self.since.elapsed() < REQUEST_TIMEOUT {
+                        true => Ok(Handshake::Pending(self)),
+                        false => Err(io::ErrorKind::TimedOut.into()),
+                    };
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        };
+
+        let header = String::from_utf8_lossy(&self.received[..header_len]);
+        let key = header.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            match name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                true => Some(value.trim().to_string()),
+                false => None,
+            }
+        });
+
+        let Some(key) = key else {
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                VIEWER_HTML.len(),
+                VIEWER_HTML
+            );
+            return Ok(Handshake::Page(Outgoing::new(
+                self.stream,
+                response.into_bytes(),
+            )));
+        };
+
+        let accept = base64(&sha1(format!("{key}{WEBSOCKET_GUID}").as_bytes()));
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+        );
+        self.stream.set_nodelay(true)?;
+        Ok(Handshake::Viewer(Client {
+            outgoing: Outgoing::new(self.stream, response.into_bytes()),
+            outdated: true,
+        }))
+    }
+}
+
+/// Wraps a payload into an unmasked binary WebSocket frame.
+fn binary_message(payload: &[u8]) -> Vec<u8> {
+    let mut message = vec![0x82];
+    match payload.len() {
+        len @ 0..=125 => message.push(len as u8),
+        len @ 126..=0xffff => {
+            message.push(126);
+            message.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            message.push(127);
+            message.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    message.extend_from_slice(payload);
+    message
+}
+
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (chunk, v) in digest.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&v.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut output = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => output.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char),
+                false => output.push('='),
+            }
+        }
+    }
+    output
+}
@@ -0,0 +1,131 @@
+//! Smoothing of noisy data, e.g. to draw a trend line over the raw values.
+//!
+//! All functions return one smoothed value per input value, so the result can be drawn like the original data.
+//!
+//! # Examples
+//!
+//! ```
+//! use drawing_stuff::smoothing;
+//!
+//! let noisy = [1.0, 3.0, 2.0, 4.0, 3.0, 5.0];
+//!
+//! let trend = smoothing::moving_average(&noisy, 3);
+//! assert_eq!(vec![2.0, 2.0, 3.0, 3.0, 4.0, 4.0], trend);
+//! ```
+
+/// Smooths values with a centered moving average over `window` values.
+///
+/// The window shrinks towards both ends of the data, so the first and last values are averaged over fewer neighbours.
+/// Even windows are widened by one to stay centered.
+pub fn moving_average(values: &[f64], window: usize) -> Vec<f64> {
+    let radius = window / 2;
+
+    let mut prefix = Vec::with_capacity(values.len() + 1);
+    prefix.push(0.0);
+    for &v in values {
+        prefix.push(prefix[prefix.len() - 1] + v);
+    }
+
+    (0..values.len())
+        .map(|i| {
+            let start = i.saturating_sub(radius);
+            let end = (i + radius + 1).min(values.len());
+            (prefix[end] - prefix[start]) / (end - start) as f64
+        })
+        .collect()
+}
+
+/// Smooths values with an exponential moving average.
+///
+/// `alpha` between `0.0` and `1.0` is the weight of the newest value, smaller values smooth more.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::smoothing;
+///
+/// let smoothed = smoothing::exponential(&[0.0, 10.0, 10.0], 0.5);
+/// assert_eq!(vec![0.0, 5.0, 7.5], smoothed);
+/// ```
+pub fn exponential(values: &[f64], alpha: f64) -> Vec<f64> {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mut current = None;
+    values
+        .iter()
+        .map(|&v| {
+            let next = match current {
+                Some(c) => c + alpha * (v - c),
+                None => v,
+            };
+            current = Some(next);
+            next
+        })
+        .collect()
+}
+
+/// Smooths points using LOESS, fitting a weighted line through the neighbourhood of every point.
+///
+/// `bandwidth` between `0.0` and `1.0` is the fraction of all points making up each neighbourhood,
+/// closer neighbours are weighted higher using a tricube kernel. The points don't need to be sorted.
+/// Each point considers all others, so this is meant for up to a few thousand points.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::smoothing;
+///
+/// // points on a line stay on it
+/// let points: Vec<(f64, f64)> = (0..10).map(|x| (x as f64, 2.0 * x as f64 + 1.0)).collect();
+/// let smoothed = smoothing::loess(&points, 0.5);
+///
+/// for ((_, y), (_, expected)) in smoothed.iter().zip(&points) {
+///     assert!((y - expected).abs() < 1e-9);
+/// }
+/// ```
+pub fn loess(points: &[(f64, f64)], bandwidth: f64) -> Vec<(f64, f64)> {
+    let n = points.len();
+    let neighbours = ((bandwidth.clamp(0.0, 1.0) * n as f64).ceil() as usize).clamp(2.min(n), n);
+
+    let mut distances = Vec::with_capacity(n);
+    points
+        .iter()
+        .map(|&(x, y)| {
+            distances.clear();
+            distances.extend(points.iter().map(|p| (p.0 - x).abs()));
+            distances.sort_by(f64::total_cmp);
+            let max_distance = distances
+                .get(neighbours.saturating_sub(1))
+                .copied()
+                .unwrap_or(0.0);
+
+            let mut sums = [0.0; 5]; // weight, wx, wy, wxx, wxy
+            for &(px, py) in points {
+                let weight = match max_distance {
+                    // all neighbours share the position, only they count
+                    0.0 if px == x => 1.0,
+                    0.0 => 0.0,
+                    _ => {
+                        let d = ((px - x).abs() / (max_distance * 1.000_001)).min(1.0);
+                        (1.0 - d * d * d).powi(3)
+                    }
+                };
+                let dx = px - x;
+                sums[0] += weight;
+                sums[1] += weight * dx;
+                sums[2] += weight * py;
+                sums[3] += weight * dx * dx;
+                sums[4] += weight * dx * py;
+            }
+
+            // weighted least squares line evaluated at `x`, falling back to the weighted mean
+            let [w, wx, wy, wxx, wxy] = sums;
+            let denominator = w * wxx - wx * wx;
+            let fitted = match denominator.abs() > f64::EPSILON * w * w {
+                true => (wxx * wy - wx * wxy) / denominator,
+                false if w > 0.0 => wy / w,
+                false => y,
+            };
+            (x, fitted)
+        })
+        .collect()
+}
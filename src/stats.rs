@@ -0,0 +1,192 @@
+use crate::canvas::{Canvas, Filter, Rect};
+use crate::color::RGB;
+
+impl Canvas {
+    /// Returns the average color of a region of the canvas.
+    ///
+    /// Returns `None` if the region is not inside the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::RGB;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.fill(RGB { r: 200, g: 100, b: 0 });
+    ///
+    /// let average = canvas.average_color(Rect::new(0, 0, 50, 50));
+    /// assert_eq!(Some(RGB { r: 200, g: 100, b: 0 }), average);
+    /// ```
+    pub fn average_color(&self, rect: Rect) -> Option<RGB> {
+        let (x, y, w, h) = rect.clip(self.width(), self.height())?;
+
+        let mut sum = [0u64; 3];
+        for row in self.rows().skip(y).take(h) {
+            for pixel in &row[x..x + w] {
+                sum[0] += pixel.r as u64;
+                sum[1] += pixel.g as u64;
+                sum[2] += pixel.b as u64;
+            }
+        }
+
+        let count = (w * h) as u64;
+        let average = |sum: u64| ((sum + count / 2) / count) as u8;
+        Some(RGB {
+            r: average(sum[0]),
+            g: average(sum[1]),
+            b: average(sum[2]),
+        })
+    }
+
+    /// Finds up to `k` dominant colors of a region of the canvas using k-means clustering.
+    ///
+    /// The colors are sorted by the number of pixels they represent, starting with the most common one.
+    /// Returns an empty list if the region is not inside the canvas or `k` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::{Canvas, Rect};
+    /// use drawing_stuff::color::{RGB, WHITE};
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.draw_circle_solid(540, 360, 100, WHITE);
+    ///
+    /// let colors = canvas.dominant_colors(Rect::new(0, 0, WIDTH, HEIGHT), 2);
+    /// assert_eq!(vec![RGB { r: 0, g: 0, b: 0 }, RGB { r: 255, g: 255, b: 255 }], colors);
+    /// ```
+    pub fn dominant_colors(&self, rect: Rect, k: usize) -> Vec<RGB> {
+        let Some((x, y, w, h)) = rect.clip(self.width(), self.height()) else {
+            return Vec::new();
+        };
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let pixels = self
+            .rows()
+            .skip(y)
+            .take(h)
+            .flat_map(|row| &row[x..x + w])
+            .map(|c| [c.r as f32, c.g as f32, c.b as f32])
+            .collect::<Vec<_>>();
+
+        let distance = |a: &[f32; 3], b: &[f32; 3]| {
+            (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+        };
+
+        // deterministic farthest point initialization starting at the first pixel
+        let mut centers = vec![pixels[0]];
+        let mut nearest = pixels
+            .iter()
+            .map(|p| distance(p, &pixels[0]))
+            .collect::<Vec<_>>();
+        while centers.len() < k {
+            let Some((farthest, &d)) = nearest.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1))
+            else {
+                break;
+            };
+            if d == 0.0 {
+                break;
+            }
+
+            let center = pixels[farthest];
+            for (n, p) in nearest.iter_mut().zip(&pixels) {
+                *n = n.min(distance(p, &center));
+            }
+            centers.push(center);
+        }
+
+        let mut counts = vec![0usize; centers.len()];
+        for _ in 0..16 {
+            let mut sums = vec![[0f32; 3]; centers.len()];
+            counts.fill(0);
+
+            for pixel in &pixels {
+                let closest = (0..centers.len())
+                    .min_by(|&a, &b| {
+                        distance(pixel, &centers[a]).total_cmp(&distance(pixel, &centers[b]))
+                    })
+                    .unwrap_or(0);
+                sums[closest][0] += pixel[0];
+                sums[closest][1] += pixel[1];
+                sums[closest][2] += pixel[2];
+                counts[closest] += 1;
+            }
+
+            let mut moved = false;
+            for ((center, sum), &count) in centers.iter_mut().zip(&sums).zip(&counts) {
+                if count == 0 {
+                    continue;
+                }
+
+                let new = sum.map(|v| v / count as f32);
+                moved |= distance(center, &new) > 0.25;
+                *center = new;
+            }
+
+            if !moved {
+                break;
+            }
+        }
+
+        let mut clusters = centers
+            .into_iter()
+            .zip(counts)
+            .filter(|(_, count)| *count > 0)
+            .collect::<Vec<_>>();
+        clusters.sort_by_key(|c| std::cmp::Reverse(c.1));
+
+        clusters
+            .into_iter()
+            .map(|(c, _)| RGB {
+                r: c[0].round() as u8,
+                g: c[1].round() as u8,
+                b: c[2].round() as u8,
+            })
+            .collect()
+    }
+
+    /// Samples `n` evenly spaced colors along the line between two points given in pixel space.
+    ///
+    /// Both end points are included and the colors are interpolated bilinearly.
+    /// Points outside of the canvas are clamped to the closest edge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let canvas = Canvas::new(WIDTH, HEIGHT);
+    ///
+    /// let profile = canvas.sample_along_line((0.0, 360.0), (1080.0, 360.0), 64);
+    /// assert_eq!(64, profile.len());
+    /// ```
+    pub fn sample_along_line(&self, p1: (f32, f32), p2: (f32, f32), n: usize) -> Vec<RGB> {
+        if self.width() == 0 || self.height() == 0 {
+            return Vec::new();
+        }
+
+        (0..n)
+            .map(|i| {
+                let t = match n {
+                    1 => 0.0,
+                    _ => i as f32 / (n - 1) as f32,
+                };
+                let x = p1.0 + (p2.0 - p1.0) * t;
+                let y = p1.1 + (p2.1 - p1.1) * t;
+                self.sample(x, y, Filter::Bilinear)
+            })
+            .collect()
+    }
+}
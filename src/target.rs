@@ -0,0 +1,465 @@
+use crate::canvas::Rect;
+use crate::color::RGBA;
+use crate::raster::{circle_points, line_points, SpanRasterizer};
+
+/// Anything pixels can be drawn onto, e.g. a [`Canvas`](crate::canvas::Canvas)
+/// or a [`CanvasView`](crate::canvas::CanvasView) into a region of one.
+///
+/// Implementors only describe their size and how to blend a color onto a part of a row,
+/// all drawing methods are built on top of that. Drawing outside of the target is clipped.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::{Canvas, Rect};
+/// use drawing_stuff::color::{RGBA, WHITE};
+/// use drawing_stuff::target::PixelTarget;
+///
+/// // works with a canvas as well as a view into one
+/// fn crosshair<T: PixelTarget>(target: &mut T, color: RGBA) {
+///     let (x, y) = (target.width() as isize / 2, target.height() as isize / 2);
+///     target.draw_hline(x - 10, x + 10, y, color);
+///     target.draw_vline(x, y - 10, y + 10, color);
+/// }
+///
+/// const WIDTH: usize = 1080;
+/// const HEIGHT: usize = 720;
+///
+/// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+///
+/// crosshair(&mut canvas, WHITE);
+/// crosshair(&mut canvas.sub_canvas_mut(Rect::new(880, 0, 200, 200)), WHITE);
+///
+/// assert_eq!(255, canvas.get_at(540, 360).unwrap().r);
+/// assert_eq!(255, canvas.get_at(980, 100).unwrap().r);
+/// ```
+pub trait PixelTarget {
+    /// Returns the width of the target.
+    fn width(&self) -> usize;
+
+    /// Returns the height of the target.
+    fn height(&self) -> usize;
+
+    /// Blends a color onto the pixels `x1..x2` of row `y`.
+    ///
+    /// The span is never empty and always inside of the target.
+    fn blend_span(&mut self, y: usize, x1: usize, x2: usize, color: RGBA);
+
+    /// Checks if the pixel specified lays inside of the target.
+    fn pixel_inside(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width() as isize && y >= 0 && y < self.height() as isize
+    }
+
+    /// Blends a color onto spans `(y, x1, x2)` sorted by row.
+    ///
+    /// Parts of the spans laying outside of the target are clipped.
+    fn fill_spans(&mut self, spans: &[(isize, isize, isize)], color: RGBA) {
+        let (width, height) = (self.width() as isize, self.height() as isize);
+        for &(y, x1, x2) in spans {
+            if y < 0 || y >= height {
+                continue;
+            }
+
+            let x1 = x1.clamp(0, width) as usize;
+            let x2 = x2.clamp(0, width) as usize;
+            if x1 < x2 {
+                self.blend_span(y as usize, x1, x2, color);
+            }
+        }
+    }
+
+    /// Draws a single pixel onto the target.
+    ///
+    /// Returns `None` if position is not inside the target.
+    fn draw_pixel(&mut self, x: isize, y: isize, color: RGBA) -> Option<()> {
+        if !self.pixel_inside(x, y) {
+            return None;
+        }
+
+        self.blend_span(y as usize, x as usize, x as usize + 1, color);
+        Some(())
+    }
+
+    /// Draws a line onto the target.
+    ///
+    /// Vertical lines leave out their lower end point, all other lines include both end points.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::RGBA;
+    /// use drawing_stuff::target::PixelTarget;
+    ///
+    /// let mut canvas = Canvas::new(16, 16);
+    /// let color = RGBA::new(255, 255, 255, 255);
+    ///
+    /// canvas.draw_line(2, 2, 2, 8, color);
+    /// assert_eq!(Some(&color.to_rgb().0), canvas.get_at(2, 7));
+    /// assert_eq!(0, canvas.get_at(2, 8).unwrap().r);
+    ///
+    /// // lines far outside of the canvas are clipped before walking their pixels
+    /// canvas.draw_line(0, 4, isize::MAX / 2, isize::MAX / 4, color);
+    /// ```
+    fn draw_line(&mut self, x1: isize, y1: isize, x2: isize, y2: isize, color: RGBA) {
+        if x1 == x2 {
+            let (start_y, end_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
+            if start_y < end_y {
+                self.draw_vline(x1, start_y, end_y - 1, color);
+            }
+            return;
+        }
+        if y1 == y2 {
+            self.draw_hline(x1, x2, y1, color);
+            return;
+        }
+
+        let Some((x1, y1, x2, y2)) = clamp_line_coords(self.width(), self.height(), x1, y1, x2, y2)
+        else {
+            return;
+        };
+        for (x, y) in line_points(x1, y1, x2, y2) {
+            self.draw_pixel(x, y, color);
+        }
+    }
+
+    /// Draws a horizontal line from `x1` to `x2` (both inclusive) onto the target.
+    fn draw_hline(&mut self, x1: isize, x2: isize, y: isize, color: RGBA) {
+        let (start_x, end_x) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
+        self.fill_spans(&[(y, start_x, end_x.saturating_add(1))], color);
+    }
+
+    /// Draws a vertical line from `y1` to `y2` (both inclusive) onto the target.
+    fn draw_vline(&mut self, x: isize, y1: isize, y2: isize, color: RGBA) {
+        if x < 0 || x >= self.width() as isize {
+            return;
+        }
+
+        let (start_y, end_y) = if y1 < y2 { (y1, y2) } else { (y2, y1) };
+        let start_y = start_y.clamp(0, self.height() as isize) as usize;
+        let end_y = end_y.saturating_add(1).clamp(0, self.height() as isize) as usize;
+        for y in start_y..end_y {
+            self.blend_span(y, x as usize, x as usize + 1, color);
+        }
+    }
+
+    /// Draws a line with specified width onto the target.
+    /// Drawing the line as a filled polygon.
+    fn draw_polyline(
+        &mut self,
+        x1: isize,
+        y1: isize,
+        x2: isize,
+        y2: isize,
+        width: u32,
+        color: RGBA,
+    ) {
+        if width == 0 {
+            return;
+        }
+
+        if width == 1 {
+            self.draw_line(x1, y1, x2, y2, color);
+            return;
+        }
+
+        let vertices = polyline_vertices(self.width(), self.height(), x1, y1, x2, y2, width);
+        let mut spans = SpanRasterizer::new();
+        spans.add_polygon(&vertices, true);
+        self.fill_spans(&spans.merged(), color);
+    }
+
+    /// Draws a line with specified width and capped ends onto the target.
+    /// Drawing the line as a filled polygon with circles on both ends.
+    fn draw_polyline_capped(
+        &mut self,
+        x1: isize,
+        y1: isize,
+        x2: isize,
+        y2: isize,
+        width: u32,
+        color: RGBA,
+    ) {
+        if width <= 1 {
+            self.draw_polyline(x1, y1, x2, y2, width, color);
+            return;
+        }
+
+        let vertices = polyline_vertices(self.width(), self.height(), x1, y1, x2, y2, width);
+        let mut spans = SpanRasterizer::new();
+        spans.add_polygon(&vertices, true);
+        spans.add_circle(x1, y1, width / 2);
+        spans.add_circle(x2, y2, width / 2);
+        self.fill_spans(&spans.merged(), color);
+    }
+
+    /// Draws an anti-aliased stroke with the specified width through a list of points onto the target.
+    ///
+    /// The stroke has round caps and joins. Every pixel is blended once even where caps, joins and segments overlap.
+    fn draw_stroke_aa(&mut self, points: &[(isize, isize)], width: u32, color: RGBA) {
+        let Some((stroke, (x, y, w, h))) = Stroke::new(points, width, self.width(), self.height())
+        else {
+            return;
+        };
+
+        for py in y..y + h {
+            for px in x..x + w {
+                let a = stroke.alpha(px, py, color.a);
+                if a > 0 {
+                    self.blend_span(py, px, px + 1, RGBA { a, ..color });
+                }
+            }
+        }
+    }
+
+    /// Draws a circle onto the target.
+    fn draw_circle(&mut self, x: isize, y: isize, r: u32, color: RGBA) {
+        if r == 0 {
+            return;
+        }
+
+        for (x, y) in circle_points(x, y, r) {
+            self.draw_pixel(x, y, color);
+        }
+    }
+
+    /// Draws a solid circle onto the target.
+    fn draw_circle_solid(&mut self, x: isize, y: isize, r: u32, color: RGBA) {
+        if r == 0 {
+            return;
+        }
+
+        let mut spans = SpanRasterizer::new();
+        spans.add_circle(x, y, r);
+        self.fill_spans(&spans.merged(), color);
+    }
+
+    /// Draws a solid axis aligned rectangle onto the target.
+    fn fill_rect(&mut self, x: isize, y: isize, width: usize, height: usize, color: RGBA) {
+        let Some((x, y, w, h)) = Rect::new(x, y, width, height).clip(self.width(), self.height())
+        else {
+            return;
+        };
+
+        for row in y..y + h {
+            self.blend_span(row, x, x + w, color);
+        }
+    }
+
+    /// Draws a polygon onto the target.
+    fn draw_polygon(&mut self, vertices: &[(isize, isize)], color: RGBA) {
+        if vertices.is_empty() {
+            return;
+        }
+
+        for i in 1..vertices.len() {
+            let (x1, y1) = vertices[i];
+            let (x2, y2) = vertices[i - 1];
+            self.draw_line(x1, y1, x2, y2, color);
+        }
+
+        let (x1, y1) = vertices[0];
+        let (x2, y2) = vertices[vertices.len() - 1];
+        self.draw_line(x1, y1, x2, y2, color);
+    }
+
+    /// Draws a solid polygon onto the target.
+    ///
+    /// The vertices of the polygon have to be given in the specified order (clockwise / anti-clockwise).
+    fn draw_polygon_solid(&mut self, vertices: &[(isize, isize)], clockwise: bool, color: RGBA) {
+        if vertices.is_empty() {
+            return;
+        }
+
+        let mut spans = SpanRasterizer::new();
+        spans.add_polygon(vertices, clockwise);
+        self.fill_spans(&spans.merged(), color);
+    }
+}
+
+/// Coverage of an anti-aliased stroke with round caps and joins.
+pub(crate) struct Stroke {
+    segments: Vec<((f32, f32), (f32, f32))>,
+    half: f32,
+}
+
+impl Stroke {
+    /// Creates the stroke through a list of points together with the part of a target of the specified size
+    /// it may cover as `(x, y, width, height)`.
+    ///
+    /// Returns `None` if the stroke is empty or doesn't touch the target.
+    pub(crate) fn new(
+        points: &[(isize, isize)],
+        width: u32,
+        target_width: usize,
+        target_height: usize,
+    ) -> Option<(Self, (usize, usize, usize, usize))> {
+        if points.is_empty() || width == 0 {
+            return None;
+        }
+
+        let to_f32 = |(x, y): (isize, isize)| (x as f32, y as f32);
+        let segments: Vec<_> = match points.len() {
+            1 => vec![(to_f32(points[0]), to_f32(points[0]))],
+            _ => points
+                .windows(2)
+                .map(|w| (to_f32(w[0]), to_f32(w[1])))
+                .collect(),
+        };
+
+        let half = width as f32 / 2.0;
+        let pad = half.ceil() as isize + 1;
+        let min_x = points.iter().map(|p| p.0).min().unwrap() - pad;
+        let max_x = points.iter().map(|p| p.0).max().unwrap() + pad;
+        let min_y = points.iter().map(|p| p.1).min().unwrap() - pad;
+        let max_y = points.iter().map(|p| p.1).max().unwrap() + pad;
+        let bounds = Rect::new(
+            min_x,
+            min_y,
+            (max_x - min_x + 1) as usize,
+            (max_y - min_y + 1) as usize,
+        );
+        let bounds = bounds.clip(target_width, target_height)?;
+
+        Some((Self { segments, half }, bounds))
+    }
+
+    /// Returns the alpha a color with alpha `a` is blended onto the pixel at the specified position with.
+    pub(crate) fn alpha(&self, x: usize, y: usize, a: u8) -> u8 {
+        let p = (x as f32, y as f32);
+        let distance = self
+            .segments
+            .iter()
+            .map(|&(a, b)| segment_distance(p, a, b))
+            .fold(f32::INFINITY, f32::min);
+
+        let coverage = (self.half + 0.5 - distance).clamp(0.0, 1.0);
+        (a as f32 * coverage).round() as u8
+    }
+}
+
+/// Returns the distance of a point to the line segment between `a` and `b`.
+fn segment_distance(p: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    let t = match length_sq > 0.0 {
+        true => (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length_sq).clamp(0.0, 1.0),
+        false => 0.0,
+    };
+    let (cx, cy) = (a.0 + t * dx, a.1 + t * dy);
+    ((p.0 - cx).powi(2) + (p.1 - cy).powi(2)).sqrt()
+}
+
+/// Computes the corners of a line with the specified width clamped into a target of the specified size.
+fn polyline_vertices(
+    target_width: usize,
+    target_height: usize,
+    x1: isize,
+    y1: isize,
+    x2: isize,
+    y2: isize,
+    width: u32,
+) -> Vec<(isize, isize)> {
+    // a wide line can still reach into the target even if its center line misses it
+    let (x1, y1, x2, y2) =
+        clamp_line_coords(target_width, target_height, x1, y1, x2, y2).unwrap_or((x1, y1, x2, y2));
+
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+
+    let d_len = ((dx * dx + dy * dy) as f32).sqrt();
+    let dx_n = dx as f32 / d_len;
+    let dy_n = dy as f32 / d_len;
+
+    let v1 = (
+        x1 - (dy_n * width as f32 / 2.0).round() as isize,
+        y1 + (dx_n * width as f32 / 2.0).round() as isize,
+    );
+    let v2 = (
+        x1 + (dy_n * width as f32 / 2.0).round() as isize,
+        y1 - (dx_n * width as f32 / 2.0).round() as isize,
+    );
+    let v3 = (
+        x2 + (dy_n * width as f32 / 2.0).round() as isize,
+        y2 - (dx_n * width as f32 / 2.0).round() as isize,
+    );
+    let v4 = (
+        x2 - (dy_n * width as f32 / 2.0).round() as isize,
+        y2 + (dx_n * width as f32 / 2.0).round() as isize,
+    );
+
+    vec![v1, v2, v3, v4]
+}
+
+/// Clips the specified coordinates of a line to a target of the specified size and returns them.
+///
+/// Returns `None` if the line doesn't cross the target.
+fn clamp_line_coords(
+    width: usize,
+    height: usize,
+    x1: isize,
+    y1: isize,
+    x2: isize,
+    y2: isize,
+) -> Option<(isize, isize, isize, isize)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let p1_inside = x1 >= 0 && x1 < width as isize && y1 >= 0 && y1 < height as isize;
+    let p2_inside = x2 >= 0 && x2 < width as isize && y2 >= 0 && y2 < height as isize;
+
+    if p1_inside && p2_inside {
+        return Some((x1, y1, x2, y2));
+    }
+
+    // Liang-Barsky against the area covered by the pixel centers, parameterized from 0.0 at p1 to 1.0 at p2
+    let (x1_f, y1_f) = (x1 as f64, y1 as f64);
+    let (dx, dy) = (x2 as f64 - x1_f, y2 as f64 - y1_f);
+    let (max_x, max_y) = (width as f64 - 0.5, height as f64 - 0.5);
+
+    let mut t_start = 0.0f64;
+    let mut t_end = 1.0f64;
+    for (p, q) in [
+        (-dx, x1_f + 0.5),
+        (dx, max_x - x1_f),
+        (-dy, y1_f + 0.5),
+        (dy, max_y - y1_f),
+    ] {
+        match p == 0.0 {
+            true => {
+                if q < 0.0 {
+                    return None;
+                }
+            }
+            false => {
+                let t = q / p;
+                match p < 0.0 {
+                    true => t_start = t_start.max(t),
+                    false => t_end = t_end.min(t),
+                }
+            }
+        }
+    }
+
+    if t_start > t_end {
+        return None;
+    }
+
+    let point = |t: f64| {
+        (
+            (x1_f + t * dx).round().clamp(0.0, width as f64 - 1.0) as isize,
+            (y1_f + t * dy).round().clamp(0.0, height as f64 - 1.0) as isize,
+        )
+    };
+    let (x1, y1) = match p1_inside {
+        true => (x1, y1),
+        false => point(t_start),
+    };
+    let (x2, y2) = match p2_inside {
+        true => (x2, y2),
+        false => point(t_end),
+    };
+
+    Some((x1, y1, x2, y2))
+}
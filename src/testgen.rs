@@ -0,0 +1,225 @@
+use crate::canvas::{Canvas, Draw};
+use crate::color::{RGB, RGBA};
+use crate::commands::DrawCommand;
+use crate::path::Path;
+use crate::random::Rng;
+
+/// Seeded generator of arbitrary geometry for fuzzing [`Draw`] implementations.
+///
+/// Generated positions are spread over an area twice the size of the canvas centered on it,
+/// so shapes regularly cross the edges and exercise clipping.
+/// The same seed always generates the same shapes, so failures can be reproduced from the seed.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::canvas::Canvas;
+/// use drawing_stuff::testgen::{check_draw_invariants, ShapeGen};
+///
+/// const WIDTH: usize = 64;
+/// const HEIGHT: usize = 48;
+///
+/// for seed in 0..20 {
+///     let mut gen = ShapeGen::new(seed, WIDTH, HEIGHT);
+///     let command = gen.command();
+///
+///     assert_eq!(Ok(()), check_draw_invariants(&command, WIDTH, HEIGHT), "seed {seed}");
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ShapeGen {
+    rng: Rng,
+
+    width: usize,
+    height: usize,
+}
+
+impl ShapeGen {
+    /// Creates a new generator for a canvas of the specified size.
+    pub fn new(seed: u64, width: usize, height: usize) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            width,
+            height,
+        }
+    }
+
+    /// Returns a random position in and around the canvas.
+    pub fn point(&mut self) -> (isize, isize) {
+        let (w, h) = (self.width.max(1) as isize, self.height.max(1) as isize);
+        let x = self.rng.below(2 * w as usize) as isize - w / 2;
+        let y = self.rng.below(2 * h as usize) as isize - h / 2;
+        (x, y)
+    }
+
+    /// Returns a random position in and around the canvas with sub-pixel precision.
+    pub fn point_f32(&mut self) -> (f32, f32) {
+        let (w, h) = (self.width.max(1) as f32, self.height.max(1) as f32);
+        (
+            self.rng.range_f32(-w / 2.0, w * 1.5),
+            self.rng.range_f32(-h / 2.0, h * 1.5),
+        )
+    }
+
+    /// Returns a random size between zero and the larger side of the canvas.
+    pub fn size(&mut self) -> usize {
+        self.rng.below(self.width.max(self.height) + 1)
+    }
+
+    /// Returns a random opaque color.
+    pub fn rgb(&mut self) -> RGB {
+        let [r, g, b, ..] = self.rng.next_u64().to_le_bytes();
+        RGB { r, g, b }
+    }
+
+    /// Returns a random color, which is fully transparent or opaque more often than a uniform choice would be.
+    pub fn rgba(&mut self) -> RGBA {
+        let [r, g, b, a, choice, ..] = self.rng.next_u64().to_le_bytes();
+        let a = match choice % 4 {
+            0 => 0,
+            1 => 255,
+            _ => a,
+        };
+        RGBA { r, g, b, a }
+    }
+
+    /// Returns a random, possibly self-intersecting or degenerate, polygon with up to `max_vertices` vertices.
+    pub fn polygon(&mut self, max_vertices: usize) -> Vec<(isize, isize)> {
+        let count = self.rng.below(max_vertices + 1);
+        (0..count).map(|_| self.point()).collect()
+    }
+
+    /// Returns a random simple polygon with vertices in clockwise order (on screen) around a random center.
+    pub fn star_polygon(&mut self, vertices: usize) -> Vec<(isize, isize)> {
+        let (cx, cy) = self.point_f32();
+        let max_radius = self.size().max(1) as f32;
+
+        (0..vertices)
+            .map(|i| {
+                let angle = i as f32 / vertices as f32 * std::f32::consts::TAU;
+                let radius = self.rng.range_f32(0.2, 1.0) * max_radius;
+                (
+                    (cx + angle.cos() * radius).round() as isize,
+                    (cy + angle.sin() * radius).round() as isize,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns a random path with up to `max_points` points.
+    pub fn path(&mut self, max_points: usize) -> Path {
+        let count = self.rng.below(max_points + 1);
+        Path::new((0..count).map(|_| self.point_f32()).collect())
+    }
+
+    /// Returns a random thick stroke.
+    pub fn stroke(&mut self) -> DrawCommand {
+        let ((x1, y1), (x2, y2)) = (self.point(), self.point());
+        DrawCommand::Polyline {
+            x1,
+            y1,
+            x2,
+            y2,
+            width: self.rng.below(self.width.max(self.height) / 4 + 2) as u32,
+            capped: self.rng.below(2) == 0,
+            color: self.rgba(),
+        }
+    }
+
+    /// Returns a random drawing command of any kind.
+    pub fn command(&mut self) -> DrawCommand {
+        match self.rng.below(7) {
+            0 => DrawCommand::Fill { color: self.rgb() },
+            1 => {
+                let (x, y) = self.point();
+                DrawCommand::Pixel {
+                    x,
+                    y,
+                    color: self.rgba(),
+                }
+            }
+            2 => {
+                let ((x1, y1), (x2, y2)) = (self.point(), self.point());
+                DrawCommand::Line {
+                    x1,
+                    y1,
+                    x2,
+                    y2,
+                    color: self.rgba(),
+                }
+            }
+            3 => self.stroke(),
+            4 => {
+                let (x, y) = self.point();
+                DrawCommand::Circle {
+                    x,
+                    y,
+                    r: self.size() as u32,
+                    solid: self.rng.below(2) == 0,
+                    color: self.rgba(),
+                }
+            }
+            5 => {
+                let (x, y) = self.point();
+                DrawCommand::Rect {
+                    x,
+                    y,
+                    width: self.size(),
+                    height: self.size(),
+                    color: self.rgba(),
+                }
+            }
+            _ => {
+                let vertices = self.rng.below(9);
+                DrawCommand::Polygon {
+                    vertices: self.star_polygon(vertices),
+                    clockwise: true,
+                    solid: self.rng.below(2) == 0,
+                    color: self.rgba(),
+                }
+            }
+        }
+    }
+}
+
+/// An invariant of drawing onto a canvas broken by a [`Draw`] implementation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// Drawing changed the size of the canvas.
+    SizeChanged,
+    /// Drawing the same drawable onto equal canvases gave different results.
+    NonDeterministic,
+}
+
+/// Draws a drawable onto canvases of the specified size and checks that it upholds the invariants of the canvas.
+///
+/// Panics inside of the drawable, e.g. from out of bounds accesses, are not caught and fail the calling test.
+pub fn check_draw_invariants<T>(
+    drawable: &T,
+    width: usize,
+    height: usize,
+) -> Result<(), InvariantViolation>
+where
+    T: Draw + ?Sized,
+{
+    let mut background = Canvas::new(width, height);
+    background.fill(RGB {
+        r: 40,
+        g: 80,
+        b: 120,
+    });
+
+    let mut first = background.clone();
+    let mut second = background;
+    drawable.draw(&mut first);
+    drawable.draw(&mut second);
+
+    if first.width() != width || first.height() != height || first.buffer().len() != width * height
+    {
+        return Err(InvariantViolation::SizeChanged);
+    }
+    if !first.approx_eq(&second, 0) {
+        return Err(InvariantViolation::NonDeterministic);
+    }
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use crate::canvas::{Canvas, Filter};
+use crate::color::{RGB, RGBA};
+use crate::mask::Mask;
+
+/// A 2D affine transformation mapping `(x, y)` to `(a * x + c * y + e, b * x + d * y + f)`.
+///
+/// # Examples
+///
+/// ```
+/// use drawing_stuff::transform::Affine;
+///
+/// let transform = Affine::scale(2.0, 2.0).then(Affine::translate(10.0, 0.0));
+///
+/// assert_eq!((12.0, 4.0), transform.apply((1.0, 2.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+    pub e: f32,
+    pub f: f32,
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Affine {
+    /// The transformation leaving every point unchanged.
+    pub const IDENTITY: Affine = Affine {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    /// Creates a translation by the specified offset.
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self {
+            e: x,
+            f: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a scaling around the origin.
+    pub fn scale(x: f32, y: f32) -> Self {
+        Self {
+            a: x,
+            d: y,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a rotation around the origin by an angle in radians.
+    ///
+    /// As the y axis of a canvas points down, positive angles rotate clockwise on screen.
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Creates a rotation around the specified point by an angle in radians.
+    pub fn rotate_around(angle: f32, x: f32, y: f32) -> Self {
+        Self::translate(-x, -y)
+            .then(Self::rotate(angle))
+            .then(Self::translate(x, y))
+    }
+
+    /// Returns the transformation applying `self` first and `other` afterwards.
+    pub fn then(self, other: Affine) -> Self {
+        Self {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    /// Returns the determinant of the linear part, which is negative if the transformation mirrors.
+    pub fn determinant(&self) -> f32 {
+        self.a * self.d - self.b * self.c
+    }
+
+    /// Transforms a point.
+    pub fn apply(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    /// Returns the transformation undoing this one.
+    ///
+    /// Returns `None` if the transformation collapses the plane onto a line or point and can't be undone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::transform::Affine;
+    ///
+    /// let transform = Affine::scale(2.0, 4.0).then(Affine::translate(10.0, 0.0));
+    /// let inverse = transform.inverse().unwrap();
+    ///
+    /// assert_eq!((1.0, 2.0), inverse.apply(transform.apply((1.0, 2.0))));
+    /// assert_eq!(None, Affine::scale(0.0, 1.0).inverse());
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let (a, b, c, d) = (self.d / det, -self.b / det, -self.c / det, self.a / det);
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            e: -(a * self.e + c * self.f),
+            f: -(b * self.e + d * self.f),
+        })
+    }
+}
+
+impl Canvas {
+    /// Lifts the pixels covered by a selection, transforms them and composites them back onto the canvas,
+    /// e.g. to move, rotate or scale a selected part of an image.
+    ///
+    /// The selection is a [`Mask`] placed at the top-left corner of the canvas, partially covered pixels are lifted partially.
+    /// The area the pixels were lifted from is filled with `fill_behind`, or keeps its content if `None`.
+    /// Transformed pixels are resampled bilinearly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use drawing_stuff::canvas::Canvas;
+    /// use drawing_stuff::color::{BLACK, WHITE};
+    /// use drawing_stuff::mask::Mask;
+    /// use drawing_stuff::transform::Affine;
+    ///
+    /// const WIDTH: usize = 1080;
+    /// const HEIGHT: usize = 720;
+    ///
+    /// let mut canvas = Canvas::new(WIDTH, HEIGHT);
+    /// canvas.draw_circle_solid(200, 100, 30, WHITE);
+    ///
+    /// let mut selection = Mask::new(WIDTH, HEIGHT);
+    /// selection.fill_circle(200, 100, 40);
+    ///
+    /// // move the circle 300 pixels to the right
+    /// canvas.transform_region(&selection, Affine::translate(300.0, 0.0), Some(BLACK.to_rgb().0));
+    ///
+    /// assert_eq!(0, canvas.get_at(200, 100).unwrap().r);
+    /// assert_eq!(255, canvas.get_at(500, 100).unwrap().r);
+    ///
+    /// // an empty selection leaves the canvas untouched
+    /// canvas.transform_region(&Mask::new(0, HEIGHT), Affine::translate(300.0, 0.0), None);
+    /// ```
+    pub fn transform_region(
+        &mut self,
+        selection: &Mask,
+        transform: Affine,
+        fill_behind: Option<RGB>,
+    ) {
+        let Some(inverse) = transform.inverse() else {
+            return;
+        };
+        let width = self.width().min(selection.width());
+        let height = self.height().min(selection.height());
+        let Some(bounds) = selection_bounds(selection, width, height) else {
+            return;
+        };
+
+        let source = self.clone();
+        if let Some(color) = fill_behind {
+            let data = selection.data();
+            self.for_each_row_mut(bounds.1..bounds.3, |y, row| {
+                for x in bounds.0..bounds.2 {
+                    let alpha = data[y * selection.width() + x];
+                    row[x] = row[x].add_rgba(RGBA::new(color.r, color.g, color.b, alpha));
+                }
+            });
+        }
+
+        // bounds of the transformed selection
+        let corners = [
+            (bounds.0, bounds.1),
+            (bounds.2, bounds.1),
+            (bounds.0, bounds.3),
+            (bounds.2, bounds.3),
+        ]
+        .map(|(x, y)| transform.apply((x as f32, y as f32)));
+        let clamp_x = |v: f32| v.clamp(0.0, self.width() as f32) as usize;
+        let clamp_y = |v: f32| v.clamp(0.0, self.height() as f32) as usize;
+        let min_x = clamp_x(
+            corners
+                .iter()
+                .map(|c| c.0)
+                .fold(f32::INFINITY, f32::min)
+                .floor(),
+        );
+        let max_x = clamp_x(
+            corners
+                .iter()
+                .map(|c| c.0)
+                .fold(f32::NEG_INFINITY, f32::max)
+                .ceil(),
+        );
+        let min_y = clamp_y(
+            corners
+                .iter()
+                .map(|c| c.1)
+                .fold(f32::INFINITY, f32::min)
+                .floor(),
+        );
+        let max_y = clamp_y(
+            corners
+                .iter()
+                .map(|c| c.1)
+                .fold(f32::NEG_INFINITY, f32::max)
+                .ceil(),
+        );
+
+        self.for_each_row_mut(min_y..max_y, |y, row| {
+            for (x, pixel) in row.iter_mut().enumerate().take(max_x).skip(min_x) {
+                let (sx, sy) = inverse.apply((x as f32 + 0.5, y as f32 + 0.5));
+                let coverage = sample_coverage(selection, width, height, sx, sy);
+                if coverage == 0 {
+                    continue;
+                }
+
+                let color = source.sample(sx, sy, Filter::Bilinear);
+                *pixel = pixel.add_rgba(RGBA::new(color.r, color.g, color.b, coverage));
+            }
+        });
+    }
+}
+
+/// Returns the bounds `(min_x, min_y, max_x, max_y)` of the covered part of a selection, with exclusive maximums.
+fn selection_bounds(
+    selection: &Mask,
+    width: usize,
+    height: usize,
+) -> Option<(usize, usize, usize, usize)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for (y, row) in selection
+        .data()
+        .chunks(selection.width())
+        .take(height)
+        .enumerate()
+    {
+        for (x, _) in row[..width].iter().enumerate().filter(|(_, &c)| c > 0) {
+            let b = bounds.get_or_insert((x, y, x + 1, y + 1));
+            *b = (b.0.min(x), b.1.min(y), b.2.max(x + 1), b.3.max(y + 1));
+        }
+    }
+    bounds
+}
+
+/// Bilinearly samples the coverage of a selection at a position given in pixel space, everything outside counts as not covered.
+fn sample_coverage(selection: &Mask, width: usize, height: usize, x: f32, y: f32) -> u8 {
+    let (x, y) = (x - 0.5, y - 0.5);
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as isize, y0 as isize);
+
+    let at = |x: isize, y: isize| match x >= 0
+        && y >= 0
+        && (x as usize) < width
+        && (y as usize) < height
+    {
+        true => selection.data()[y as usize * selection.width() + x as usize] as f32,
+        false => 0.0,
+    };
+    let top = at(x0, y0) + (at(x0 + 1, y0) - at(x0, y0)) * tx;
+    let bottom = at(x0, y0 + 1) + (at(x0 + 1, y0 + 1) - at(x0, y0 + 1)) * tx;
+    (top + (bottom - top) * ty).round() as u8
+}